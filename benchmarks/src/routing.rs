@@ -1,11 +1,11 @@
 use criterion::Criterion;
 
-use rocket::{handler, Request, Data, Route, Config};
+use rocket::{route, Request, Data, Route, Config};
 use rocket::http::{Method, RawStr, ContentType, Accept, Status};
 use rocket::local::blocking::{Client, LocalRequest};
 
-fn dummy_handler<'r>(req: &'r Request, _: Data) -> handler::HandlerFuture<'r> {
-    handler::Outcome::from(req, ()).pin()
+fn dummy_handler<'r>(req: &'r Request, _: Data) -> route::BoxFuture<'r> {
+    route::Outcome::from(req, ()).pin()
 }
 
 fn parse_routes_table(table: &str) -> Vec<Route> {
@@ -76,7 +76,7 @@ fn client(routes: Vec<Route>) -> Client {
         profile: Config::RELEASE_PROFILE,
         log_level: rocket::config::LogLevel::Off,
         cli_colors: false,
-        ctrlc: false,
+        shutdown: rocket::config::Shutdown { ctrlc: false, ..Default::default() },
         ..Default::default()
     };
 
@@ -117,3 +117,63 @@ pub fn bench_bitwarden_routes(c: &mut Criterion) {
     }));
 }
 
+
+// Generates a table of `n` routes in the shape of a large REST API: a handful of
+// static resources, each with nested collections and dynamic identifiers.
+fn generate_large_routes_table(n: usize) -> String {
+    let methods = ["GET", "POST", "PUT", "DELETE"];
+    let mut table = String::new();
+    for i in 0.. {
+        for (j, method) in methods.iter().enumerate() {
+            if table.lines().count() == n {
+                return table;
+            }
+
+            let line = match j {
+                0 => format!("{} /api/v{}/resource{}\n", method, i % 3, i),
+                1 => format!("{} /api/v{}/resource{}/<id>\n", method, i % 3, i),
+                2 => format!("{} /api/v{}/resource{}/<id>/sub/<sub>\n", method, i % 3, i),
+                _ => format!("{} /api/v{}/resource{}/<id>/<path..>\n", method, i % 3, i),
+            };
+
+            table.push_str(&line);
+        }
+    }
+
+    table
+}
+
+#[criterion]
+pub fn bench_large_generated_routes(c: &mut Criterion) {
+    let table = generate_large_routes_table(640);
+    let routes = parse_routes_table(&table);
+    let client = client(routes.clone());
+    let requests = generate_matching_requests(&client, &routes);
+    c.bench_function("generated-640.routes", |b| b.iter(|| {
+        for request in requests.clone() {
+            let response = request.dispatch();
+            assert_eq!(response.status(), Status::Ok);
+        }
+    }));
+}
+
+// Matches the same requests against the same routes, without dispatching, by
+// checking every route in turn and by walking the router's trees.
+#[criterion]
+pub fn bench_large_generated_routes_matching(c: &mut Criterion) {
+    let table = generate_large_routes_table(640);
+    let routes = parse_routes_table(&table);
+    let client = client(routes.clone());
+    let requests = generate_matching_requests(&client, &routes);
+    let mut group = c.benchmark_group("generated-640.routes-matching");
+    for &(name, linear) in &[("linear", true), ("tree", false)] {
+        group.bench_function(name, |b| b.iter(|| {
+            for request in &requests {
+                let count = client.rocket().__count_matching_routes(request.inner(), linear);
+                assert!(count > 0);
+            }
+        }));
+    }
+
+    group.finish();
+}
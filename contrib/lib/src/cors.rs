@@ -0,0 +1,403 @@
+//! Fairing for Cross-Origin Resource Sharing (CORS).
+//!
+//! See the [`Cors`] type for further details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `cors` feature is enabled. Enable it
+//! in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["cors"]
+//! ```
+
+use std::time::Duration;
+
+use regex::Regex;
+
+use rocket::{Rocket, Build, Request, Response, Data, Route, route};
+use rocket::http::{Method, Status, Header};
+use rocket::fairing::{self, Fairing, Info, Kind};
+
+/// A [`Fairing`](../../rocket/fairing/trait.Fairing.html) that implements
+/// Cross-Origin Resource Sharing (CORS).
+///
+/// # Usage
+///
+/// Construct a `Cors` with [`Cors::new()`], which allows no origins, and
+/// configure it with the builder methods below. Then, attach it:
+///
+/// ```rust
+/// # extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use std::time::Duration;
+///
+/// use rocket::http::Method;
+/// use rocket_contrib::cors::{Cors, AllowedOrigin};
+///
+/// let cors = Cors::new()
+///     .allow_origin(AllowedOrigin::exact("https://rocket.rs"))
+///     .allow_origin(AllowedOrigin::wildcard("https://*.rocket.rs"))
+///     .allow_origin(AllowedOrigin::regex(r"^https://rocket-\d+\.dev$").unwrap())
+///     .allow_methods(&[Method::Get, Method::Post])
+///     .allow_headers(&["Content-Type", "Authorization"])
+///     .expose_headers(&["X-Request-Id"])
+///     .allow_credentials(true)
+///     .max_age(Duration::from_secs(3600));
+///
+/// rocket::build()
+///     // ...
+///     .attach(cors)
+/// # ;
+/// ```
+///
+/// The fairing adds CORS headers to every response to a request with an
+/// allowed `Origin`. Responses to requests with no `Origin` header, or with an
+/// origin that is not allowed, are left untouched.
+///
+/// # Preflight Requests
+///
+/// Preflight requests, `OPTIONS` requests with an `Access-Control-Request-Method`
+/// header, are answered automatically with a `204 No Content`. The methods
+/// allowed for the request's path are those of the mounted routes that match
+/// the path, as reported by
+/// [`Rocket::route_methods()`](rocket::Rocket::route_methods()), further
+/// restricted to those set via [`Cors::allow_methods()`], if any. A preflight
+/// request for a path no route matches receives a `404`.
+///
+/// Preflight requests are answered by a catch-all `OPTIONS` route mounted with
+/// the lowest possible precedence. An application's own `OPTIONS` routes thus
+/// take precedence; the fairing adds CORS headers to their responses as well.
+#[derive(Debug, Clone, Default)]
+pub struct Cors {
+    origins: Vec<AllowedOrigin>,
+    methods: Option<Vec<Method>>,
+    headers: Option<Vec<String>>,
+    exposed: Vec<String>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+/// An origin, or set of origins, allowed by a [`Cors`] fairing.
+#[derive(Debug, Clone)]
+pub enum AllowedOrigin {
+    /// Any origin.
+    Any,
+    /// Exactly the given origin, like `https://rocket.rs`.
+    Exact(String),
+    /// Origins matching a pattern with a single `*`, like
+    /// `https://*.rocket.rs`. The `*` matches one or more characters other
+    /// than `/`.
+    Wildcard(String),
+    /// Origins matched by a regular expression. The expression must match the
+    /// _entire_ origin; prefer [`AllowedOrigin::regex()`], which anchors it.
+    Regex(Regex),
+}
+
+impl AllowedOrigin {
+    /// An `AllowedOrigin` allowing exactly `origin`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::AllowedOrigin;
+    ///
+    /// let origin = AllowedOrigin::exact("https://rocket.rs");
+    /// assert!(origin.matches("https://rocket.rs"));
+    /// assert!(!origin.matches("http://rocket.rs"));
+    /// ```
+    pub fn exact<S: Into<String>>(origin: S) -> Self {
+        AllowedOrigin::Exact(origin.into())
+    }
+
+    /// An `AllowedOrigin` allowing origins matching `pattern`, which may
+    /// contain a single `*`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::AllowedOrigin;
+    ///
+    /// let origin = AllowedOrigin::wildcard("https://*.rocket.rs");
+    /// assert!(origin.matches("https://api.rocket.rs"));
+    /// assert!(!origin.matches("https://rocket.rs"));
+    /// assert!(!origin.matches("https://evil.com/.rocket.rs"));
+    /// ```
+    pub fn wildcard<S: Into<String>>(pattern: S) -> Self {
+        AllowedOrigin::Wildcard(pattern.into())
+    }
+
+    /// An `AllowedOrigin` allowing origins matched in their entirety by the
+    /// regular expression `regex`. Returns an error if `regex` is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::AllowedOrigin;
+    ///
+    /// let origin = AllowedOrigin::regex(r"https://rocket-\d+\.dev").unwrap();
+    /// assert!(origin.matches("https://rocket-12.dev"));
+    /// assert!(!origin.matches("https://rocket-x.dev"));
+    /// assert!(!origin.matches("https://rocket-12.dev.evil.com"));
+    /// ```
+    pub fn regex(regex: &str) -> Result<Self, regex::Error> {
+        Ok(AllowedOrigin::Regex(Regex::new(&format!("^(?:{})$", regex))?))
+    }
+
+    /// Returns `true` if `origin` is allowed by `self`.
+    pub fn matches(&self, origin: &str) -> bool {
+        match self {
+            AllowedOrigin::Any => true,
+            AllowedOrigin::Exact(allowed) => allowed.eq_ignore_ascii_case(origin),
+            AllowedOrigin::Regex(regex) => regex.find(origin)
+                .map_or(false, |m| m.start() == 0 && m.end() == origin.len()),
+            AllowedOrigin::Wildcard(pattern) => match pattern.find('*') {
+                Some(i) => {
+                    let (prefix, suffix) = (&pattern[..i], &pattern[(i + 1)..]);
+                    origin.len() > prefix.len() + suffix.len()
+                        && starts_with_ignore_case(origin, prefix)
+                        && ends_with_ignore_case(origin, suffix)
+                        && !origin[prefix.len()..(origin.len() - suffix.len())].contains('/')
+                }
+                None => pattern.eq_ignore_ascii_case(origin),
+            }
+        }
+    }
+}
+
+fn starts_with_ignore_case(string: &str, prefix: &str) -> bool {
+    let (string, prefix) = (string.as_bytes(), prefix.as_bytes());
+    string.len() >= prefix.len() && string[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn ends_with_ignore_case(string: &str, suffix: &str) -> bool {
+    let (string, suffix) = (string.as_bytes(), suffix.as_bytes());
+    string.len() >= suffix.len()
+        && string[(string.len() - suffix.len())..].eq_ignore_ascii_case(suffix)
+}
+
+impl Cors {
+    /// Returns a `Cors` fairing allowing no origins.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::Cors;
+    ///
+    /// let cors = Cors::new();
+    /// ```
+    pub fn new() -> Self {
+        Cors::default()
+    }
+
+    /// Allows requests from `origin`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::{Cors, AllowedOrigin};
+    ///
+    /// let cors = Cors::new().allow_origin(AllowedOrigin::Any);
+    /// ```
+    pub fn allow_origin(mut self, origin: AllowedOrigin) -> Self {
+        self.origins.push(origin);
+        self
+    }
+
+    /// Restricts the methods allowed in cross-origin requests to `methods`.
+    /// By default, every method of a route matching the request is allowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Method;
+    /// use rocket_contrib::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_methods(&[Method::Get, Method::Post]);
+    /// ```
+    pub fn allow_methods(mut self, methods: &[Method]) -> Self {
+        self.methods = Some(methods.to_vec());
+        self
+    }
+
+    /// Restricts the request headers allowed in cross-origin requests to
+    /// `headers`. By default, all requested headers are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_headers(&["Content-Type", "Authorization"]);
+    /// ```
+    pub fn allow_headers(mut self, headers: &[&str]) -> Self {
+        self.headers = Some(headers.iter().map(|h| h.to_string()).collect());
+        self
+    }
+
+    /// Sets the response headers exposed to cross-origin requests via
+    /// `Access-Control-Expose-Headers`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::Cors;
+    ///
+    /// let cors = Cors::new().expose_headers(&["X-Request-Id"]);
+    /// ```
+    pub fn expose_headers(mut self, headers: &[&str]) -> Self {
+        self.exposed = headers.iter().map(|h| h.to_string()).collect();
+        self
+    }
+
+    /// Sets whether credentials are allowed in cross-origin requests via
+    /// `Access-Control-Allow-Credentials`. Defaults to `false`. When enabled,
+    /// the request's origin, never `*`, is sent as the allowed origin.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_credentials(true);
+    /// ```
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.credentials = allow;
+        self
+    }
+
+    /// Sets how long preflight responses may be cached via
+    /// `Access-Control-Max-Age`. By default, no `Access-Control-Max-Age` is
+    /// sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_contrib::cors::Cors;
+    ///
+    /// let cors = Cors::new().max_age(Duration::from_secs(600));
+    /// ```
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// The methods allowed in a cross-origin request for `req`'s path.
+    fn allowed_methods(&self, req: &Request<'_>) -> Vec<Method> {
+        req.rocket().route_methods(req)
+            .into_iter()
+            .filter(|m| *m != Method::Options)
+            .filter(|m| self.methods.as_ref().map_or(true, |allowed| allowed.contains(m)))
+            .collect()
+    }
+
+    /// Adds the preflight headers to `res` if the preflight request `req` is
+    /// allowed. Returns `false` otherwise.
+    fn preflight(&self, req: &Request<'_>, res: &mut Response<'_>) -> bool {
+        let methods = self.allowed_methods(req);
+        let requested = req.headers().get_one("Access-Control-Request-Method")
+            .and_then(|m| m.parse::<Method>().ok());
+
+        if !requested.map_or(false, |m| methods.contains(&m)) {
+            warn_!("CORS: preflight for disallowed method in {}.", req);
+            return false;
+        }
+
+        let requested_headers = req.headers()
+            .get("Access-Control-Request-Headers")
+            .flat_map(|list| list.split(','))
+            .map(|h| h.trim())
+            .filter(|h| !h.is_empty());
+
+        let mut headers = vec![];
+        for header in requested_headers {
+            let allowed = self.headers.as_ref()
+                .map_or(true, |allowed| allowed.iter().any(|a| a.eq_ignore_ascii_case(header)));
+
+            if !allowed {
+                warn_!("CORS: preflight for disallowed header '{}' in {}.", header, req);
+                return false;
+            }
+
+            headers.push(header);
+        }
+
+        let methods = methods.iter().map(|m| m.as_str()).collect::<Vec<_>>();
+        res.set_header(Header::new("Access-Control-Allow-Methods", methods.join(", ")));
+        if !headers.is_empty() {
+            res.set_header(Header::new("Access-Control-Allow-Headers", headers.join(", ")));
+        }
+
+        if let Some(max_age) = self.max_age {
+            res.set_header(Header::new("Access-Control-Max-Age", max_age.as_secs().to_string()));
+        }
+
+        true
+    }
+}
+
+fn is_preflight(req: &Request<'_>) -> bool {
+    req.method() == Method::Options
+        && req.headers().contains("Origin")
+        && req.headers().contains("Access-Control-Request-Method")
+}
+
+/// Answers preflight requests for paths that some route matches.
+fn preflight_handler<'r>(req: &'r Request<'_>, data: Data) -> route::BoxFuture<'r> {
+    let routed = req.rocket().route_methods(req).iter().any(|m| *m != Method::Options);
+    let outcome = match is_preflight(req) && routed {
+        true => route::Outcome::from(req, Status::NoContent),
+        false => route::Outcome::forward(data),
+    };
+
+    Box::pin(async move { outcome })
+}
+
+#[rocket::async_trait]
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info { name: "CORS", kind: Kind::Ignite | Kind::Response }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut route = Route::ranked(isize::MAX, Method::Options, "/<_..>", preflight_handler);
+        route.name = Some("cors_preflight".into());
+        Ok(rocket.mount("/", vec![route]))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let origin = match req.headers().get_one("Origin") {
+            Some(origin) => origin,
+            None => return,
+        };
+
+        let allowed = self.origins.iter().find(|o| o.matches(origin));
+        let allows_any = match allowed {
+            Some(allowed) => matches!(allowed, AllowedOrigin::Any),
+            None => return,
+        };
+
+        if is_preflight(req) && !self.preflight(req, res) {
+            return;
+        }
+
+        if allows_any && !self.credentials {
+            res.set_header(Header::new("Access-Control-Allow-Origin", "*"));
+        } else {
+            res.set_header(Header::new("Access-Control-Allow-Origin", origin.to_string()));
+            res.adjoin_header(Header::new("Vary", "Origin"));
+        }
+
+        if self.credentials {
+            res.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
+        }
+
+        if !self.exposed.is_empty() && !is_preflight(req) {
+            res.set_header(Header::new("Access-Control-Expose-Headers", self.exposed.join(", ")));
+        }
+    }
+}
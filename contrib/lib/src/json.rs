@@ -21,8 +21,9 @@ use std::iter::FromIterator;
 use rocket::request::{Request, local_cache};
 use rocket::data::{ByteUnit, Data, FromData, Outcome};
 use rocket::response::{self, Responder, content};
-use rocket::http::Status;
+use rocket::http::{Status, MediaType};
 use rocket::form::prelude as form;
+use rocket::schema::{Describe, Schema};
 
 use serde::{Serialize, Serializer, Deserialize, Deserializer};
 
@@ -226,6 +227,17 @@ impl<T> DerefMut for Json<T> {
     }
 }
 
+/// Describes the value as JSON.
+impl<T: Describe> Describe for Json<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        Some(MediaType::JSON)
+    }
+}
+
 impl From<JsonError<'_>> for form::Error<'_> {
     fn from(e: JsonError<'_>) -> Self {
         match e {
@@ -346,6 +358,17 @@ impl<'r> Responder<'r, 'static> for JsonValue {
     }
 }
 
+/// Describes any JSON value.
+impl Describe for JsonValue {
+    fn schema() -> Schema {
+        Schema::any()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        Some(MediaType::JSON)
+    }
+}
+
 /// A macro to create ad-hoc JSON serializable values using JSON syntax.
 ///
 /// # Usage
@@ -24,6 +24,9 @@
 //! * [uuid](uuid) - UUID (de)serialization
 //! * [${database}_pool](databases) - Database Configuration and Pooling
 //! * [helmet](helmet) - Fairing for Security and Privacy Headers
+//! * [cors](cors) - Fairing for Cross-Origin Resource Sharing
+//! * [openapi](openapi) - Fairing for OpenAPI Document Generation
+//! * [webhook](webhook) - Signed Request Body Verification
 //!
 //! The recommend way to include features from this crate via Rocket in your
 //! project is by adding a `[dependencies.rocket_contrib]` section to your
@@ -49,6 +52,9 @@
 #[cfg(feature="uuid")] pub mod uuid;
 #[cfg(feature="databases")] pub mod databases;
 #[cfg(feature = "helmet")] pub mod helmet;
+#[cfg(feature = "cors")] pub mod cors;
+#[cfg(feature = "openapi")] pub mod openapi;
+#[cfg(feature = "webhook")] pub mod webhook;
 // TODO.async: Migrate compression, reenable this, tests, and add to docs.
 //#[cfg(any(feature="brotli_compression", feature="gzip_compression"))] pub mod compression;
 
@@ -0,0 +1,392 @@
+//! Fairing serving an OpenAPI 3 document generated from the mounted routes.
+//!
+//! See the [`OpenApi`] type for further details.
+//!
+//! # Enabling
+//!
+//! This module is only available when the `openapi` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["openapi"]
+//! ```
+//!
+//! The `openapi` feature enables Rocket's `operations` feature, which makes
+//! route attributes record the [`Operation`](rocket::schema::Operation) of
+//! each route the document is generated from.
+
+use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{json, Map, Value};
+
+use rocket::{Rocket, Build, Orbit, Request, Data, Route};
+use rocket::http::Method;
+use rocket::route::{Handler, Outcome};
+use rocket::response::content::{Html, Json};
+use rocket::fairing::{self, AdHoc, Fairing, Info, Kind};
+use rocket::schema::{Content, Location, Operation, Parameter, Schema};
+
+/// A [`Fairing`](../../rocket/fairing/trait.Fairing.html) that serves an
+/// [OpenAPI 3](https://spec.openapis.org/oas/v3.0.3) document describing an
+/// application's routes and, optionally, a Swagger UI page rendering it.
+///
+/// # Usage
+///
+/// Construct an `OpenApi` with [`OpenApi::new()`], optionally configure it
+/// with the builder methods below, and attach it:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket::schema::Describe;
+/// use rocket_contrib::json::Json;
+/// use rocket_contrib::openapi::OpenApi;
+///
+/// #[derive(serde::Deserialize, Describe)]
+/// struct Task {
+///     description: String,
+///     done: Option<bool>,
+/// }
+///
+/// #[post("/tasks", data = "<task>")]
+/// fn new(task: Json<Task>) { /* .. */ }
+///
+/// #[get("/tasks/<id>")]
+/// fn get(id: usize) -> Option<Json<Task>> {
+///     # None
+///     /* .. */
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![new, get])
+///         .attach(OpenApi::new("Tasks", "1.0.0").swagger_ui("/docs"))
+/// }
+/// ```
+///
+/// The document is served as JSON at `/openapi.json` or the path set via
+/// [`OpenApi::path()`]. It is generated from the [`Operation`] the route
+/// attributes record for each route, retrieved via [`Operation::of()`]: the
+/// schemas of the route's path and query parameters, of its data guard, and of
+/// its return type, as far as those types implement
+/// [`Describe`](rocket::schema::Describe). Routes without an `Operation`, such
+/// as manually constructed routes, are not documented.
+///
+/// The document is generated once, at liftoff, and the cached copy is served
+/// thereafter. Every route mounted before launch is documented, including
+/// those mounted after the fairing is attached, but routes added while Rocket
+/// is running are not. When several routes share a path and method, only the
+/// one with the lowest rank is documented.
+///
+/// The `operationId` of an operation is the name of the route's handler. If
+/// several handlers share a name, their names are prefixed with the path of
+/// the module they're defined in, as in `api_v1_get`, and any remaining
+/// duplicates, such as a handler mounted twice, are suffixed with a counter.
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    path: String,
+    swagger_ui: Option<String>,
+}
+
+impl OpenApi {
+    /// Returns a new `OpenApi` documenting an API named `title` at `version`,
+    /// the version of the API, not of OpenAPI. The document is served at
+    /// `/openapi.json` and no Swagger UI is served.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new("Tasks", "1.0.0");
+    /// ```
+    pub fn new<T: Into<String>, V: Into<String>>(title: T, version: V) -> Self {
+        OpenApi {
+            title: title.into(),
+            version: version.into(),
+            path: "/openapi.json".into(),
+            swagger_ui: None,
+        }
+    }
+
+    /// Sets the path at which the document is served to `path`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new("Tasks", "1.0.0").path("/api/spec.json");
+    /// ```
+    pub fn path<P: Into<String>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Serves a Swagger UI page rendering the document at `path`. The page
+    /// loads Swagger UI's scripts and styles from the `unpkg.com` CDN.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// let openapi = OpenApi::new("Tasks", "1.0.0").swagger_ui("/docs");
+    /// ```
+    pub fn swagger_ui<P: Into<String>>(mut self, path: P) -> Self {
+        self.swagger_ui = Some(path.into());
+        self
+    }
+
+    /// Returns the document describing `routes`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// #[get("/tasks/<id>")]
+    /// fn get(id: usize) -> String {
+    ///     # unimplemented!()
+    ///     /* .. */
+    /// }
+    ///
+    /// let routes = routes![get];
+    /// let document = OpenApi::new("Tasks", "1.0.0").document(&routes);
+    /// let operation = &document["paths"]["/tasks/{id}"]["get"];
+    /// assert_eq!(operation["parameters"][0]["name"], "id");
+    /// assert_eq!(operation["parameters"][0]["schema"]["type"], "integer");
+    /// assert_eq!(operation["operationId"], "get");
+    /// ```
+    pub fn document<'a, I>(&self, routes: I) -> Value
+        where I: IntoIterator<Item = &'a Route>
+    {
+        let mut routes = routes.into_iter()
+            .filter(|r| r.method != Method::Connect)
+            .filter_map(|r| Operation::of(r).map(|op| (r, op)))
+            .collect::<Vec<_>>();
+
+        // Sort fully so that operation IDs are assigned deterministically.
+        routes.sort_by(|(a, _), (b, _)| {
+            (a.rank, a.uri.path(), a.method.as_str())
+                .cmp(&(b.rank, b.uri.path(), b.method.as_str()))
+        });
+
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for name in routes.iter().filter_map(|(r, _)| r.name.as_deref()) {
+            *name_counts.entry(name).or_default() += 1;
+        }
+
+        let mut ids = HashSet::new();
+        let mut paths = Map::new();
+        for (route, operation) in &routes {
+            let item = paths.entry(openapi_path(route.uri.path()))
+                .or_insert_with(|| Value::Object(Map::new()));
+
+            let method = route.method.as_str().to_ascii_lowercase();
+            if let Some(item) = item.as_object_mut() {
+                if item.contains_key(&method) {
+                    continue;
+                }
+
+                let id = route.name.as_deref().map(|name| {
+                    let id = match (name_counts[name] > 1, operation.module) {
+                        (true, Some(module)) => format!("{}_{}", module.replace("::", "_"), name),
+                        _ => name.to_string(),
+                    };
+
+                    unique_id(&mut ids, id)
+                });
+
+                item.insert(method, operation_object(route, operation, id));
+            }
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": self.title, "version": self.version },
+            "paths": paths,
+        })
+    }
+}
+
+/// Converts a route path like `/tasks/<id>/<rest..>` into an OpenAPI path like
+/// `/tasks/{id}/{rest}`.
+fn openapi_path(path: &str) -> String {
+    let segments = path.split('/').map(|segment| {
+        if !(segment.starts_with('<') && segment.ends_with('>')) {
+            return segment.to_string();
+        }
+
+        let name = &segment[1..(segment.len() - 1)];
+        let name = name.split(':').next().unwrap_or(name);
+        format!("{{{}}}", name.trim_end_matches(".."))
+    });
+
+    segments.collect::<Vec<_>>().join("/")
+}
+
+/// Returns `id`, suffixed with the smallest counter from `2` that makes it
+/// unique if `id` is in `ids`, and records the returned ID in `ids`.
+fn unique_id(ids: &mut HashSet<String>, id: String) -> String {
+    let (mut unique, mut n) = (id.clone(), 1);
+    while !ids.insert(unique.clone()) {
+        n += 1;
+        unique = format!("{}_{}", id, n);
+    }
+
+    unique
+}
+
+/// The content object for `content` served or accepted by `route`, keyed by
+/// its media type.
+fn content_object(route: &Route, content: &Content) -> Value {
+    let media_type = content.media_type.as_ref()
+        .or(route.format.as_ref())
+        .map(|media_type| media_type.to_string())
+        .unwrap_or_else(|| "*/*".into());
+
+    let mut object = Map::new();
+    object.insert(media_type, json!({ "schema": content.schema }));
+    Value::Object(object)
+}
+
+fn parameter_object(param: &Parameter) -> Value {
+    let schema = param.schema.clone().unwrap_or_else(Schema::any);
+    let (location, required) = match param.location {
+        Location::Path => ("path", true),
+        Location::Query => ("query", !schema.nullable),
+    };
+
+    let mut object = json!({
+        "name": param.name,
+        "in": location,
+        "required": required,
+        "schema": schema,
+    });
+
+    // A trailing query parameter, `<params..>`, collects every query field.
+    if param.trailing && param.location == Location::Query {
+        object["style"] = "form".into();
+        object["explode"] = true.into();
+    }
+
+    object
+}
+
+fn operation_object(route: &Route, operation: &Operation, id: Option<String>) -> Value {
+    let mut response = json!({ "description": "Success" });
+    if let Some(content) = &operation.response {
+        response["content"] = content_object(route, content);
+    }
+
+    let mut object = json!({ "responses": { "200": response } });
+    if let Some(id) = id {
+        object["operationId"] = id.into();
+    }
+
+    if !operation.params.is_empty() {
+        let params = operation.params.iter().map(parameter_object).collect::<Vec<_>>();
+        object["parameters"] = params.into();
+    }
+
+    if let Some(content) = &operation.body {
+        object["requestBody"] = json!({
+            "required": !content.schema.nullable,
+            "content": content_object(route, content),
+        });
+    }
+
+    object
+}
+
+/// Serves the document describing the routes of the `Rocket` instance, as
+/// cached at liftoff.
+#[derive(Clone)]
+struct Document {
+    openapi: OpenApi,
+    cache: Arc<RwLock<Option<String>>>,
+}
+
+impl Document {
+    /// Generates the document for the routes `rocket` launched with and caches
+    /// it, returning the cached copy.
+    fn generate(&self, rocket: &Rocket<Orbit>) -> String {
+        let document = self.openapi.document(rocket.routes()).to_string();
+        if let Ok(mut cache) = self.cache.write() {
+            *cache = Some(document.clone());
+        }
+
+        document
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Document {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data) -> Outcome<'r> {
+        let cached = self.cache.read().ok().and_then(|cache| cache.clone());
+        let document = cached.unwrap_or_else(|| self.generate(req.rocket()));
+        Outcome::from(req, Json(document))
+    }
+}
+
+/// Serves a Swagger UI page rendering the document at the wrapped path.
+#[derive(Clone)]
+struct SwaggerUi(String);
+
+#[rocket::async_trait]
+impl Handler for SwaggerUi {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data) -> Outcome<'r> {
+        let url = Value::from(self.0.as_str());
+        Outcome::from(req, Html(format!(r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>Swagger UI</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@3/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@3/swagger-ui-bundle.js"></script>
+  <script>SwaggerUIBundle({{ url: {}, dom_id: "#swagger-ui" }});</script>
+</body>
+</html>
+"#, url)))
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for OpenApi {
+    fn info(&self) -> Info {
+        Info { name: "OpenAPI", kind: Kind::Ignite }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut routes = vec![];
+        let document = Document { openapi: self.clone(), cache: Arc::default() };
+        let mut route = Route::new(Method::Get, &self.path, document.clone());
+        route.name = Some("openapi_document".into());
+        routes.push(route);
+
+        if let Some(path) = &self.swagger_ui {
+            let mut ui = Route::new(Method::Get, path, SwaggerUi(self.path.clone()));
+            ui.name = Some("openapi_swagger_ui".into());
+            routes.push(ui);
+        }
+
+        let cache = AdHoc::on_liftoff("OpenAPI Document", move |rocket| {
+            Box::pin(async move { document.generate(rocket); })
+        });
+
+        Ok(rocket.mount("/", routes).attach(cache))
+    }
+}
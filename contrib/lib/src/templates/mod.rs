@@ -138,7 +138,7 @@ use std::path::PathBuf;
 use std::error::Error;
 
 use rocket::{Rocket, Orbit, Ignite, Sentinel};
-use rocket::request::Request;
+use rocket::request::{Request, Locale};
 use rocket::fairing::Fairing;
 use rocket::response::{self, Responder};
 use rocket::http::{ContentType, Status};
@@ -192,6 +192,16 @@ const DEFAULT_TEMPLATE_DIR: &str = "templates";
 /// }
 /// ```
 ///
+/// # Locale
+///
+/// If [`Locales`] are managed, the [`Locale`] of the request being responded
+/// to is available to `Template` responders as the `locale` variable, unless
+/// the context is not a map or already has a `locale` entry. For example, a
+/// Tera template might use `{% if locale == "fr" %}`.
+///
+/// [`Locales`]: rocket::request::Locales
+/// [`Locale`]: rocket::request::Locale
+///
 /// # Helpers, Filters, and Customization
 ///
 /// You may use the [`Template::custom()`] method to construct a fairing with
@@ -418,7 +428,11 @@ impl Template {
 /// extension and a fixed-size body containing the rendered template. If
 /// rendering fails, an `Err` of `Status::InternalServerError` is returned.
 impl<'r> Responder<'r, 'static> for Template {
-    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+    fn respond_to(mut self, req: &'r Request<'_>) -> response::Result<'static> {
+        if let (Some(Value::Object(map)), Some(locale)) = (&mut self.value, Locale::of(req)) {
+            map.entry("locale").or_insert_with(|| Value::String(locale.tag().into()));
+        }
+
         let (render, content_type) = {
             let ctxt = req.rocket().state::<ContextManager>().ok_or_else(|| {
                 error_!("Uninitialized template context: missing fairing.");
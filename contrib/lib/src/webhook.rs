@@ -0,0 +1,306 @@
+//! Verification of HMAC-SHA256 signed request bodies, as sent by webhooks.
+//!
+//! See the [`Signed`] data guard for details. Signatures are verified with the
+//! secret in the application's [`Config`], which must be read by attaching
+//! [`Config::fairing()`].
+//!
+//! # Enabling
+//!
+//! This module is only available when the `webhook` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["webhook"]
+//! ```
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rocket::{Rocket, Ignite, Sentinel};
+use rocket::request::Request;
+use rocket::data::{Data, FromData, Outcome, Digest};
+use rocket::fairing::{AdHoc, Fairing};
+use rocket::http::{Status, MediaType};
+use rocket::schema::{Describe, Schema};
+
+use hmac::{Hmac, Mac, NewMac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+/// The configuration of the [`Signed`] data guard, read from the `webhook`
+/// configuration key by [`Config::fairing()`].
+///
+/// ```toml
+/// [default.webhook]
+/// secret = "shared secret key"
+/// # optional, the default:
+/// header = "X-Hub-Signature-256"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Config {
+    /// The secret key the signature is computed with.
+    pub secret: String,
+    /// The name of the header containing the signature.
+    #[serde(default = "Config::default_header")]
+    pub header: String,
+}
+
+impl Config {
+    fn default_header() -> String {
+        "X-Hub-Signature-256".into()
+    }
+
+    /// Returns a fairing that reads the `Config` from the `webhook`
+    /// configuration key and manages it. Ignition fails if the configuration is
+    /// missing or invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::webhook;
+    ///
+    /// let rocket = rocket::build().attach(webhook::Config::fairing());
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("Webhook Config", |rocket| async {
+            match rocket.figment().extract_inner::<Config>("webhook") {
+                Ok(config) => Ok(rocket.manage(config)),
+                Err(e) => {
+                    error!("Invalid or missing `webhook` configuration.");
+                    info_!("{}", e);
+                    Err(rocket)
+                }
+            }
+        })
+    }
+}
+
+/// Data guard wrapper that verifies the HMAC-SHA256 signature of a body before
+/// the wrapped data guard `T` is allowed to succeed.
+///
+/// Webhook providers sign the raw bytes of the request body with a shared
+/// secret. `Signed<T>` computes the signature of the body as `T` reads it, via
+/// [`Data::digest()`], and compares it against the signature in the request's
+/// signature header once the body has been read to completion. A body whose
+/// signature doesn't match fails to read, so `T` never deserializes it.
+///
+/// The secret and the name of the signature header are read from the
+/// [`Config`] managed by [`Config::fairing()`], which must be attached. The
+/// signature is the hex-encoded HMAC-SHA256 of the body, optionally prefixed
+/// with `sha256=`.
+///
+/// The signature is computed over the body as it is read. If the
+/// [`Decompression`](rocket::data::Decompression) fairing is attached, that is
+/// the _decompressed_ body, while providers sign the body as sent. As a result,
+/// compressed webhook bodies fail verification.
+///
+/// # Outcomes
+///
+///   * If [`Config::fairing()`] isn't attached, the guard fails with `500
+///     Internal Server Error` and [`SignatureError::Config`]. As a
+///     [`Sentinel`], `Signed<T>` also aborts launch in this case.
+///   * If the signature header is missing or malformed, the guard fails with
+///     `403 Forbidden` and [`SignatureError::Missing`] or
+///     [`SignatureError::Malformed`].
+///   * If the signature of the body doesn't match, the guard fails with `403
+///     Forbidden` and [`SignatureError::Mismatch`].
+///   * If `T` succeeds without reading the entire body, the guard fails with
+///     `403 Forbidden` and [`SignatureError::Unverified`].
+///   * Otherwise, the guard forwards or fails as `T` does, with failures
+///     wrapped in [`SignatureError::Inner`], or succeeds.
+///
+/// # Example
+///
+/// Any data guard can be wrapped. With `json`, for instance, a signed JSON
+/// body is received as a `Signed<Json<T>>`. Below, a plain-text body:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # extern crate rocket_contrib;
+/// use rocket_contrib::webhook::{self, Signed};
+///
+/// #[post("/hook", data = "<event>")]
+/// fn hook(event: Signed<String>) -> String {
+///     format!("verified event: {}", event.into_inner())
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(webhook::Config::fairing())
+///         .mount("/", routes![hook])
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Signed<T>(pub T);
+
+/// An error returned by the [`Signed`] data guard.
+#[derive(Debug)]
+pub enum SignatureError<E> {
+    /// The [`Config`] isn't managed: [`Config::fairing()`] isn't attached.
+    Config,
+    /// The request has no signature header.
+    Missing,
+    /// The signature header isn't a hex-encoded signature.
+    Malformed,
+    /// The signature of the body doesn't match the signature header.
+    Mismatch,
+    /// The wrapped data guard succeeded without reading the entire body.
+    Unverified,
+    /// The wrapped data guard failed.
+    Inner(E),
+}
+
+impl<T> Signed<T> {
+    /// Consumes `self` and returns the inner value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket_contrib;
+    /// use rocket_contrib::webhook::Signed;
+    ///
+    /// let signed = Signed("body");
+    /// assert_eq!(signed.into_inner(), "body");
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Computes the HMAC of a body, failing at the end of the body if it doesn't
+/// match `expected`.
+struct Verifier {
+    mac: Option<Hmac<Sha256>>,
+    expected: Vec<u8>,
+    mismatch: Arc<AtomicBool>,
+}
+
+impl Digest for Verifier {
+    fn update(&mut self, bytes: &[u8]) {
+        if let Some(mac) = self.mac.as_mut() {
+            mac.update(bytes);
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let verified = self.mac.take().map_or(false, |mac| mac.verify(&self.expected).is_ok());
+        if !verified {
+            self.mismatch.store(true, Ordering::Release);
+            let msg = "request body signature mismatch";
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes the signature in `value`: hex, optionally prefixed with `sha256=`.
+fn decode_signature(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    let hex = match value.get(..7) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("sha256=") => &value[7..],
+        _ => value,
+    };
+
+    if hex.is_empty() || hex.len() % 2 != 0 {
+        return None;
+    }
+
+    let nibble = |b: u8| (b as char).to_digit(16).map(|n| n as u8);
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| Some(nibble(pair[0])? << 4 | nibble(pair[1])?))
+        .collect()
+}
+
+#[rocket::async_trait]
+impl<'r, T: FromData<'r>> FromData<'r> for Signed<T> {
+    type Error = SignatureError<T::Error>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data) -> Outcome<Self, Self::Error> {
+        let config = match req.rocket().state::<Config>() {
+            Some(config) => config,
+            None => {
+                error_!("Missing `webhook` configuration: `Config::fairing()` isn't attached.");
+                return Outcome::Failure((Status::InternalServerError, SignatureError::Config));
+            }
+        };
+
+        let expected = match req.headers().get_one(&config.header).map(decode_signature) {
+            Some(Some(signature)) => signature,
+            Some(None) => return Outcome::Failure((Status::Forbidden, SignatureError::Malformed)),
+            None => return Outcome::Failure((Status::Forbidden, SignatureError::Missing)),
+        };
+
+        let mac = Hmac::<Sha256>::new_from_slice(config.secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+
+        let mismatch = Arc::new(AtomicBool::new(false));
+        let verifier = Verifier { mac: Some(mac), expected, mismatch: mismatch.clone() };
+        let (data, digested) = data.digest(verifier);
+        let outcome = T::from_data(req, data).await;
+        if mismatch.load(Ordering::Acquire) {
+            warn_!("Request body signature mismatch.");
+            return Outcome::Failure((Status::Forbidden, SignatureError::Mismatch));
+        }
+
+        match outcome {
+            Outcome::Success(value) if digested.is_complete() => Outcome::Success(Signed(value)),
+            Outcome::Success(_) => {
+                Outcome::Failure((Status::Forbidden, SignatureError::Unverified))
+            }
+            Outcome::Failure((status, e)) => Outcome::Failure((status, SignatureError::Inner(e))),
+            Outcome::Forward(data) => Outcome::Forward(data),
+        }
+    }
+}
+
+impl<T> Sentinel for Signed<T> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Config>().is_none() {
+            let signed = rocket::yansi::Paint::default("Signed").bold();
+            let fairing = rocket::yansi::Paint::default("webhook::Config::fairing()").bold();
+            error!("using the `{}` data guard without attaching `{}`.", signed, fairing);
+            info_!("To verify signatures, you must attach `{}`.", fairing);
+            return true;
+        }
+
+        false
+    }
+}
+
+impl<T> Deref for Signed<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Signed<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Describes the value as the wrapped data guard does.
+impl<T: Describe> Describe for Signed<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        T::media_type()
+    }
+}
@@ -0,0 +1,139 @@
+#[macro_use]
+#[cfg(feature = "cors")]
+extern crate rocket;
+
+#[cfg(feature = "cors")]
+mod cors_tests {
+    use std::time::Duration;
+
+    use rocket::http::{Method, Status, Header};
+    use rocket::local::blocking::Client;
+
+    use rocket_contrib::cors::{Cors, AllowedOrigin};
+
+    #[get("/item")] fn get_item() -> &'static str { "item" }
+    #[put("/item")] fn put_item() { }
+    #[delete("/item")] fn delete_item() { }
+
+    fn client(cors: Cors) -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![get_item, put_item, delete_item])
+            .attach(cors);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn cors() -> Cors {
+        Cors::new()
+            .allow_origin(AllowedOrigin::exact("https://rocket.rs"))
+            .allow_origin(AllowedOrigin::wildcard("https://*.rocket.rs"))
+            .allow_origin(AllowedOrigin::regex(r"^https://rocket-\d+\.dev$").unwrap())
+            .expose_headers(&["X-Request-Id"])
+    }
+
+    #[test]
+    fn simple_requests() {
+        let client = client(cors());
+        for origin in &["https://rocket.rs", "https://api.rocket.rs", "https://rocket-9.dev"] {
+            let response = client.get("/item").header(Header::new("Origin", *origin)).dispatch();
+            assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some(*origin));
+            assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+            assert_eq!(response.headers().get_one("Access-Control-Expose-Headers"),
+                Some("X-Request-Id"));
+        }
+
+        for origin in &["https://evil.com", "https://rocket.rs.evil.com", "https://rocket-x.dev"] {
+            let response = client.get("/item").header(Header::new("Origin", *origin)).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+        }
+
+        let response = client.get("/item").dispatch();
+        assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+    }
+
+    #[test]
+    fn regex_origins_match_entirely() {
+        let origin = AllowedOrigin::regex(r"https://rocket\.rs").unwrap();
+        let client = client(Cors::new().allow_origin(origin).allow_credentials(true));
+        let response = client.get("/item")
+            .header(Header::new("Origin", "https://rocket.rs"))
+            .dispatch();
+
+        let headers = response.headers();
+        assert_eq!(headers.get_one("Access-Control-Allow-Origin"), Some("https://rocket.rs"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Credentials"), Some("true"));
+
+        for origin in &["https://rocket.rs.evil.com", "https://evil.com/https://rocket.rs"] {
+            let response = client.get("/item").header(Header::new("Origin", *origin)).dispatch();
+            assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+            assert!(response.headers().get_one("Access-Control-Allow-Credentials").is_none());
+        }
+
+        let unanchored = AllowedOrigin::Regex(regex::Regex::new(r"https://rocket\.rs").unwrap());
+        assert!(unanchored.matches("https://rocket.rs"));
+        assert!(!unanchored.matches("https://rocket.rs.evil.com"));
+    }
+
+    #[test]
+    fn any_origin() {
+        let origin = Header::new("Origin", "https://a.com");
+        let client = client(Cors::new().allow_origin(AllowedOrigin::Any));
+        let response = client.get("/item").header(origin.clone()).dispatch();
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some("*"));
+
+        let cors = Cors::new().allow_origin(AllowedOrigin::Any).allow_credentials(true);
+        let client = self::client(cors);
+        let response = client.get("/item").header(origin).dispatch();
+        let headers = response.headers();
+        assert_eq!(headers.get_one("Access-Control-Allow-Origin"), Some("https://a.com"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Credentials"), Some("true"));
+    }
+
+    #[test]
+    fn preflight_uses_route_methods() {
+        let cors = cors().allow_headers(&["Content-Type"]).max_age(Duration::from_secs(60));
+        let client = client(cors);
+
+        let response = client.req(Method::Options, "/item")
+            .header(Header::new("Origin", "https://rocket.rs"))
+            .header(Header::new("Access-Control-Request-Method", "PUT"))
+            .header(Header::new("Access-Control-Request-Headers", "content-type"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NoContent);
+        let headers = response.headers();
+        assert_eq!(headers.get_one("Access-Control-Allow-Origin"), Some("https://rocket.rs"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Methods"), Some("DELETE, GET, HEAD, PUT"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Headers"), Some("content-type"));
+        assert_eq!(headers.get_one("Access-Control-Max-Age"), Some("60"));
+        assert!(headers.get_one("Access-Control-Expose-Headers").is_none());
+    }
+
+    #[test]
+    fn disallowed_preflights() {
+        let cors = cors().allow_methods(&[Method::Get]).allow_headers(&["Content-Type"]);
+        let client = client(cors);
+
+        let preflight = |path: &'static str, method: &'static str, headers: &'static str| {
+            client.req(Method::Options, path)
+                .header(Header::new("Origin", "https://rocket.rs"))
+                .header(Header::new("Access-Control-Request-Method", method))
+                .header(Header::new("Access-Control-Request-Headers", headers))
+                .dispatch()
+        };
+
+        let response = preflight("/item", "PUT", "");
+        assert_eq!(response.status(), Status::NoContent);
+        assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+
+        let response = preflight("/item", "GET", "X-Custom");
+        assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+
+        let response = preflight("/item", "GET", "");
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Methods"), Some("GET"));
+
+        let response = preflight("/nothing", "GET", "");
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
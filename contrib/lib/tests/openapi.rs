@@ -0,0 +1,237 @@
+#[macro_use]
+#[cfg(feature = "openapi")]
+extern crate rocket;
+
+#[cfg(feature = "openapi")]
+mod openapi_tests {
+    use rocket::form::Form;
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket::schema::Describe;
+
+    use rocket_contrib::json::Json;
+    use rocket_contrib::openapi::OpenApi;
+
+    use serde_json::Value;
+
+    #[derive(Describe, serde::Serialize, serde::Deserialize)]
+    struct Task {
+        description: String,
+        done: Option<bool>,
+    }
+
+    #[derive(FromForm, Describe)]
+    struct Filter<'r> {
+        #[field(name = "q")]
+        query: &'r str,
+        #[field(name = "done")]
+        completed: Option<bool>,
+    }
+
+    #[derive(FromFormField, Describe)]
+    enum Sort {
+        Asc,
+        #[field(value = "desc")]
+        Descending,
+    }
+
+    struct Opaque;
+
+    #[get("/tasks?<sort>&<filter..>")]
+    fn list(sort: Option<Sort>, filter: Filter<'_>) -> Json<Vec<Task>> {
+        let _ = (sort, filter.query, filter.completed);
+        Json(vec![])
+    }
+
+    #[post("/tasks", data = "<task>")]
+    fn new(task: Json<Task>) -> Json<Task> {
+        task
+    }
+
+    #[get("/tasks/<id>/<path..>")]
+    fn file(id: usize, path: std::path::PathBuf) -> Option<Vec<u8>> {
+        let _ = (id, path);
+        None
+    }
+
+    #[put("/tasks/<id>", data = "<task>")]
+    fn update(id: usize, task: Form<Filter<'_>>) -> &'static str {
+        let _ = (id, task.query);
+        "ok"
+    }
+
+    #[get("/opaque")]
+    fn opaque() -> &'static str {
+        let _ = Opaque;
+        "opaque"
+    }
+
+    mod v2 {
+        #[get("/v2/tasks")]
+        pub fn list() -> &'static str {
+            "[]"
+        }
+    }
+
+    fn client(openapi: OpenApi) -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![list, new, file, update, opaque])
+            .attach(openapi);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn document(client: &Client, path: &'static str) -> Value {
+        let response = client.get(path).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        serde_json::from_str(&response.into_string().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn document_info_and_paths() {
+        let client = client(OpenApi::new("Tasks", "1.2.3"));
+        let doc = document(&client, "/openapi.json");
+
+        assert_eq!(doc["openapi"], "3.0.3");
+        assert_eq!(doc["info"]["title"], "Tasks");
+        assert_eq!(doc["info"]["version"], "1.2.3");
+
+        let paths = doc["paths"].as_object().unwrap();
+        assert!(paths["/tasks"]["get"].is_object());
+        assert!(paths["/tasks"]["post"].is_object());
+        assert!(paths["/tasks/{id}"]["put"].is_object());
+        assert!(paths["/tasks/{id}/{path}"]["get"].is_object());
+        assert!(paths["/opaque"]["get"].is_object());
+        assert!(!paths.contains_key("/openapi.json"));
+        assert_eq!(paths["/tasks"]["get"]["operationId"], "list");
+    }
+
+    #[test]
+    fn parameters() {
+        let client = client(OpenApi::new("Tasks", "1.0.0"));
+        let doc = document(&client, "/openapi.json");
+
+        let params = &doc["paths"]["/tasks"]["get"]["parameters"];
+        assert_eq!(params[0]["name"], "sort");
+        assert_eq!(params[0]["in"], "query");
+        assert_eq!(params[0]["required"], false);
+        assert_eq!(params[0]["schema"]["enum"], serde_json::json!(["Asc", "desc"]));
+
+        assert_eq!(params[1]["name"], "filter");
+        assert_eq!(params[1]["style"], "form");
+        assert_eq!(params[1]["explode"], true);
+        assert_eq!(params[1]["schema"]["properties"]["q"]["type"], "string");
+        assert_eq!(params[1]["schema"]["required"], serde_json::json!(["q"]));
+
+        let params = &doc["paths"]["/tasks/{id}/{path}"]["get"]["parameters"];
+        assert_eq!(params[0]["name"], "id");
+        assert_eq!(params[0]["in"], "path");
+        assert_eq!(params[0]["required"], true);
+        assert_eq!(params[0]["schema"]["type"], "integer");
+        assert_eq!(params[1]["name"], "path");
+        assert_eq!(params[1]["schema"]["type"], "string");
+    }
+
+    #[test]
+    fn bodies_and_responses() {
+        let client = client(OpenApi::new("Tasks", "1.0.0"));
+        let doc = document(&client, "/openapi.json");
+
+        let post = &doc["paths"]["/tasks"]["post"];
+        let body = &post["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(body["type"], "object");
+        assert_eq!(body["properties"]["description"]["type"], "string");
+        assert_eq!(body["properties"]["done"]["nullable"], true);
+        assert_eq!(body["required"], serde_json::json!(["description"]));
+
+        let response = &post["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(response, body);
+
+        let list = &doc["paths"]["/tasks"]["get"]["responses"]["200"]["content"];
+        let items = &list["application/json"]["schema"]["items"];
+        assert_eq!(items, body);
+
+        let put = &doc["paths"]["/tasks/{id}"]["put"]["requestBody"]["content"];
+        let form = &put["application/x-www-form-urlencoded"]["schema"];
+        assert_eq!(form["properties"]["q"]["type"], "string");
+
+        let opaque = &doc["paths"]["/opaque"]["get"];
+        assert!(opaque["parameters"].is_null());
+        assert!(opaque["requestBody"].is_null());
+        assert_eq!(opaque["responses"]["200"]["description"], "Success");
+    }
+
+    #[test]
+    fn unique_operation_ids() {
+        let mut routes = routes![list, v2::list, opaque];
+        let remounted = routes![list].remove(0).map_base(|_| "/other".into()).unwrap();
+        routes.push(remounted);
+
+        let doc = OpenApi::new("Tasks", "1.0.0").document(&routes);
+        let module = module_path!().replace("::", "_");
+        assert_eq!(doc["paths"]["/opaque"]["get"]["operationId"], "opaque");
+        assert_eq!(doc["paths"]["/other/tasks"]["get"]["operationId"],
+            format!("{}_list", module));
+        assert_eq!(doc["paths"]["/tasks"]["get"]["operationId"], format!("{}_list_2", module));
+        assert_eq!(doc["paths"]["/v2/tasks"]["get"]["operationId"],
+            format!("{}_v2_list", module));
+    }
+
+    #[test]
+    fn custom_path_and_swagger_ui() {
+        let client = client(OpenApi::new("Tasks", "1.0.0"));
+        assert_eq!(client.get("/docs").dispatch().status(), Status::NotFound);
+
+        let client = client(OpenApi::new("Tasks", "1.0.0").path("/spec.json").swagger_ui("/docs"));
+        assert_eq!(client.get("/openapi.json").dispatch().status(), Status::NotFound);
+        assert_eq!(document(&client, "/spec.json")["info"]["title"], "Tasks");
+
+        let response = client.get("/docs").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        let html = response.into_string().unwrap();
+        assert!(html.contains("SwaggerUIBundle"));
+        assert!(html.contains(r#"url: "/spec.json""#));
+    }
+
+    #[test]
+    fn document_is_cached_at_liftoff() {
+        let client = client(OpenApi::new("Tasks", "1.0.0"));
+        client.rocket().routing().mount("/runtime", routes![opaque]).unwrap();
+
+        let doc = document(&client, "/openapi.json");
+        assert!(doc["paths"]["/opaque"]["get"].is_object());
+        assert!(doc["paths"].get("/runtime/opaque").is_none());
+        assert_eq!(document(&client, "/openapi.json"), doc);
+    }
+
+    #[test]
+    fn serde_renames_are_described() {
+        #[derive(Describe, serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Event {
+            event_id: u64,
+            #[serde(rename = "type")]
+            kind: Level,
+            #[serde(skip)]
+            #[allow(dead_code)]
+            internal: bool,
+        }
+
+        #[derive(Describe, serde::Serialize)]
+        #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+        enum Level {
+            LowPriority,
+            #[serde(rename = "urgent")]
+            High,
+        }
+
+        let schema = Event::schema();
+        let names = schema.properties.keys().map(|k| k.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, &["eventId", "type"]);
+        assert_eq!(schema.properties["type"], Level::schema());
+
+        let values = rocket::schema::Schema::one_of_strings(&["LOW_PRIORITY", "urgent"]);
+        assert_eq!(Level::schema(), values);
+    }
+}
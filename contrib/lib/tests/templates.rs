@@ -129,6 +129,26 @@ mod templates_tests {
             assert_eq!(template, Some(ESCAPED_EXPECTED.into()));
         }
 
+        #[test]
+        fn test_tera_templates_see_locale() {
+            use rocket::http::Header;
+            use rocket::request::Locales;
+
+            #[get("/locale")]
+            fn locale() -> Template {
+                Template::render("tera/locale", HashMap::<String, String>::new())
+            }
+
+            let rocket = rocket().manage(Locales::new(["en", "fr"])).mount("/", routes![locale]);
+            let client = Client::debug(rocket).unwrap();
+            let response = client.get("/locale").dispatch();
+            assert_eq!(response.into_string().unwrap(), "en\n");
+
+            let header = Header::new("Accept-Language", "fr-CA, en;q=0.5");
+            let response = client.get("/locale").header(header).dispatch();
+            assert_eq!(response.into_string().unwrap(), "fr\n");
+        }
+
         #[test]
         fn test_template_metadata_with_tera() {
             let client = Client::debug(rocket()).unwrap();
@@ -0,0 +1,106 @@
+#[macro_use]
+#[cfg(feature = "webhook")]
+extern crate rocket;
+
+#[cfg(feature = "webhook")]
+mod webhook_tests {
+    use rocket::{Rocket, Build};
+    use rocket::http::{Status, Header};
+    use rocket::local::blocking::Client;
+
+    use rocket_contrib::webhook::{self, Signed};
+
+    const BODY: &str = "hello";
+    const SIGNATURE: &str = "ef10c166cadc5407ad7cb1c34e6c963bdec0003636de71c1526a8a3510d58cf9";
+
+    #[post("/", data = "<body>")]
+    fn hook(body: Signed<String>) -> String {
+        body.into_inner()
+    }
+
+    fn rocket(header: Option<&str>) -> Rocket<Build> {
+        let mut figment = rocket::Config::figment()
+            .merge(("webhook.secret", "webhook secret"));
+
+        if let Some(header) = header {
+            figment = figment.merge(("webhook.header", header));
+        }
+
+        rocket::custom(figment).attach(webhook::Config::fairing()).mount("/", routes![hook])
+    }
+
+    fn client() -> Client {
+        Client::debug(rocket(None)).unwrap()
+    }
+
+    #[test]
+    fn verifies_signature() {
+        let client = client();
+        let signatures = [format!("sha256={}", SIGNATURE), SIGNATURE.to_uppercase()];
+        for signature in signatures.iter() {
+            let response = client.post("/")
+                .header(Header::new("X-Hub-Signature-256", signature.clone()))
+                .body(BODY)
+                .dispatch();
+
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.into_string().unwrap(), BODY);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_signatures() {
+        let client = client();
+        let response = client.post("/").body(BODY).dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        for bad in &["sha256=xyz", "sha256=", "abc"] {
+            let response = client.post("/")
+                .header(Header::new("X-Hub-Signature-256", *bad))
+                .body(BODY)
+                .dispatch();
+
+            assert_eq!(response.status(), Status::Forbidden);
+        }
+
+        let response = client.post("/")
+            .header(Header::new("X-Hub-Signature-256", SIGNATURE))
+            .body("hellO")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn custom_signature_header() {
+        let client = Client::debug(rocket(Some("X-Signature"))).unwrap();
+        let response = client.post("/")
+            .header(Header::new("X-Signature", SIGNATURE))
+            .body(BODY)
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), BODY);
+
+        let response = client.post("/")
+            .header(Header::new("X-Hub-Signature-256", SIGNATURE))
+            .body(BODY)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn sentinel_requires_fairing() {
+        let figment = rocket::Config::figment().merge(("webhook.secret", "webhook secret"));
+        let rocket = rocket::custom(figment).mount("/", routes![hook]);
+        let error = Client::debug(rocket).err().expect("sentinel aborts");
+        assert!(matches!(error.kind(), rocket::error::ErrorKind::SentinelAborts(..)));
+    }
+
+    #[test]
+    fn fairing_requires_config() {
+        let rocket = rocket::build().attach(webhook::Config::fairing()).mount("/", routes![hook]);
+        let error = Client::debug(rocket).err().expect("ignite fails");
+        assert!(matches!(error.kind(), rocket::error::ErrorKind::FailedFairings(..)));
+    }
+}
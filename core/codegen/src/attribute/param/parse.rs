@@ -24,8 +24,19 @@ pub enum ErrorKind {
     EarlyTrailing,
     NoTrailing,
     Static,
+    Constrained,
+    BadConstraint,
+    UnknownType,
 }
 
+/// The types that can be used as constraints, as in `<id:u64>`. Must match the
+/// types recognized by `rocket::route::Constraint`.
+const CONSTRAINT_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize",
+    "bool", "f32", "f64",
+];
+
 impl Dynamic {
     pub fn parse<P: UriPart>(
         segment: &str,
@@ -49,6 +60,12 @@ impl Parameter {
         // Check if this is a dynamic param. If so, check its well-formedness.
         if segment.starts_with('<') && segment.ends_with('>') {
             let mut name = &segment[1..(segment.len() - 1)];
+            let mut constraint = None;
+            if let Some(i) = name.find(':') {
+                constraint = Some(&name[(i + 1)..]);
+                name = &name[..i];
+            }
+
             if name.ends_with("..") {
                 trailing = true;
                 name = &name[..(name.len() - 2)];
@@ -61,6 +78,17 @@ impl Parameter {
                 return Err(Error::new(name, span, ErrorKind::BadIdent));
             }
 
+            if let Some(constraint) = constraint {
+                let span = subspan(constraint, segment, source_span);
+                if trailing || P::KIND != uri::Kind::Path {
+                    return Err(Error::new(constraint, span, ErrorKind::Constrained));
+                } else if regex_syntax::Parser::new().parse(constraint).is_err() {
+                    return Err(Error::new(constraint, span, ErrorKind::BadConstraint));
+                } else if is_valid_ident(constraint) && !CONSTRAINT_TYPES.contains(&constraint) {
+                    return Err(Error::new(constraint, span, ErrorKind::UnknownType));
+                }
+            }
+
             let dynamic = Dynamic { name: Name::new(name, span), trailing, index: 0 };
             if dynamic.is_wild() && P::KIND != uri::Kind::Path {
                 return Err(Error::new(name, span, ErrorKind::Ignored));
@@ -128,6 +156,9 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::NoTrailing => "parameter cannot be trailing".fmt(f),
             ErrorKind::EarlyTrailing => "unexpected text after trailing parameter".fmt(f),
             ErrorKind::Static => "unexpected static parameter".fmt(f),
+            ErrorKind::Constrained => "parameter cannot be constrained".fmt(f),
+            ErrorKind::BadConstraint => "invalid parameter constraint".fmt(f),
+            ErrorKind::UnknownType => "unknown constraint type".fmt(f),
         }
     }
 }
@@ -174,6 +205,29 @@ impl From<Error<'_>> for Diagnostic {
                 error.span.error(error.kind.to_string())
                     .help(format!("parameter must be dynamic: `<{}>`", candidate))
             }
+            ErrorKind::Constrained => {
+                error.span.error(error.kind.to_string())
+                    .help("only single, non-trailing path parameters can be constrained")
+            }
+            ErrorKind::BadConstraint => {
+                let diag = error.span.error(error.kind.to_string())
+                    .help("constraints are a type like `u64` or a regular expression");
+
+                match regex_syntax::Parser::new().parse(error.segment) {
+                    Err(e) => diag.note(e.to_string()),
+                    Ok(_) => diag,
+                }
+            }
+            ErrorKind::UnknownType => {
+                let types = CONSTRAINT_TYPES.iter()
+                    .map(|ty| format!("`{}`", ty))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                error.span.error(format!("{}: `{}`", error.kind, error.segment))
+                    .help(format!("type constraints are one of {}", types))
+                    .help(format!("to match the literal text, use the regex `({})`", error.segment))
+            }
         }
     }
 }
@@ -184,7 +238,9 @@ impl devise::FromMeta for Dynamic {
         let span = string.subspan(1..string.len() + 1);
         let param = Dynamic::parse::<uri::Path>(&string, span)?;
 
-        if param.is_wild() {
+        if string.contains(':') {
+            return Err(Error::new(&string, span, ErrorKind::Constrained).into());
+        } else if param.is_wild() {
             return Err(Error::new(&string, span, ErrorKind::Ignored).into());
         } else if param.trailing {
             return Err(Error::new(&string, span, ErrorKind::NoTrailing).into());
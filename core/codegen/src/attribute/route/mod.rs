@@ -12,7 +12,7 @@ use crate::syn_ext::{IdentExt, TypeExt as _};
 use crate::http_codegen::{Method, Optional};
 use crate::attribute::param::Guard;
 
-use self::parse::{Route, Attribute, MethodAttribute};
+use self::parse::{Route, Attribute, MethodAttribute, RouteMeta, RouteLimits};
 
 impl Route {
     pub fn guards(&self) -> impl Iterator<Item = &Guard> {
@@ -296,6 +296,75 @@ fn sentinels_expr(route: &Route) -> TokenStream {
     quote!(::std::vec![#(#sentinel),*])
 }
 
+fn operation_expr(route: &Route) -> TokenStream {
+    use crate::exports::*;
+
+    let generic_idents: Vec<_> = route.handler.sig.generics
+        .type_params()
+        .map(|p| &p.ident)
+        .collect();
+
+    // Types that aren't concrete, like `impl Responder`, can't be described.
+    let describe = |ty: &syn::Type| match ty.is_concrete(&generic_idents) {
+        true => {
+            let ty = ty.with_stripped_lifetimes();
+            quote_spanned!(ty.span() => #_schema::describe!(#ty))
+        }
+        false => quote!(#_None),
+    };
+
+    let path_params = route.path_params.iter().map(|p| (p, quote!(Path)));
+    let query_params = route.query_params.iter().map(|p| (p, quote!(Query)));
+    let params = path_params.chain(query_params)
+        .filter_map(|(param, location)| param.guard().map(|guard| (guard, location)))
+        .map(|(guard, location)| {
+            let name = guard.source.name.as_str();
+            let trailing = guard.source.trailing;
+            let content = describe(&guard.ty);
+            quote! {
+                #_schema::Parameter {
+                    name: #name,
+                    location: #_schema::Location::#location,
+                    trailing: #trailing,
+                    schema: #content.map(|c| c.schema),
+                }
+            }
+        });
+
+    let body = match route.data_guard {
+        Some(ref guard) => describe(&guard.ty),
+        None => quote!(#_None),
+    };
+
+    let response = match route.handler.sig.output {
+        syn::ReturnType::Type(_, ref ty) => describe(ty),
+        syn::ReturnType::Default => quote!(#_None),
+    };
+
+    quote! {
+        || #_schema::Operation {
+            module: #_Some(::std::module_path!()),
+            params: ::std::vec![#(#params),*],
+            body: #body,
+            response: #response,
+        }
+    }
+}
+
+fn limits_expr(route: &Route) -> TokenStream {
+    use crate::exports::*;
+
+    if route.limits.0.is_empty() {
+        return quote!(#_None);
+    }
+
+    let limits = route.limits.0.iter().map(|(name, bytes)| {
+        quote!(.limit(#name, #_data::ByteUnit::from(#bytes)))
+    });
+
+    quote!(#_Some(#_data::Limits::new() #(#limits)*))
+}
+
 fn codegen_route(route: Route) -> Result<TokenStream> {
     use crate::exports::*;
 
@@ -318,6 +387,17 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let uri = route.attr.uri.to_string();
     let rank = Optional(route.attr.rank);
     let format = Optional(route.attr.format.as_ref());
+    let host = Optional(route.attr.host.as_ref().map(|h| &h.value));
+    let meta = &route.meta.0;
+    let limits = limits_expr(&route);
+    let operation = operation_expr(&route);
+    let location = quote_spanned! { handler_fn_name.span() =>
+        #_route::SourceLocation {
+            file: ::std::file!(),
+            line: ::std::line!(),
+            column: ::std::column!(),
+        }
+    };
 
     Ok(quote! {
         #handler_fn
@@ -352,6 +432,15 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    host: #host,
+                    meta: {
+                        let mut #__meta = #_route::Meta::new();
+                        #_schema::__set_operation!(#__meta, #operation);
+                        #(#__meta.set(#meta);)*
+                        #__meta
+                    },
+                    limits: #limits,
+                    location: #location,
                     sentinels: #sentinels,
                 }
             }
@@ -372,9 +461,11 @@ fn complete_route(args: TokenStream, input: TokenStream) -> Result<TokenStream>
         .map_err(|e| Diagnostic::from(e))
         .map_err(|diag| diag.help("`#[route]` can only be used on functions"))?;
 
+    let (args, meta) = RouteMeta::extract(args)?;
+    let (args, limits) = RouteLimits::extract(args)?;
     let attr_tokens = quote!(route(#args));
     let attribute = Attribute::from_meta(&syn::parse2(attr_tokens)?)?;
-    codegen_route(Route::from(attribute, function)?)
+    codegen_route(Route::from(attribute, meta, limits, function)?)
 }
 
 fn incomplete_route(
@@ -393,6 +484,8 @@ fn incomplete_route(
         .map_err(|e| Diagnostic::from(e))
         .map_err(|d| d.help(format!("#[{}] can only be used on functions", method_str)))?;
 
+    let (args, meta) = RouteMeta::extract(args)?;
+    let (args, limits) = RouteLimits::extract(args)?;
     let full_attr = quote!(#method_ident(#args));
     let method_attribute = MethodAttribute::from_meta(&syn::parse2(full_attr)?)?;
 
@@ -404,9 +497,10 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        host: method_attribute.host,
     };
 
-    codegen_route(Route::from(attribute, function)?)
+    codegen_route(Route::from(attribute, meta, limits, function)?)
 }
 
 pub fn route_attribute<M: Into<Option<crate::http::Method>>>(
@@ -1,6 +1,7 @@
 use devise::{syn, Spanned, SpanWrapped, Result, FromMeta};
 use devise::ext::{SpanDiagnosticExt, TypeExt};
 use indexmap::{IndexSet, IndexMap};
+use ubyte::ByteUnit;
 
 use crate::proc_macro_ext::Diagnostics;
 use crate::http_codegen::{Method, MediaType};
@@ -8,7 +9,8 @@ use crate::attribute::param::{Parameter, Dynamic, Guard};
 use crate::syn_ext::FnArgExt;
 
 use crate::name::Name;
-use crate::proc_macro2::Span;
+use crate::proc_macro2::{Span, TokenStream, TokenTree, Delimiter, Group};
+use crate::syn::{parse::{Parser, ParseStream}, punctuated::Punctuated};
 use crate::http::ext::IntoOwned;
 use crate::http::uri::{self, Origin};
 
@@ -29,6 +31,10 @@ pub struct Route {
     pub handler: syn::ItemFn,
     /// The parsed arguments to the user's function.
     pub arguments: Arguments,
+    /// The route metadata expressions, if any.
+    pub meta: RouteMeta,
+    /// The route's limits, if any.
+    pub limits: RouteLimits,
 }
 
 type ArgumentMap = IndexMap<Name, (syn::Ident, syn::Type)>;
@@ -48,6 +54,7 @@ pub struct Attribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub host: Option<SpanWrapped<String>>,
 }
 
 /// The parsed `#[method(..)]` (e.g, `get`, `put`, etc.) attribute.
@@ -58,6 +65,158 @@ pub struct MethodAttribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub host: Option<SpanWrapped<String>>,
+}
+
+/// The expressions in the `meta(...)` argument of a route attribute.
+///
+/// Metadata values are arbitrary expressions, which the `MetaItem` parser
+/// doesn't accept, so `meta(...)` is removed from the attribute arguments
+/// before they're parsed with `FromMeta`.
+#[derive(Debug, Default)]
+pub struct RouteMeta(pub Vec<syn::Expr>);
+
+/// The `name = "size"` pairs in the `limits(...)` argument of a route
+/// attribute, with sizes in bytes.
+///
+/// Like `meta(...)`, `limits(...)` is removed from the attribute arguments
+/// before they're parsed with `FromMeta` as limit names, such as `"data-form"`
+/// or `"file/pdf"`, need not be identifiers.
+#[derive(Debug, Default)]
+pub struct RouteLimits(pub Vec<(String, u64)>);
+
+/// Splits the argument `name(...)`, if any, from the attribute arguments
+/// `args`, returning the remaining arguments and the argument's group.
+fn extract_group(args: TokenStream, name: &str) -> Result<(TokenStream, Option<Group>)> {
+    let mut items: Vec<Vec<TokenTree>> = vec![vec![]];
+    for tree in args {
+        match tree {
+            TokenTree::Punct(ref p) if p.as_char() == ',' => items.push(vec![]),
+            tree => items.last_mut().expect("non-empty").push(tree),
+        }
+    }
+
+    let (mut rest, mut found) = (vec![], None);
+    for item in items.into_iter().filter(|item| !item.is_empty()) {
+        let group = match item.as_slice() {
+            [TokenTree::Ident(i), TokenTree::Group(g)]
+                if i == name && g.delimiter() == Delimiter::Parenthesis => g.clone(),
+            _ => {
+                rest.push(item.into_iter().collect::<TokenStream>());
+                continue;
+            }
+        };
+
+        if found.is_some() {
+            return Err(group.span().error(format!("duplicate `{}` argument", name))
+                .help(format!("all values can be set in a single `{}(...)`", name)));
+        }
+
+        found = Some(group);
+    }
+
+    Ok((quote!(#(#rest),*), found))
+}
+
+/// Checks that `pattern` is a valid host pattern. Mirrors the validation in
+/// `rocket::route::Host::new()`, which panics where this returns an error.
+fn validate_host(pattern: &str) -> std::result::Result<(), &'static str> {
+    if pattern.starts_with('[') {
+        let valid = pattern.len() > 2
+            && pattern.ends_with(']')
+            && pattern[1..pattern.len() - 1].bytes().all(|b| b.is_ascii_hexdigit() || b == b':');
+
+        return if valid { Ok(()) } else { Err("expected a bracketed IPv6 address") };
+    }
+
+    let name = pattern.strip_prefix("*.").unwrap_or(pattern);
+    if name.is_empty() {
+        return Err("expected a host name");
+    } else if name.len() > 253 {
+        return Err("host names are at most 253 characters long");
+    } else if name.contains(':') {
+        return Err("host patterns cannot contain a port");
+    } else if name.contains('*') {
+        return Err("`*` is only allowed as the first label, as in `*.example.com`");
+    }
+
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err("host name labels must be between 1 and 63 characters long");
+        } else if label.starts_with('-') || label.ends_with('-') {
+            return Err("host name labels cannot begin or end with `-`");
+        } else if !label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+            return Err("host name labels can only contain ASCII letters, digits, and `-`");
+        }
+    }
+
+    Ok(())
+}
+
+impl RouteMeta {
+    /// Splits `meta(...)`, if any, from the attribute arguments `args`,
+    /// returning the remaining arguments and the parsed metadata.
+    pub fn extract(args: TokenStream) -> Result<(TokenStream, RouteMeta)> {
+        let (rest, group) = extract_group(args, "meta")?;
+        let meta = match group {
+            Some(group) => {
+                let parser = Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated;
+                RouteMeta(parser.parse2(group.stream())?.into_iter().collect())
+            }
+            None => RouteMeta::default(),
+        };
+
+        Ok((rest, meta))
+    }
+}
+
+impl RouteLimits {
+    /// Splits `limits(...)`, if any, from the attribute arguments `args`,
+    /// returning the remaining arguments and the parsed limits.
+    pub fn extract(args: TokenStream) -> Result<(TokenStream, RouteLimits)> {
+        let (rest, group) = extract_group(args, "limits")?;
+        let group = match group {
+            Some(group) => group,
+            None => return Ok((rest, RouteLimits::default())),
+        };
+
+        fn parse_limit(input: ParseStream<'_>) -> syn::Result<(syn::LitStr, syn::LitStr)> {
+            let name = match input.peek(syn::Ident) {
+                true => input.parse::<syn::Ident>()
+                    .map(|ident| syn::LitStr::new(&ident.to_string(), ident.span()))?,
+                false => input.parse::<syn::LitStr>()?,
+            };
+
+            input.parse::<syn::Token![=]>()?;
+            Ok((name, input.parse::<syn::LitStr>()?))
+        }
+
+        let parser = |input: ParseStream<'_>| {
+            Punctuated::<_, syn::Token![,]>::parse_terminated_with(input, parse_limit)
+        };
+
+        let mut limits = RouteLimits::default();
+        for (name, size) in parser.parse2(group.stream())? {
+            if name.value().is_empty() || name.value().split('/').any(|s| s.is_empty()) {
+                return Err(name.span().error("invalid limit name")
+                    .help("expected a name like `file`, `\"data-form\"`, or `\"file/pdf\"`"));
+            }
+
+            if limits.0.iter().any(|(n, _)| n.eq_ignore_ascii_case(&name.value())) {
+                return Err(name.span().error("duplicate limit name"));
+            }
+
+            let bytes = match size.value().parse::<ByteUnit>() {
+                Ok(bytes) => bytes.as_u64(),
+                Err(e) => return Err(size.span().error(format!("invalid size: {}", e))
+                    .help("expected a size like \"512KiB\" or \"500MiB\"")),
+            };
+
+            limits.0.push((name.value(), bytes));
+        }
+
+        Ok((rest, limits))
+    }
 }
 
 #[derive(Debug)]
@@ -71,6 +230,24 @@ impl FromMeta for RouteUri {
     fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
         let string = crate::proc_macro_ext::StringLit::from_meta(meta)?;
 
+        // A `?` in a constraint, as in `<id:[a-z]?>`, would begin the query.
+        let mut param = None;
+        for (i, c) in string.char_indices() {
+            match c {
+                '<' => param = Some(i),
+                '>' | '/' => param = None,
+                '?' if param.map_or(false, |p| string[p..i].contains(':')) => {
+                    return Err(string.subspan(i + 1..i + 2)
+                        .error("parameter constraints cannot contain `?`")
+                        .help("`?` begins the query; use `{0,1}` instead of a `?` quantifier")
+                        .note("groups that begin with `?`, like `(?:...)` or `(?i)`, \
+                            aren't supported; use `(...)` or a character class instead"));
+                }
+                '?' => break,
+                _ => continue,
+            }
+        }
+
         let origin = Origin::parse_route(&string)
             .map_err(|e| {
                 let span = string.subspan(e.index() + 1..(e.index() + 2));
@@ -78,7 +255,15 @@ impl FromMeta for RouteUri {
                     .help("expected URI in origin form: \"/path/<param>\"")
             })?;
 
-        if !origin.is_normalized() {
+        // A single trailing slash, as in `/foo/`, is permitted: it matters
+        // under strict or redirecting trailing slash policies.
+        let trailing_slash = origin.path().len() > 1 && origin.path().ends_with('/');
+        let path_segments = origin.raw_path_segments().count() - trailing_slash as usize;
+        let is_normalized = origin.path().starts_with('/')
+            && origin.raw_path_segments().take(path_segments).all(|s| !s.is_empty())
+            && origin.raw_query_segments().all(|s| !s.is_empty());
+
+        if !is_normalized {
             let normalized = origin.clone().into_normalized();
             let span = origin.path().find("//")
                 .or_else(|| origin.query()
@@ -122,7 +307,12 @@ impl Route {
         }
     }
 
-    pub fn from(attr: Attribute, handler: syn::ItemFn) -> Result<Route> {
+    pub fn from(
+        attr: Attribute,
+        meta: RouteMeta,
+        limits: RouteLimits,
+        handler: syn::ItemFn
+    ) -> Result<Route> {
         // Collect diagnostics as we proceed.
         let mut diags = Diagnostics::new();
 
@@ -137,6 +327,14 @@ impl Route {
             }
         }
 
+        // Check that the `host`, if any, is a valid host pattern.
+        if let Some(ref host) = attr.host {
+            if let Err(reason) = validate_host(&host.value) {
+                diags.push(host.span.error(format!("invalid host pattern: {}", reason))
+                    .help("expected a host name like \"example.com\" or \"*.example.com\""));
+            }
+        }
+
         // Check the validity of function arguments.
         let span = handler.sig.paren_token.span;
         let mut arguments = Arguments { map: ArgumentMap::new(), span };
@@ -211,7 +409,7 @@ impl Route {
 
         diags.head_err_or(Route {
             attr, path_params, query_params, data_guard, request_guards,
-            handler, arguments,
+            handler, arguments, meta, limits,
         })
     }
 }
@@ -138,6 +138,8 @@ fn explode_path<'a>(
     mut exprs: impl Iterator<Item = &'a Expr>,
     mut args: impl Iterator<Item = (&'a Ident, &'a Type)>,
 ) -> TokenStream {
+    // Generated URIs keep the route's trailing slash, if any.
+    let slash = if internal.trailing_slash { "/" } else { "" };
     if internal.dynamic_path_params().count() == 0 {
         let route_uri = &internal.route_uri;
         if let Some(ref mount) = internal.uri_params.mount_point {
@@ -145,10 +147,10 @@ fn explode_path<'a>(
                 .expect("origin from path")
                 .into_normalized();
 
-            let path = full_uri.path().as_str();
+            let path = format!("{}{}", full_uri.path(), slash);
             return quote!(#_uri::UriArgumentsKind::Static(#path));
         } else {
-            let path = route_uri.path().as_str();
+            let path = format!("{}{}", route_uri.path(), slash);
             return quote!(#_uri::UriArgumentsKind::Static(#path));
         }
     }
@@ -173,7 +175,9 @@ fn explode_path<'a>(
         }
     });
 
-    quote!(#_uri::UriArgumentsKind::Dynamic(&[#(#dyn_exprs),*]))
+    // An empty final value makes the formatter emit a trailing delimiter.
+    let trailing = internal.trailing_slash.then(|| quote!(&"" as &dyn #uri_display));
+    quote!(#_uri::UriArgumentsKind::Dynamic(&[#(#dyn_exprs,)* #trailing]))
 }
 
 fn explode_query<'a>(
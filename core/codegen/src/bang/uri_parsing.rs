@@ -79,6 +79,8 @@ pub enum Validation<'a> {
 #[derive(Debug)]
 pub struct InternalUriParams {
     pub route_uri: Origin<'static>,
+    /// Whether the route URI, before normalization, ends with a `/`.
+    pub trailing_slash: bool,
     pub mount_params: Vec<Parameter>,
     pub path_params: Vec<Parameter>,
     pub query_params: Vec<Parameter>,
@@ -204,9 +206,12 @@ impl Parse for InternalUriParams {
         // if the route attribute succeeded, implying a valid route URI.
         let route_uri_str = StringLit::new(route_uri_str.value(), route_uri_str.span());
         let route_uri = Origin::parse_route(&route_uri_str)
-            .map(|o| o.into_normalized().into_owned())
             .map_err(|_| input.error("internal error: invalid route URI"))?;
 
+        let path = route_uri.path();
+        let trailing_slash = path.len() > 1 && path.ends_with('/');
+        let route_uri = route_uri.into_normalized().into_owned();
+
         let content;
         syn::parenthesized!(content in input);
         let fn_args: Punctuated<FnArg, Token![,]> = content.parse_terminated(FnArg::parse)?;
@@ -241,6 +246,7 @@ impl Parse for InternalUriParams {
 
         Ok(InternalUriParams {
             route_uri,
+            trailing_slash,
             mount_params,
             path_params,
             query_params,
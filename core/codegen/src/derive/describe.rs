@@ -0,0 +1,256 @@
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+
+use crate::exports::*;
+use crate::proc_macro2::TokenStream;
+use crate::derive::form_field::{FieldExt, VariantExt};
+
+#[derive(Default, FromMeta)]
+struct FieldAttr {
+    body: bool,
+}
+
+impl FieldAttr {
+    const NAME: &'static str = "schema";
+}
+
+/// The naming-related `#[serde(...)]` attributes of a container, field, or
+/// variant. Only the forms that apply to both serialization and
+/// deserialization are supported: the schema describes both.
+#[derive(Default)]
+struct SerdeAttr {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+}
+
+impl SerdeAttr {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<SerdeAttr> {
+        let mut serde = SerdeAttr::default();
+        for attr in attrs.iter().filter(|a| a.path.is_ident("serde")) {
+            let list = match attr.parse_meta() {
+                Ok(syn::Meta::List(list)) => list,
+                _ => continue,
+            };
+
+            for item in list.nested.iter() {
+                let meta = match item {
+                    syn::NestedMeta::Meta(meta) => meta,
+                    syn::NestedMeta::Lit(_) => continue,
+                };
+
+                let unsupported = ["rename", "rename_all", "flatten"].iter()
+                    .find(|name| meta.path().is_ident(name))
+                    .filter(|_| !matches!(meta, syn::Meta::NameValue(_)));
+
+                if let Some(name) = unsupported {
+                    return Err(meta.span().error(format!("unsupported `serde({})`", name))
+                        .help("`Describe` supports `rename = \"...\"`, `rename_all = \"...\"`, \
+                            and `skip`"));
+                }
+
+                match meta {
+                    syn::Meta::NameValue(nv) => match &nv.lit {
+                        syn::Lit::Str(v) if nv.path.is_ident("rename") => {
+                            serde.rename = Some(v.value());
+                        }
+                        syn::Lit::Str(v) if nv.path.is_ident("rename_all") => {
+                            if rename_all(&v.value(), "a_b", false).is_none() {
+                                return Err(v.span().error("unknown `rename_all` rule"));
+                            }
+
+                            serde.rename_all = Some(v.value());
+                        }
+                        _ => continue,
+                    },
+                    syn::Meta::Path(path) if path.is_ident("skip") => serde.skip = true,
+                    _ => continue,
+                }
+            }
+        }
+
+        Ok(serde)
+    }
+}
+
+/// Applies serde's `rename_all` rule `rule` to the field or, if `variant`, the
+/// variant named `name`. Returns `None` if `rule` is unknown.
+fn rename_all(rule: &str, name: &str, variant: bool) -> Option<String> {
+    // Variants are `PascalCase`; fields are `snake_case`.
+    let snake = match variant {
+        true => name.char_indices().fold(String::new(), |mut snake, (i, c)| {
+            if i > 0 && c.is_uppercase() {
+                snake.push('_');
+            }
+
+            snake.extend(c.to_lowercase());
+            snake
+        }),
+        false => name.to_string(),
+    };
+
+    let pascal = match variant {
+        true => name.to_string(),
+        false => snake.split('_').map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |c| c.to_uppercase().chain(chars).collect())
+        }).collect(),
+    };
+
+    let renamed = match rule {
+        "lowercase" if variant => name.to_ascii_lowercase(),
+        "UPPERCASE" if variant => name.to_ascii_uppercase(),
+        "lowercase" => snake,
+        "UPPERCASE" => snake.to_ascii_uppercase(),
+        "PascalCase" => pascal,
+        "camelCase" => {
+            let mut chars = pascal.chars();
+            chars.next().map_or(String::new(), |c| c.to_lowercase().chain(chars).collect())
+        }
+        "snake_case" => snake,
+        "SCREAMING_SNAKE_CASE" => snake.to_ascii_uppercase(),
+        "kebab-case" => snake.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake.to_ascii_uppercase().replace('_', "-"),
+        _ => return None,
+    };
+
+    Some(renamed)
+}
+
+/// The serialized name of the field or, if `variant`, the variant named
+/// `ident` with serde attributes `attr` in a container with `container`
+/// attributes, if serde renames it.
+fn serde_name(
+    container: &SerdeAttr,
+    attr: &SerdeAttr,
+    ident: &syn::Ident,
+    variant: bool,
+) -> Option<String> {
+    let ident = ident.to_string();
+    let ident = ident.trim_start_matches("r#");
+    attr.rename.clone()
+        .or_else(|| container.rename_all.as_ref().and_then(|r| rename_all(r, ident, variant)))
+}
+
+/// The schema of `ty`, or `Schema::any()` if `ty` doesn't implement `Describe`.
+fn schema_of(ty: &syn::Type) -> TokenStream {
+    let ty = ty.with_stripped_lifetimes();
+    quote_spanned! { ty.span() =>
+        #_schema::describe!(#ty).map_or_else(#_schema::Schema::any, |c| c.schema)
+    }
+}
+
+pub fn derive_describe(input: proc_macro::TokenStream) -> TokenStream {
+    // Container attributes aren't otherwise reachable from the field mappers.
+    let container = syn::parse::<syn::DeriveInput>(input.clone())
+        .map_or_else(|_| Ok(SerdeAttr::default()), |i| SerdeAttr::from_attrs(&i.attrs));
+
+    let container = match container {
+        Ok(container) => std::sync::Arc::new(container),
+        Err(diag) => return diag.emit_as_item_tokens(),
+    };
+
+    let fields_container = container.clone();
+    DeriveGenerator::build_for(input, quote!(impl #_schema::Describe))
+        .support(Support::Struct | Support::Enum | Support::Lifetime | Support::Type)
+        .type_bound(quote!(#_schema::Describe))
+        .validator(ValidatorBuild::new()
+            .fields_validate(|_, fields| {
+                let mut bodies = vec![];
+                for field in fields.iter() {
+                    let attr = FieldAttr::one_from_attrs(FieldAttr::NAME, &field.attrs)?;
+                    if attr.map_or(false, |a| a.body) {
+                        bodies.push(field.span());
+                    }
+                }
+
+                match bodies.get(1) {
+                    Some(span) => Err(span.error("only one field can be the body")
+                        .span_note(bodies[0], "previous body field here")),
+                    None => Ok(())
+                }
+            })
+        )
+        .inner_mapper(MapperBuild::new()
+            .with_output(|_, output| quote! {
+                fn schema() -> #_schema::Schema {
+                    #output
+                }
+            })
+            .try_enum_map(move |_, data| {
+                if data.variants().all(|v| v.fields().is_empty()) {
+                    let mut values = vec![];
+                    for variant in data.variants() {
+                        let attr = SerdeAttr::from_attrs(&variant.attrs)?;
+                        if attr.skip {
+                            continue;
+                        }
+
+                        values.push(match serde_name(&container, &attr, &variant.ident, true) {
+                            Some(name) => quote!(#name),
+                            None => {
+                                let value = variant.first_form_field_value()?;
+                                quote!(#value)
+                            }
+                        });
+                    }
+
+                    return Ok(quote! {
+                        #_schema::Schema::one_of_strings(&[#(#values),*])
+                    });
+                }
+
+                // As in a `Responder`, a variant is its first field.
+                let schemas = data.variants().map(|v| match v.fields().iter().next() {
+                    Some(field) => schema_of(&field.ty),
+                    None => quote!(#_schema::Schema::any()),
+                });
+
+                Ok(quote! {
+                    #_schema::Schema {
+                        one_of: ::std::vec![#(#schemas),*],
+                        ..#_schema::Schema::any()
+                    }
+                })
+            })
+            .try_fields_map(move |_, fields| {
+                let mut body = None;
+                for field in fields.iter() {
+                    let attr = FieldAttr::one_from_attrs(FieldAttr::NAME, &field.attrs)?;
+                    if attr.map_or(false, |a| a.body) {
+                        body = Some(field);
+                    }
+                }
+
+                if fields.are_unnamed() {
+                    body = body.or_else(|| fields.iter().next());
+                }
+
+                if let Some(field) = body {
+                    return Ok(schema_of(&field.ty));
+                }
+
+                let mut properties = vec![];
+                for field in fields.iter() {
+                    let attr = SerdeAttr::from_attrs(&field.attrs)?;
+                    if attr.skip {
+                        continue;
+                    }
+
+                    let ident = field.ident.as_ref().expect("named field");
+                    let name = match serde_name(&fields_container, &attr, ident, false) {
+                        Some(name) => quote!(#name),
+                        None => {
+                            let name = field.first_field_name()?;
+                            quote!(#name)
+                        }
+                    };
+
+                    let schema = schema_of(&field.ty);
+                    properties.push(quote_spanned!(field.span() => .property(#name, #schema)));
+                }
+
+                Ok(quote!(#_schema::Schema::object() #(#properties)*))
+            })
+        )
+        .to_tokens()
+}
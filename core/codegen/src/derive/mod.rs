@@ -1,4 +1,5 @@
 mod form_field;
+pub mod describe;
 pub mod from_form;
 pub mod from_form_field;
 pub mod responder;
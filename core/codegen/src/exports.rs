@@ -67,11 +67,14 @@ define_exported_paths! {
     __data => __data,
     __error => __error,
     __trail => __trail,
+    __meta => __meta,
     _request => ::rocket::request,
     _response => ::rocket::response,
     _route => ::rocket::route,
+    _data => ::rocket::data,
     _catcher => ::rocket::catcher,
     _sentinel => ::rocket::sentinel,
+    _schema => ::rocket::schema,
     _log => ::rocket::log,
     _form => ::rocket::form::prelude,
     _http => ::rocket::http,
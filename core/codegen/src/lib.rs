@@ -158,12 +158,22 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'host' '=' '"' HOST '"'
+        ///            | 'meta' '(' EXPR (',' EXPR)* ')'
+        ///            | 'limits' '(' limit (',' limit)* ')'
         ///
-        /// SINGLE_PARAM := '<' IDENT '>'
+        /// limit := (IDENT | '"' LIMIT_NAME '"') '=' '"' SIZE '"'
+        ///
+        /// SINGLE_PARAM := '<' IDENT (':' CONSTRAINT)? '>'
         /// TRAILING_PARAM := '<' IDENT '..>'
         ///
         /// URI_SEG := valid, non-percent-encoded HTTP URI segment
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
+        /// HOST := host name, optionally prefixed with `*.` as a wildcard
+        /// CONSTRAINT := primitive type name or regular expression
+        /// EXPR := expression of a `Send + Sync + 'static` type
+        /// LIMIT_NAME := data limit name, such as `data-form` or `file/pdf`
+        /// SIZE := byte size, such as `512KiB` or `500MiB`
         ///
         /// INTEGER := unsigned integer, as defined by Rust
         /// IDENT := valid identifier, as defined by Rust, except `_`
@@ -255,14 +265,21 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name), path, query, rank,
+        ///      format, host, metadata, and limits from the route attribute.
+        ///      Each `meta` expression is evaluated and set in [`Route::meta`].
+        ///      The `limits` are set in [`Route::limits`], overriding the
+        ///      configured limits of the same name and those below them, as
+        ///      returned by [`Request::limits()`], for requests to the route.
+        ///      The handler is set to the generated handler.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
         ///
         /// [`Handler`]: rocket::route::Handler
+        /// [`Route::meta`]: rocket::Route::meta
+        /// [`Route::limits`]: rocket::Route::limits
+        /// [`Request::limits()`]: rocket::Request::limits()
         /// [`routes!`]: macro.routes.html
         /// [`uri!`]: macro.uri.html
         /// [`Origin`]: rocket::http::uri::Origin
@@ -914,6 +931,62 @@ pub fn derive_uri_display_path(input: TokenStream) -> TokenStream {
     emit!(derive::uri_display::derive_uri_display_path(input))
 }
 
+/// Derive for the [`Describe`] trait.
+///
+/// The [`Describe`] derive can be applied to structs and enums with any number
+/// of fields. Every field's type should implement [`Describe`]; fields whose
+/// types don't are described by [`Schema::any()`].
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::schema::Describe;
+///
+/// #[derive(FromForm, Describe)]
+/// struct Task<'r> {
+///     description: &'r str,
+///     #[field(name = "done")]
+///     completed: Option<bool>,
+/// }
+///
+/// #[derive(FromFormField, Describe)]
+/// enum Priority {
+///     Low,
+///     #[field(value = "med")]
+///     Medium,
+///     High,
+/// }
+/// ```
+///
+/// The derive generates an implementation of [`Describe::schema()`]:
+///
+///   * A struct with named fields is described by an object schema with one
+///     property per field, named by the field's `#[field(name)]`, if any, or
+///     its identifier otherwise. Properties are required unless nullable.
+///
+///   * A tuple struct, or a struct with a field annotated `#[schema(body)]`,
+///     is described by the schema of its first or annotated field.
+///
+///   * An enum with only unit variants is described by a string schema whose
+///     values are the variants' `#[field(value)]`s, if any, or their
+///     identifiers otherwise.
+///
+///   * Any other enum is described by a `oneOf` schema of its variants, each
+///     described by its first field, as in [`Responder`](derive.Responder.html).
+///
+/// Serde's `#[serde(rename = "...")]` and `#[serde(rename_all = "...")]` take
+/// precedence over `#[field]` names and values so that the schema of a type
+/// used in a `Json` body matches its JSON. Fields and variants marked
+/// `#[serde(skip)]` are omitted. Other forms of `rename` and `rename_all`, as
+/// well as `#[serde(flatten)]`, are rejected.
+///
+/// [`Describe`]: ../rocket/schema/trait.Describe.html
+/// [`Describe::schema()`]: ../rocket/schema/trait.Describe.html#tymethod.schema
+/// [`Schema::any()`]: ../rocket/schema/struct.Schema.html#method.any
+#[proc_macro_derive(Describe, attributes(field, schema))]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    emit!(derive::describe::derive_describe(input))
+}
+
 /// Generates a [`Vec`] of [`Route`]s from a set of route paths.
 ///
 /// The `routes!` macro expands a list of route paths into a [`Vec`] of their
@@ -0,0 +1,138 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, MediaType};
+use rocket::form::Form;
+use rocket::schema::{Describe, Schema, Operation, Location};
+
+#[derive(FromForm, Describe)]
+struct Person<'r> {
+    name: &'r str,
+    #[field(name = "years")]
+    age: Option<u8>,
+    tags: Vec<String>,
+}
+
+#[derive(Describe)]
+struct Wrapper<T>(T);
+
+#[derive(FromFormField, Describe)]
+enum Kind {
+    Cat,
+    #[field(value = "pup")]
+    #[field(value = "puppy")]
+    Dog,
+}
+
+#[derive(Describe)]
+enum Either<'r> {
+    Left(&'r str),
+    Right { value: usize, other: bool },
+    Neither,
+}
+
+#[derive(Responder, Describe)]
+struct Page {
+    #[schema(body)]
+    body: Vec<u8>,
+    kind: ContentType,
+}
+
+#[test]
+fn derived_struct_schemas() {
+    let schema = Person::schema();
+    assert_eq!(schema.kind, Some("object"));
+    assert_eq!(schema.required, &["name", "tags"]);
+
+    let names = schema.properties.keys().map(|k| k.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, &["name", "years", "tags"]);
+    assert_eq!(schema.properties["name"], Schema::string());
+    assert_eq!(schema.properties["years"], Schema::integer("int32").nullable());
+    assert_eq!(schema.properties["tags"], Schema::array(Schema::string()));
+
+    assert_eq!(Wrapper::<bool>::schema(), Schema::boolean());
+    assert_eq!(Wrapper::<Person<'_>>::schema(), Person::schema());
+    assert_eq!(Page::schema(), Schema::string().format("binary"));
+}
+
+#[test]
+fn derived_enum_schemas() {
+    assert_eq!(Kind::schema(), Schema::one_of_strings(&["Cat", "pup"]));
+
+    let schema = Either::schema();
+    assert_eq!(schema.kind, None);
+    assert_eq!(schema.one_of, vec![Schema::string(), Schema::integer("int64"), Schema::any()]);
+}
+
+#[test]
+fn bytes_are_binary() {
+    assert_eq!(Vec::<u8>::schema(), Schema::string().format("binary"));
+    assert_eq!(Vec::<u8>::media_type(), Some(MediaType::Binary));
+    assert_eq!(Vec::<u16>::schema(), Schema::array(Schema::integer("int32")));
+    assert_eq!(Vec::<u16>::media_type(), None);
+}
+
+#[test]
+fn wide_integers_have_no_format() {
+    assert_eq!(u64::schema(), Schema::integer("int64"));
+    assert_eq!(u128::schema().format, None);
+    assert_eq!(i128::schema(), Schema::integer_any());
+}
+
+struct Opaque;
+
+#[rocket::async_trait]
+impl<'r> rocket::request::FromRequest<'r> for Opaque {
+    type Error = ();
+
+    async fn from_request(_: &'r rocket::Request<'_>) -> rocket::request::Outcome<Self, ()> {
+        rocket::request::Outcome::Success(Opaque)
+    }
+}
+
+#[post("/<kind>/<rest..>?<q>&<person..>", data = "<form>")]
+fn operation(
+    kind: Kind,
+    rest: std::path::PathBuf,
+    q: Option<&str>,
+    person: Person<'_>,
+    form: Form<Person<'_>>,
+    _opaque: Opaque,
+) -> Option<String> {
+    let _ = (kind, rest, q, person.name, form.name);
+    None
+}
+
+#[post("/<_>", data = "<data>")]
+fn undescribed(data: rocket::Data) {
+    let _ = data;
+}
+
+#[test]
+fn route_operation() {
+    let routes = routes![operation, undescribed];
+    let op = Operation::of(&routes[0]).unwrap();
+
+    let params = op.params.iter()
+        .map(|p| (p.name, p.location, p.trailing, p.schema.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(params, vec![
+        ("kind", Location::Path, false, Some(Kind::schema())),
+        ("rest", Location::Path, true, Some(Schema::string())),
+        ("q", Location::Query, false, Some(Schema::string().nullable())),
+        ("person", Location::Query, true, Some(Person::schema())),
+    ]);
+
+    let body = op.body.as_ref().unwrap();
+    assert_eq!(body.schema, Person::schema());
+    assert_eq!(body.media_type, Some(MediaType::Form));
+
+    let response = op.response.as_ref().unwrap();
+    assert_eq!(response.schema, Schema::string().nullable());
+    assert_eq!(response.media_type, Some(MediaType::Plain));
+    assert_eq!(op.module, Some(module_path!()));
+
+    let op = Operation::of(&routes[1]).unwrap();
+    assert!(op.body.is_none());
+    assert!(op.response.is_none());
+}
@@ -8,6 +8,7 @@ use either::Either;
 use crate::{Header, MediaType};
 use crate::ext::IntoCollection;
 use crate::parse::parse_accept;
+use crate::uncased::UncasedStr;
 
 /// The HTTP Accept header.
 ///
@@ -242,6 +243,77 @@ impl Accept {
         self.iter().map(|weighted_mt| weighted_mt.media_type())
     }
 
+    /// Selects the media type in `offered` the client most prefers, following
+    /// [RFC 7231 5.3.2]. Returns `None` if the client accepts none of them.
+    ///
+    /// The quality of an offered media type is the weight of the most specific
+    /// media range in `self` that matches it, where a range matches if its
+    /// type, subtype, and parameters are `*` or equal to those of the offered
+    /// type. Offered types without a matching range or with a quality of `0`
+    /// are not acceptable. Among the acceptable types with the highest quality,
+    /// the one appearing first in `offered` is selected. If `self` is empty,
+    /// the first offered type is selected.
+    ///
+    /// [RFC 7231 5.3.2]: https://tools.ietf.org/html/rfc7231#section-5.3.2
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Accept, MediaType};
+    ///
+    /// let offered = [MediaType::JSON, MediaType::MsgPack, MediaType::HTML];
+    ///
+    /// let accept: Accept = "text/*, application/json; q=0.5".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), Some(&MediaType::HTML));
+    ///
+    /// let accept: Accept = "*/*; q=0.1, application/json".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), Some(&MediaType::JSON));
+    ///
+    /// let accept: Accept = "*/*, application/*; q=0".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), Some(&MediaType::HTML));
+    ///
+    /// let accept: Accept = "image/png".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), None);
+    /// ```
+    pub fn negotiate<'m>(&self, offered: &'m [MediaType]) -> Option<&'m MediaType> {
+        if self.first().is_none() {
+            return offered.first();
+        }
+
+        let mut selected: Option<(&MediaType, f32)> = None;
+        for media_type in offered {
+            let quality = self.quality_of(media_type);
+            if quality > 0.0 && selected.map_or(true, |(_, q)| quality > q) {
+                selected = Some((media_type, quality));
+            }
+        }
+
+        selected.map(|(media_type, _)| media_type)
+    }
+
+    /// The weight of the most specific media range matching `media_type`, or
+    /// `0` if none matches.
+    fn quality_of(&self, media_type: &MediaType) -> f32 {
+        // Parameters following `q` are accept-extensions, not media type ones.
+        fn range_params(range: &QMediaType) -> impl Iterator<Item = (&UncasedStr, &str)> + '_ {
+            range.params().take_while(|(name, _)| *name != "q")
+        }
+
+        let matches = |range: &&QMediaType| {
+            (range.top() == "*" || range.top() == media_type.top())
+                && (range.sub() == "*" || range.sub() == media_type.sub())
+                && range_params(range).all(|(name, value)| {
+                    media_type.param(name.as_str()).map_or(false, |v| v == value)
+                })
+        };
+
+        self.iter()
+            .filter(matches)
+            .max_by_key(|range| (range.specificity(), range_params(range).count()))
+            .map_or(0.0, |range| range.weight_or(1.0))
+    }
+
     known_media_types!(accept_constructor);
 }
 
@@ -377,7 +449,7 @@ impl Extend<QMediaType> for AcceptParams {
 
 #[cfg(test)]
 mod test {
-    use crate::{Accept, MediaType};
+    use crate::{Accept, MediaType, QMediaType};
 
     macro_rules! assert_preference {
         ($string:expr, $expect:expr) => (
@@ -422,4 +494,36 @@ mod test {
         assert_preference!("a/b; q=0.6; v=1, a/b; q=0.5; v=1; c=2",
             "a/b; q=0.6; v=1");
     }
+
+    macro_rules! assert_negotiation {
+        ($string:expr, [$($offered:expr),*], $expect:expr) => (
+            let accept: Accept = $string.parse().expect("accept string parse");
+            let offered: Vec<MediaType> = vec![$($offered.parse().unwrap()),*];
+            let selected = accept.negotiate(&offered).map(|mt| mt.to_string());
+            assert_eq!(selected.as_deref(), $expect);
+        )
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let empty = Accept::new(Vec::<QMediaType>::new());
+        assert_eq!(empty.negotiate(&[MediaType::JSON, MediaType::HTML]), Some(&MediaType::JSON));
+
+        assert_negotiation!("c/d", ["a/b", "c/d"], Some("c/d"));
+        assert_negotiation!("*/*", ["a/b", "c/d"], Some("a/b"));
+        assert_negotiation!("c/*", ["a/b", "c/d"], Some("c/d"));
+        assert_negotiation!("e/f", ["a/b", "c/d"], None);
+
+        assert_negotiation!("a/b; q=0.5, c/d", ["a/b", "c/d"], Some("c/d"));
+        assert_negotiation!("a/b; q=0.5, c/d; q=0.5", ["a/b", "c/d"], Some("a/b"));
+        assert_negotiation!("*/*; q=0.1, c/d; q=0.2", ["a/b", "c/d"], Some("c/d"));
+        assert_negotiation!("*/*; q=0.3, c/d; q=0.2", ["a/b", "c/d"], Some("a/b"));
+
+        assert_negotiation!("*/*, a/*; q=0", ["a/b", "c/d"], Some("c/d"));
+        assert_negotiation!("*/*, a/b; q=0", ["a/b"], None);
+        assert_negotiation!("a/*, a/b; q=0", ["a/b", "a/c"], Some("a/c"));
+
+        assert_negotiation!("a/b; v=1", ["a/b", "a/b; v=2", "a/b; v=1"], Some("a/b; v=1"));
+        assert_negotiation!("a/b; q=0.9; ext=1", ["a/b"], Some("a/b"));
+    }
 }
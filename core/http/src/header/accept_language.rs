@@ -0,0 +1,214 @@
+use std::fmt;
+
+use crate::header::typed::{split, param};
+use crate::{TypedHeader, InvalidHeader};
+
+/// The HTTP `Accept-Language` header: the natural languages a client prefers.
+///
+/// An `Accept-Language` header is composed of one or more language ranges,
+/// each of which may have an optional quality value (a [`QLanguage`]). The
+/// [`negotiate()`](AcceptLanguage::negotiate()) method selects the language a
+/// client most prefers from a list of supported language tags.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, AcceptLanguage};
+///
+/// let header = AcceptLanguage::parse("fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5").unwrap();
+/// assert_eq!(header.0.len(), 4);
+/// assert_eq!(header.0[1].range(), "fr");
+/// assert_eq!(header.0[1].weight(), Some(0.9));
+/// assert_eq!(header.to_string(), "fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptLanguage(pub Vec<QLanguage>);
+
+/// A language range, such as `en-US`, `en`, or `*`, with an associated quality
+/// value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QLanguage(pub String, pub Option<f32>);
+
+impl QLanguage {
+    /// The language range.
+    pub fn range(&self) -> &str {
+        &self.0
+    }
+
+    /// The weight of the language range, if there is any.
+    pub fn weight(&self) -> Option<f32> {
+        self.1
+    }
+
+    /// The weight of the language range or `default` if there is none.
+    pub fn weight_or(&self, default: f32) -> f32 {
+        self.1.unwrap_or(default)
+    }
+
+    /// How specifically the range matches `tag`, if it does at all. Ranges
+    /// equal to the tag are most specific, followed by prefixes of the tag,
+    /// tags the range is a more specific form of, and finally `*`.
+    fn matches(&self, tag: &str) -> Option<(u8, usize)> {
+        let is_prefix = |prefix: &str, of: &str| {
+            of.len() > prefix.len()
+                && of.as_bytes()[prefix.len()] == b'-'
+                && of[..prefix.len()].eq_ignore_ascii_case(prefix)
+        };
+
+        let range = self.range();
+        let kind = match () {
+            _ if range.eq_ignore_ascii_case(tag) => 3,
+            _ if is_prefix(range, tag) => 2,
+            _ if is_prefix(tag, range) => 1,
+            _ if range == "*" => 0,
+            _ => return None,
+        };
+
+        Some((kind, range.len()))
+    }
+}
+
+impl AcceptLanguage {
+    /// Selects the language tag in `supported` the client most prefers.
+    /// Returns `None` if the client accepts none of them.
+    ///
+    /// The quality of a supported tag is the weight of the most specific range
+    /// in `self` that matches it. A range matches a tag if it is equal to it
+    /// (ignoring case), is a prefix of it (`en` matches `en-US`), is a more
+    /// specific form of it (`en-US` matches `en`), or is `*`. Tags without a
+    /// matching range or with a quality of `0` are not acceptable. Among the
+    /// acceptable tags with the highest quality, the one appearing first in
+    /// `supported` is selected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{TypedHeader, AcceptLanguage};
+    ///
+    /// let supported = ["en-US", "fr", "de"];
+    ///
+    /// let header = AcceptLanguage::parse("fr-CH, en;q=0.8").unwrap();
+    /// assert_eq!(header.negotiate(&supported), Some(&"fr"));
+    ///
+    /// let header = AcceptLanguage::parse("de;q=0.5, en").unwrap();
+    /// assert_eq!(header.negotiate(&supported), Some(&"en-US"));
+    ///
+    /// let header = AcceptLanguage::parse("*, en;q=0").unwrap();
+    /// assert_eq!(header.negotiate(&supported), Some(&"fr"));
+    ///
+    /// let header = AcceptLanguage::parse("ja").unwrap();
+    /// assert_eq!(header.negotiate(&supported), None);
+    /// ```
+    pub fn negotiate<'s, S: AsRef<str>>(&self, supported: &'s [S]) -> Option<&'s S> {
+        let mut selected: Option<(&S, f32)> = None;
+        for tag in supported {
+            let quality = self.quality_of(tag.as_ref());
+            if quality > 0.0 && selected.map_or(true, |(_, q)| quality > q) {
+                selected = Some((tag, quality));
+            }
+        }
+
+        selected.map(|(tag, _)| tag)
+    }
+
+    /// The weight of the most specific range matching `tag`, preferring the
+    /// greatest weight among equally specific ranges, or `0` if none match.
+    fn quality_of(&self, tag: &str) -> f32 {
+        let mut best: Option<((u8, usize), f32)> = None;
+        for language in &self.0 {
+            if let Some(specificity) = language.matches(tag) {
+                let weight = language.weight_or(1.0);
+                let better = match best {
+                    Some((s, w)) => specificity > s || (specificity == s && weight > w),
+                    None => true,
+                };
+
+                if better {
+                    best = Some((specificity, weight));
+                }
+            }
+        }
+
+        best.map_or(0.0, |(_, weight)| weight)
+    }
+}
+
+impl QLanguage {
+    fn parse(string: &str) -> Result<QLanguage, InvalidHeader> {
+        let invalid = |reason: &str| {
+            let msg = format!("{} in `{}`", reason, string);
+            InvalidHeader::new(AcceptLanguage::NAME, msg)
+        };
+
+        let mut parts = split(string, ';');
+        let range = parts.next().ok_or_else(|| invalid("missing language range"))?;
+        let valid_range = range == "*" || range.split('-').enumerate().all(|(i, subtag)| {
+            (1..=8).contains(&subtag.len()) && match i {
+                0 => subtag.bytes().all(|b| b.is_ascii_alphabetic()),
+                _ => subtag.bytes().all(|b| b.is_ascii_alphanumeric()),
+            }
+        });
+
+        if !valid_range {
+            return Err(invalid("malformed language range"));
+        }
+
+        let weight = match parts.next().map(param) {
+            Some(Some((name, Some(value)))) if name.eq_ignore_ascii_case("q") => {
+                match value.parse::<f32>() {
+                    Ok(q) if (0.0..=1.0).contains(&q) => Some(q),
+                    _ => return Err(invalid("invalid quality value")),
+                }
+            }
+            Some(_) => return Err(invalid("unexpected parameter")),
+            None => None,
+        };
+
+        if parts.next().is_some() {
+            return Err(invalid("unexpected parameter"));
+        }
+
+        Ok(QLanguage(range.into(), weight))
+    }
+}
+
+impl TypedHeader for AcceptLanguage {
+    const NAME: &'static str = "Accept-Language";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let languages = split(value, ',')
+            .map(QLanguage::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if languages.is_empty() {
+            return Err(InvalidHeader::new(Self::NAME, "expected at least one language"));
+        }
+
+        Ok(AcceptLanguage(languages))
+    }
+}
+
+impl fmt::Display for QLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Some(q) => write!(f, "{};q={}", self.0, q),
+            None => f.write_str(&self.0),
+        }
+    }
+}
+
+impl fmt::Display for AcceptLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, language) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            language.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::header::typed::is_token;
+use crate::{TypedHeader, InvalidHeader};
+
+/// The HTTP `Authorization` header: credentials authenticating a client.
+///
+/// An `Authorization` header consists of an authentication scheme, such as
+/// `Basic` or `Bearer`, and the credentials for that scheme. The credentials
+/// are not interpreted.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, Authorization};
+///
+/// let auth = Authorization::parse("Bearer mF_9.B5f-4.1JqM").unwrap();
+/// assert!(auth.scheme_is("bearer"));
+/// assert_eq!(auth.credentials(), "mF_9.B5f-4.1JqM");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorization {
+    scheme: String,
+    credentials: String,
+}
+
+impl Authorization {
+    /// Creates an `Authorization` header with the scheme `scheme` and the
+    /// credentials `credentials`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scheme` is not a valid token.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Authorization;
+    ///
+    /// let auth = Authorization::new("Bearer", "mF_9.B5f-4.1JqM");
+    /// assert_eq!(auth.to_string(), "Bearer mF_9.B5f-4.1JqM");
+    /// ```
+    pub fn new<S, C>(scheme: S, credentials: C) -> Authorization
+        where S: Into<String>, C: Into<String>
+    {
+        let scheme = scheme.into();
+        assert!(is_token(&scheme), "authorization scheme must be a token");
+        Authorization { scheme, credentials: credentials.into() }
+    }
+
+    /// The authentication scheme.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Returns `true` if the authentication scheme is `scheme`, compared
+    /// case-insensitively.
+    pub fn scheme_is(&self, scheme: &str) -> bool {
+        self.scheme.eq_ignore_ascii_case(scheme)
+    }
+
+    /// The credentials, without the scheme.
+    pub fn credentials(&self) -> &str {
+        &self.credentials
+    }
+}
+
+impl TypedHeader for Authorization {
+    const NAME: &'static str = "Authorization";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let value = value.trim();
+        let (scheme, credentials) = match value.find(char::is_whitespace) {
+            Some(i) => (&value[..i], value[i..].trim_start()),
+            None => (value, ""),
+        };
+
+        if !is_token(scheme) {
+            return Err(InvalidHeader::new(Self::NAME, "malformed authentication scheme"));
+        }
+
+        Ok(Authorization { scheme: scheme.into(), credentials: credentials.into() })
+    }
+}
+
+impl fmt::Display for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.credentials.is_empty() {
+            true => f.write_str(&self.scheme),
+            false => write!(f, "{} {}", self.scheme, self.credentials),
+        }
+    }
+}
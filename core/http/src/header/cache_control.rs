@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::header::typed::{split, param, write_quoted};
+use crate::{TypedHeader, InvalidHeader};
+
+/// The HTTP `Cache-Control` header: a list of caching directives.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, CacheControl, CacheDirective};
+///
+/// let cc = CacheControl::parse("public, max-age=3600").unwrap();
+/// assert_eq!(cc.0, [CacheDirective::Public, CacheDirective::MaxAge(3600)]);
+/// assert_eq!(cc.max_age(), Some(3600));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheControl(pub Vec<CacheDirective>);
+
+/// A single directive in a [`CacheControl`] header.
+///
+/// Directive names are case-insensitive and are normalized to lowercase.
+/// Directives that aren't known, as well as `no-cache` and `private` with a
+/// list of field names, are parsed as [`CacheDirective::Extension`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheDirective {
+    /// `no-cache`
+    NoCache,
+    /// `no-store`
+    NoStore,
+    /// `no-transform`
+    NoTransform,
+    /// `only-if-cached`
+    OnlyIfCached,
+    /// `must-revalidate`
+    MustRevalidate,
+    /// `proxy-revalidate`
+    ProxyRevalidate,
+    /// `public`
+    Public,
+    /// `private`
+    Private,
+    /// `immutable`
+    Immutable,
+    /// `max-age=seconds`
+    MaxAge(u64),
+    /// `s-maxage=seconds`
+    SMaxAge(u64),
+    /// `max-stale` or `max-stale=seconds`
+    MaxStale(Option<u64>),
+    /// `min-fresh=seconds`
+    MinFresh(u64),
+    /// Any other directive, with an optional, unquoted argument.
+    Extension(String, Option<String>),
+}
+
+impl CacheControl {
+    /// Returns the value of the `max-age` directive, if there is one.
+    pub fn max_age(&self) -> Option<u64> {
+        self.0.iter().find_map(|directive| match directive {
+            CacheDirective::MaxAge(secs) => Some(*secs),
+            _ => None,
+        })
+    }
+}
+
+impl CacheDirective {
+    fn parse(directive: &str) -> Result<CacheDirective, InvalidHeader> {
+        use CacheDirective::*;
+
+        let invalid = || {
+            let msg = format!("malformed directive `{}`", directive);
+            InvalidHeader::new(CacheControl::NAME, msg)
+        };
+
+        let (name, value) = param(directive).ok_or_else(invalid)?;
+        let seconds = || value.as_ref().and_then(|v| v.parse().ok()).ok_or_else(invalid);
+        let name = name.to_ascii_lowercase();
+        Ok(match (name.as_str(), &value) {
+            ("no-cache", None) => NoCache,
+            ("no-store", None) => NoStore,
+            ("no-transform", None) => NoTransform,
+            ("only-if-cached", None) => OnlyIfCached,
+            ("must-revalidate", None) => MustRevalidate,
+            ("proxy-revalidate", None) => ProxyRevalidate,
+            ("public", None) => Public,
+            ("private", None) => Private,
+            ("immutable", None) => Immutable,
+            ("max-stale", None) => MaxStale(None),
+            ("max-stale", Some(_)) => MaxStale(Some(seconds()?)),
+            ("max-age", _) => MaxAge(seconds()?),
+            ("s-maxage", _) => SMaxAge(seconds()?),
+            ("min-fresh", _) => MinFresh(seconds()?),
+            _ => Extension(name.clone(), value.as_ref().map(|v| v.to_string())),
+        })
+    }
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: &'static str = "Cache-Control";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let directives = split(value, ',')
+            .map(CacheDirective::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if directives.is_empty() {
+            return Err(InvalidHeader::new(Self::NAME, "expected at least one directive"));
+        }
+
+        Ok(CacheControl(directives))
+    }
+}
+
+impl fmt::Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CacheDirective::*;
+
+        match self {
+            NoCache => f.write_str("no-cache"),
+            NoStore => f.write_str("no-store"),
+            NoTransform => f.write_str("no-transform"),
+            OnlyIfCached => f.write_str("only-if-cached"),
+            MustRevalidate => f.write_str("must-revalidate"),
+            ProxyRevalidate => f.write_str("proxy-revalidate"),
+            Public => f.write_str("public"),
+            Private => f.write_str("private"),
+            Immutable => f.write_str("immutable"),
+            MaxAge(secs) => write!(f, "max-age={}", secs),
+            SMaxAge(secs) => write!(f, "s-maxage={}", secs),
+            MaxStale(None) => f.write_str("max-stale"),
+            MaxStale(Some(secs)) => write!(f, "max-stale={}", secs),
+            MinFresh(secs) => write!(f, "min-fresh={}", secs),
+            Extension(name, None) => f.write_str(name),
+            Extension(name, Some(value)) => {
+                write!(f, "{}=", name)?;
+                write_quoted(f, value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, directive) in self.0.iter().enumerate() {
+            if i != 0 { f.write_str(", ")?; }
+            directive.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+use crate::header::typed::split;
+use crate::{TypedHeader, InvalidHeader};
+
+/// An entity tag: an opaque validator for a representation of a resource.
+///
+/// An entity tag is either _strong_ or _weak_. Strong tags are equal when both
+/// are strong and their opaque tags are equal; weak tags are equal when their
+/// opaque tags are equal. See [`EntityTag::strong_eq()`] and
+/// [`EntityTag::weak_eq()`].
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+///
+/// let strong = EntityTag::strong("v1");
+/// let weak = EntityTag::weak("v1");
+/// assert!(strong.weak_eq(&weak));
+/// assert!(!strong.strong_eq(&weak));
+/// assert_eq!(weak.to_string(), "W/\"v1\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Creates a strong entity tag with the opaque tag `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a `"`.
+    pub fn strong<T: Into<String>>(tag: T) -> EntityTag {
+        EntityTag::new(false, tag.into())
+    }
+
+    /// Creates a weak entity tag with the opaque tag `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a `"`.
+    pub fn weak<T: Into<String>>(tag: T) -> EntityTag {
+        EntityTag::new(true, tag.into())
+    }
+
+    fn new(weak: bool, tag: String) -> EntityTag {
+        assert!(!tag.contains('"'), "entity tags cannot contain '\"'");
+        EntityTag { weak, tag }
+    }
+
+    /// Returns the opaque tag of `self`.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if `self` is a weak entity tag.
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns `true` if `self` and `other` are both strong and have equal
+    /// opaque tags.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Returns `true` if `self` and `other` have equal opaque tags.
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+
+    fn parse(name: &'static str, value: &str) -> Result<EntityTag, InvalidHeader> {
+        let (weak, tag) = match value.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None => (false, value),
+        };
+
+        let invalid = || InvalidHeader::new(name, "malformed entity tag");
+        let tag = tag.strip_prefix('"').and_then(|t| t.strip_suffix('"')).ok_or_else(invalid)?;
+        if tag.contains('"') {
+            return Err(invalid());
+        }
+
+        Ok(EntityTag { weak, tag: tag.into() })
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+/// The HTTP `ETag` header: the entity tag of the selected representation.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{ETag, EntityTag};
+/// use rocket::response::Response;
+///
+/// let response = Response::build().header(ETag(EntityTag::strong("v1"))).finalize();
+/// assert_eq!(response.headers().get_one("ETag"), Some("\"v1\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag(pub EntityTag);
+
+impl TypedHeader for ETag {
+    const NAME: &'static str = "ETag";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        EntityTag::parse(Self::NAME, value.trim()).map(ETag)
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The HTTP `If-None-Match` header: a conditional request for representations
+/// whose entity tag matches none of those listed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{IfNoneMatch, EntityTag, Status};
+///
+/// const CURRENT: &str = "v2";
+///
+/// #[get("/")]
+/// fn index(if_none_match: Option<IfNoneMatch>) -> Result<&'static str, Status> {
+///     match if_none_match {
+///         Some(header) if header.matches(&EntityTag::strong(CURRENT)) => {
+///             Err(Status::NotModified)
+///         }
+///         _ => Ok("the current version"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatch {
+    /// `*`: matches any current representation.
+    Any,
+    /// A list of entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfNoneMatch {
+    /// Returns `true` if `etag` matches `self` using the weak comparison
+    /// required for `If-None-Match`, that is, if a request with this header
+    /// for a representation with entity tag `etag` would fail its precondition.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(tags) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        }
+    }
+}
+
+impl TypedHeader for IfNoneMatch {
+    const NAME: &'static str = "If-None-Match";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        if value.trim() == "*" {
+            return Ok(IfNoneMatch::Any);
+        }
+
+        let tags = split(value, ',')
+            .map(|tag| EntityTag::parse(Self::NAME, tag))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if tags.is_empty() {
+            return Err(InvalidHeader::new(Self::NAME, "expected `*` or an entity tag"));
+        }
+
+        Ok(IfNoneMatch::Tags(tags))
+    }
+}
+
+impl fmt::Display for IfNoneMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IfNoneMatch::Any => f.write_str("*"),
+            IfNoneMatch::Tags(tags) => {
+                for (i, tag) in tags.iter().enumerate() {
+                    if i != 0 { f.write_str(", ")?; }
+                    tag.fmt(f)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The HTTP `If-Modified-Since` header: a conditional request for
+/// representations modified after a given date.
+///
+/// Dates in any of the three formats permitted by RFC 7231 are accepted: the
+/// preferred IMF-fixdate, as in `Sun, 06 Nov 1994 08:49:37 GMT`, and the
+/// obsolete RFC 850 and asctime formats, as in `Sunday, 06-Nov-94 08:49:37 GMT`
+/// and `Sun Nov  6 08:49:37 1994`. Dates are always formatted as IMF-fixdate.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, IfModifiedSince};
+///
+/// let header = IfModifiedSince::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+/// let later = header.0 + rocket::time::Duration::hours(1);
+/// assert!(header.is_modified(later));
+/// assert!(!header.is_modified(header.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfModifiedSince(pub OffsetDateTime);
+
+impl IfModifiedSince {
+    /// Returns `true` if a representation last modified at `last_modified` was
+    /// modified after the date in `self`, comparing at a resolution of one
+    /// second as is the resolution of HTTP dates.
+    pub fn is_modified(&self, last_modified: OffsetDateTime) -> bool {
+        last_modified.unix_timestamp() > self.0.unix_timestamp()
+    }
+}
+
+impl TypedHeader for IfModifiedSince {
+    const NAME: &'static str = "If-Modified-Since";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let date = imf_fixdate(value.trim())
+            .ok_or_else(|| InvalidHeader::new(Self::NAME, "invalid HTTP date"))?;
+
+        PrimitiveDateTime::parse(&*date, IMF_FIXDATE)
+            .map(|date| IfModifiedSince(date.assume_utc()))
+            .map_err(|e| InvalidHeader::new(Self::NAME, e.to_string()))
+    }
+}
+
+impl fmt::Display for IfModifiedSince {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = self.0.to_offset(UtcOffset::UTC);
+        f.write_str(&date.format(IMF_FIXDATE))
+    }
+}
+
+/// The format of an IMF-fixdate, as in `Sun, 06 Nov 1994 08:49:37 GMT`.
+const IMF_FIXDATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Rewrites the HTTP date `date`, in any of the IMF-fixdate, RFC 850, or
+/// asctime formats, as an IMF-fixdate. Returns `None` if `date` is in none of
+/// the formats. The rewritten date is not otherwise validated.
+fn imf_fixdate(date: &str) -> Option<Cow<'_, str>> {
+    let mut parts = date.splitn(2, ", ");
+    match (parts.next(), parts.next()) {
+        // IMF-fixdate: `Sun, 06 Nov 1994 08:49:37 GMT`.
+        (Some(weekday), Some(_)) if weekday.len() == 3 => Some(Cow::Borrowed(date)),
+        // RFC 850: `Sunday, 06-Nov-94 08:49:37 GMT`.
+        (Some(weekday), Some(rest)) => {
+            let mut rest = rest.splitn(2, ' ');
+            let (date, time) = (rest.next()?, rest.next()?);
+            let mut date = date.split('-');
+            let (day, month, year) = (date.next()?, date.next()?, date.next()?);
+            if year.len() != 2 || date.next().is_some() {
+                return None;
+            }
+
+            // Two-digit years below 70 are taken to be in the 2000s.
+            let year: u16 = year.parse().ok()?;
+            let year = if year < 70 { 2000 + year } else { 1900 + year };
+            let weekday = weekday.get(..3)?;
+            Some(format!("{}, {} {} {} {}", weekday, day, month, year, time).into())
+        }
+        // asctime: `Sun Nov  6 08:49:37 1994`.
+        _ => match date.split_whitespace().collect::<Vec<_>>()[..] {
+            [weekday, month, day, time, year] => {
+                let date = format!("{}, {:0>2} {} {} {} GMT", weekday, day, month, year, time);
+                Some(date.into())
+            }
+            _ => None,
+        }
+    }
+}
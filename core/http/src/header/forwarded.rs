@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::header::typed::{split, param, write_quoted};
+use crate::{TypedHeader, InvalidHeader};
+
+/// The HTTP `Forwarded` header: information added by proxies about the
+/// connections a request was forwarded through.
+///
+/// The header contains one element per proxy, in the order they were added, so
+/// the first element describes the connection from the original client.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, Forwarded};
+///
+/// let header = Forwarded::parse("for=192.0.2.60;proto=https, for=198.51.100.17").unwrap();
+/// assert_eq!(header.0[0].for_.as_deref(), Some("192.0.2.60"));
+/// assert_eq!(header.0[0].proto.as_deref(), Some("https"));
+/// assert_eq!(header.0[1].for_.as_deref(), Some("198.51.100.17"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forwarded(pub Vec<ForwardedElement>);
+
+/// A single element of a [`Forwarded`] header, describing one forwarding hop.
+///
+/// Parameter values are unquoted. Extension parameters are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ForwardedElement {
+    /// The `by` parameter: the interface where the request came in to the
+    /// proxy.
+    pub by: Option<String>,
+    /// The `for` parameter: the node making the request to the proxy.
+    pub for_: Option<String>,
+    /// The `host` parameter: the `Host` header as received by the proxy.
+    pub host: Option<String>,
+    /// The `proto` parameter: the protocol used to make the request.
+    pub proto: Option<String>,
+}
+
+impl ForwardedElement {
+    fn parse(element: &str) -> Result<ForwardedElement, InvalidHeader> {
+        let mut fwd = ForwardedElement::default();
+        for pair in split(element, ';') {
+            let (name, value) = match param(pair) {
+                Some((name, Some(value))) => (name, value.into_owned()),
+                _ => {
+                    let msg = format!("malformed parameter `{}`", pair);
+                    return Err(InvalidHeader::new(Forwarded::NAME, msg));
+                }
+            };
+
+            match name.to_ascii_lowercase().as_str() {
+                "by" => fwd.by = Some(value),
+                "for" => fwd.for_ = Some(value),
+                "host" => fwd.host = Some(value),
+                "proto" => fwd.proto = Some(value),
+                _ => (),
+            }
+        }
+
+        Ok(fwd)
+    }
+}
+
+impl TypedHeader for Forwarded {
+    const NAME: &'static str = "Forwarded";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let elements = split(value, ',')
+            .map(ForwardedElement::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if elements.is_empty() {
+            return Err(InvalidHeader::new(Self::NAME, "expected at least one element"));
+        }
+
+        Ok(Forwarded(elements))
+    }
+}
+
+impl fmt::Display for ForwardedElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params = [
+            ("by", &self.by), ("for", &self.for_), ("host", &self.host), ("proto", &self.proto)
+        ];
+
+        let mut first = true;
+        for (name, value) in params.iter() {
+            if let Some(value) = value {
+                if !first { f.write_str(";")?; }
+                write!(f, "{}=", name)?;
+                write_quoted(f, value)?;
+                first = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Forwarded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, element) in self.0.iter().enumerate() {
+            if i != 0 { f.write_str(", ")?; }
+            element.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use crate::header::typed::{split, param, write_quoted, is_token};
+use crate::{TypedHeader, InvalidHeader};
+
+/// The HTTP `Link` header: typed links to related resources, as in RFC 8288.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{Link, LinkValue};
+/// use rocket::response::Response;
+///
+/// let link = Link(vec![
+///     LinkValue::new("/items?page=3").with_param("rel", "next"),
+///     LinkValue::new("/items?page=1").with_param("rel", "prev"),
+/// ]);
+///
+/// let response = Response::build().header(link).finalize();
+/// let header = response.headers().get_one("Link").unwrap();
+/// assert_eq!(header, "</items?page=3>; rel=next, </items?page=1>; rel=prev");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link(pub Vec<LinkValue>);
+
+/// A single link in a [`Link`] header: a target URI and its parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LinkValue {
+    uri: String,
+    params: Vec<(String, String)>,
+}
+
+impl LinkValue {
+    /// Creates a link to `uri` without any parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `uri` contains a `>`.
+    pub fn new<U: Into<String>>(uri: U) -> LinkValue {
+        let uri = uri.into();
+        assert!(!uri.contains('>'), "link target cannot contain '>'");
+        LinkValue { uri, params: vec![] }
+    }
+
+    /// Adds the parameter `name` with value `value` to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid token.
+    pub fn with_param<N, V>(mut self, name: N, value: V) -> LinkValue
+        where N: Into<String>, V: Into<String>
+    {
+        let name = name.into();
+        assert!(is_token(&name), "link parameter names must be tokens");
+        self.params.push((name, value.into()));
+        self
+    }
+
+    /// The target URI of the link.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// The value of the first parameter named `name`, compared
+    /// case-insensitively.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The value of the `rel` parameter: the relation type of the link.
+    pub fn rel(&self) -> Option<&str> {
+        self.param("rel")
+    }
+
+    /// All of the link's parameters, in order.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    fn parse(link: &str) -> Result<LinkValue, InvalidHeader> {
+        let invalid = || InvalidHeader::new(Link::NAME, format!("malformed link `{}`", link));
+        let rest = link.strip_prefix('<').ok_or_else(invalid)?;
+        let end = rest.find('>').ok_or_else(invalid)?;
+        let (uri, rest) = (&rest[..end], rest[end + 1..].trim_start());
+        if !rest.is_empty() && !rest.starts_with(';') {
+            return Err(invalid());
+        }
+
+        let mut value = LinkValue { uri: uri.into(), params: vec![] };
+        for pair in split(rest, ';') {
+            let (name, arg) = param(pair).ok_or_else(invalid)?;
+            let arg = arg.map(|v| v.into_owned()).unwrap_or_default();
+            value.params.push((name.into(), arg));
+        }
+
+        Ok(value)
+    }
+}
+
+impl TypedHeader for Link {
+    const NAME: &'static str = "Link";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let links = split(value, ',')
+            .map(LinkValue::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if links.is_empty() {
+            return Err(InvalidHeader::new(Self::NAME, "expected at least one link"));
+        }
+
+        Ok(Link(links))
+    }
+}
+
+impl fmt::Display for LinkValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.uri)?;
+        for (name, value) in &self.params {
+            write!(f, "; {}=", name)?;
+            write_quoted(f, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, link) in self.0.iter().enumerate() {
+            if i != 0 { f.write_str(", ")?; }
+            link.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -3,11 +3,27 @@ mod known_media_types;
 mod media_type;
 mod content_type;
 mod accept;
+mod accept_language;
 mod header;
+mod typed;
+mod conditional;
+mod range;
+mod authorization;
+mod cache_control;
+mod forwarded;
+mod link;
 
 pub use self::content_type::ContentType;
 pub use self::accept::{Accept, QMediaType};
+pub use self::accept_language::{AcceptLanguage, QLanguage};
 pub use self::media_type::MediaType;
 pub use self::header::{Header, HeaderMap};
+pub use self::typed::{TypedHeader, InvalidHeader};
+pub use self::conditional::{EntityTag, ETag, IfNoneMatch, IfModifiedSince};
+pub use self::range::{Range, ByteRange};
+pub use self::authorization::Authorization;
+pub use self::cache_control::{CacheControl, CacheDirective};
+pub use self::forwarded::{Forwarded, ForwardedElement};
+pub use self::link::{Link, LinkValue};
 
 pub(crate) use self::media_type::Source;
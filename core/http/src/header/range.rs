@@ -0,0 +1,114 @@
+use std::fmt;
+
+use crate::header::typed::split;
+use crate::{TypedHeader, InvalidHeader};
+
+/// The HTTP `Range` header: a request for one or more byte ranges of a
+/// representation.
+///
+/// Only the `bytes` range unit is supported.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, Range, ByteRange};
+///
+/// let range = Range::parse("bytes=0-99, -50").unwrap();
+/// assert_eq!(range.0, [ByteRange::FromTo(0, 99), ByteRange::Last(50)]);
+/// assert_eq!(range.to_string(), "bytes=0-99, -50");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range(pub Vec<ByteRange>);
+
+/// A single range in a [`Range`] header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRange {
+    /// `first-last`: the bytes from `first` to `last`, inclusive.
+    FromTo(u64, u64),
+    /// `first-`: the bytes from `first` to the end.
+    From(u64),
+    /// `-len`: the final `len` bytes.
+    Last(u64),
+}
+
+impl ByteRange {
+    /// Resolves `self` against a representation of `len` bytes, returning the
+    /// range of byte offsets selected or `None` if the range isn't satisfiable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ByteRange;
+    ///
+    /// assert_eq!(ByteRange::FromTo(10, 1000).resolve(100), Some(10..100));
+    /// assert_eq!(ByteRange::From(100).resolve(100), None);
+    /// assert_eq!(ByteRange::Last(10).resolve(100), Some(90..100));
+    /// ```
+    pub fn resolve(&self, len: u64) -> Option<std::ops::Range<u64>> {
+        let range = match *self {
+            ByteRange::FromTo(first, last) => first..len.min(last.saturating_add(1)),
+            ByteRange::From(first) => first..len,
+            ByteRange::Last(n) => len.saturating_sub(n)..len,
+        };
+
+        (range.start < range.end).then(|| range)
+    }
+
+    fn parse(value: &str) -> Option<ByteRange> {
+        let mut parts = value.splitn(2, '-');
+        let (first, last) = (parts.next()?.trim(), parts.next()?.trim());
+        match (first.is_empty(), last.is_empty()) {
+            (true, false) => Some(ByteRange::Last(last.parse().ok()?)),
+            (false, true) => Some(ByteRange::From(first.parse().ok()?)),
+            (false, false) => {
+                let (first, last) = (first.parse().ok()?, last.parse().ok()?);
+                (first <= last).then(|| ByteRange::FromTo(first, last))
+            }
+            (true, true) => None,
+        }
+    }
+}
+
+impl TypedHeader for Range {
+    const NAME: &'static str = "Range";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        let ranges = value.trim().strip_prefix("bytes=")
+            .ok_or_else(|| InvalidHeader::new(Self::NAME, "expected a `bytes` range"))?;
+
+        let ranges = split(ranges, ',')
+            .map(|range| ByteRange::parse(range).ok_or(range))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|r| InvalidHeader::new(Self::NAME, format!("malformed range `{}`", r)))?;
+
+        if ranges.is_empty() {
+            return Err(InvalidHeader::new(Self::NAME, "expected at least one range"));
+        }
+
+        Ok(Range(ranges))
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ByteRange::FromTo(first, last) => write!(f, "{}-{}", first, last),
+            ByteRange::From(first) => write!(f, "{}-", first),
+            ByteRange::Last(n) => write!(f, "-{}", n),
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bytes=")?;
+        for (i, range) in self.0.iter().enumerate() {
+            if i != 0 { f.write_str(", ")?; }
+            range.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
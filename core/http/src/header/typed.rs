@@ -0,0 +1,416 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{Header, HeaderMap};
+
+/// A header with a typed, structured value.
+///
+/// A `TypedHeader` knows the name of the header it represents, how to parse
+/// itself from a header value via [`TypedHeader::parse()`], and how to format
+/// itself back into a header value via its [`Display`](fmt::Display)
+/// implementation. Rocket provides implementations for several common headers:
+///
+///   * [`Authorization`](crate::Authorization)
+///   * [`CacheControl`](crate::CacheControl)
+///   * [`ETag`](crate::ETag)
+///   * [`Forwarded`](crate::Forwarded)
+///   * [`IfModifiedSince`](crate::IfModifiedSince)
+///   * [`IfNoneMatch`](crate::IfNoneMatch)
+///   * [`Link`](crate::Link)
+///   * [`Range`](crate::Range)
+///
+/// # Usage
+///
+/// Every typed header implements `Into<Header>` and can thus be used in any
+/// context where an `Into<Header>` is expected, such as
+/// [`Response::set_header()`]. Rocket's built-in typed headers are also request
+/// guards: if the header is missing, the guard forwards; if it is present but
+/// invalid, the guard fails with a status of `400 Bad Request`. The exception is
+/// `IfModifiedSince`, which forwards if its date is invalid. Any other typed
+/// header can be used as a request guard via [`Typed`].
+///
+/// [`Response::set_header()`]: rocket::Response::set_header()
+/// [`Typed`]: rocket::request::Typed
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{TypedHeader, CacheControl, CacheDirective};
+/// use rocket::response::Response;
+///
+/// let header = CacheControl::parse("no-cache, max-age=60").unwrap();
+/// assert_eq!(header.max_age(), Some(60));
+///
+/// let cache_control = CacheControl(vec![CacheDirective::NoStore]);
+/// let response = Response::build().header(cache_control).finalize();
+/// assert_eq!(response.headers().get_one("Cache-Control"), Some("no-store"));
+/// ```
+///
+/// # Implementing
+///
+/// Implementing `TypedHeader` requires a header name, a parser, and a
+/// `Display` implementation that formats the value such that it can be parsed
+/// again. A header sent multiple times in a request is parsed as a single,
+/// comma-separated value as permitted for list-based headers by RFC 7230.
+///
+/// ```rust
+/// # extern crate rocket;
+/// use std::fmt;
+///
+/// use rocket::http::{TypedHeader, InvalidHeader};
+///
+/// struct ApiVersion(u8);
+///
+/// impl TypedHeader for ApiVersion {
+///     const NAME: &'static str = "X-Api-Version";
+///
+///     fn parse(value: &str) -> Result<Self, InvalidHeader> {
+///         value.parse()
+///             .map(ApiVersion)
+///             .map_err(|_| InvalidHeader::new(Self::NAME, "expected a version number"))
+///     }
+/// }
+///
+/// impl fmt::Display for ApiVersion {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+/// ```
+pub trait TypedHeader: fmt::Display + Sized {
+    /// The name of the header.
+    const NAME: &'static str;
+
+    /// Parses the header value `value`.
+    fn parse(value: &str) -> Result<Self, InvalidHeader>;
+
+    /// Parses the header named [`Self::NAME`] from `headers`. Returns `None`
+    /// if the header is not present. Multiple values are joined with `, `
+    /// before being parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{TypedHeader, HeaderMap, IfNoneMatch, EntityTag};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// assert!(IfNoneMatch::from_headers(&headers).is_none());
+    ///
+    /// headers.add_raw("If-None-Match", "\"a\"");
+    /// headers.add_raw("If-None-Match", "W/\"b\"");
+    /// let header = IfNoneMatch::from_headers(&headers).unwrap().unwrap();
+    /// assert!(header.matches(&EntityTag::strong("b")));
+    /// ```
+    fn from_headers(headers: &HeaderMap<'_>) -> Option<Result<Self, InvalidHeader>> {
+        let mut values = headers.get(Self::NAME);
+        let first = values.next()?;
+        let value = values.fold(Cow::Borrowed(first), |acc, value| {
+            Cow::Owned(format!("{}, {}", acc, value))
+        });
+
+        Some(Self::parse(&value))
+    }
+}
+
+/// Creates a new `Header` with name `T::NAME` and the value set to the `Display`
+/// rendering of `T`.
+impl<'h, T: TypedHeader> From<T> for Header<'h> {
+    fn from(header: T) -> Header<'h> {
+        Header::new(T::NAME, header.to_string())
+    }
+}
+
+/// An error returned when a [`TypedHeader`] fails to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidHeader {
+    name: &'static str,
+    reason: Cow<'static, str>,
+}
+
+impl InvalidHeader {
+    /// Creates an error for the header named `name` that failed to parse for
+    /// `reason`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::InvalidHeader;
+    ///
+    /// let error = InvalidHeader::new("X-Api-Version", "expected a number");
+    /// assert_eq!(error.name(), "X-Api-Version");
+    /// assert_eq!(error.reason(), "expected a number");
+    /// ```
+    pub fn new<R>(name: &'static str, reason: R) -> InvalidHeader
+        where R: Into<Cow<'static, str>>
+    {
+        InvalidHeader { name, reason: reason.into() }
+    }
+
+    /// The name of the header that failed to parse.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Why the header failed to parse.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for InvalidHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} header: {}", self.name, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidHeader {}
+
+/// Splits `string` at every `sep` that isn't within a quoted string or a
+/// `<>`-delimited URI, trimming whitespace and skipping empty elements.
+pub(crate) fn split(string: &str, sep: char) -> impl Iterator<Item = &str> {
+    let (mut quoted, mut escaped, mut bracketed) = (false, false, false);
+    string.split(move |c| {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' if !bracketed => quoted = !quoted,
+            '<' if !quoted => bracketed = true,
+            '>' if !quoted => bracketed = false,
+            _ if c == sep => return !quoted && !bracketed,
+            _ => (),
+        }
+
+        false
+    }).map(|s| s.trim()).filter(|s| !s.is_empty())
+}
+
+/// Splits a `name=value` parameter, unquoting the value if necessary.
+pub(crate) fn param(string: &str) -> Option<(&str, Option<Cow<'_, str>>)> {
+    let mut parts = string.splitn(2, '=');
+    let name = parts.next()?.trim();
+    if !is_token(name) {
+        return None;
+    }
+
+    match parts.next() {
+        Some(value) => Some((name, Some(unquote(value.trim())?))),
+        None => Some((name, None)),
+    }
+}
+
+/// Returns the contents of `string` if it is a token or a quoted string.
+pub(crate) fn unquote(string: &str) -> Option<Cow<'_, str>> {
+    if !string.starts_with('"') {
+        return is_token(string).then(|| Cow::Borrowed(string));
+    }
+
+    if string.len() < 2 || !string.ends_with('"') {
+        return None;
+    }
+
+    let inner = &string[1..string.len() - 1];
+    if !inner.contains('\\') {
+        return (!inner.contains('"')).then(|| Cow::Borrowed(inner));
+    }
+
+    let (mut value, mut escaped) = (String::with_capacity(inner.len()), false);
+    for c in inner.chars() {
+        match c {
+            _ if escaped => { value.push(c); escaped = false; }
+            '\\' => escaped = true,
+            '"' => return None,
+            _ => value.push(c),
+        }
+    }
+
+    (!escaped).then(|| Cow::Owned(value))
+}
+
+/// Writes `value` as a token if it is one and as a quoted string otherwise.
+pub(crate) fn write_quoted(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    if is_token(value) {
+        return f.write_str(value);
+    }
+
+    f.write_str("\"")?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+
+        write!(f, "{}", c)?;
+    }
+
+    f.write_str("\"")
+}
+
+/// Returns `true` if `string` is a non-empty RFC 7230 token.
+pub(crate) fn is_token(string: &str) -> bool {
+    !string.is_empty() && string.bytes().all(|b| match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' |
+        b'^' | b'_' | b'`' | b'|' | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    fn assert_roundtrip<T: TypedHeader + std::fmt::Debug + PartialEq>(value: &str) {
+        let header = T::parse(value).expect("valid header");
+        let formatted = header.to_string();
+        assert_eq!(T::parse(&formatted).expect("reparse"), header);
+    }
+
+    #[test]
+    fn test_split() {
+        let split = |s| super::split(s, ',').collect::<Vec<_>>();
+        assert_eq!(split("a, b,,c ,"), ["a", "b", "c"]);
+        assert_eq!(split("\"a,b\", c"), ["\"a,b\"", "c"]);
+        assert_eq!(split("\"a\\\",b\", c"), ["\"a\\\",b\"", "c"]);
+        assert_eq!(split("<a,b>; rel=x, <c>"), ["<a,b>; rel=x", "<c>"]);
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(super::unquote("abc").unwrap(), "abc");
+        assert_eq!(super::unquote("\"a b\"").unwrap(), "a b");
+        assert_eq!(super::unquote("\"a\\\"b\"").unwrap(), "a\"b");
+        assert!(super::unquote("a b").is_none());
+        assert!(super::unquote("\"ab").is_none());
+        assert!(super::unquote("\"a\"b\"").is_none());
+    }
+
+    #[test]
+    fn test_entity_tags() {
+        let etag = ETag::parse("W/\"abc\"").unwrap();
+        assert!(etag.0.is_weak());
+        assert_eq!(etag.0.tag(), "abc");
+        assert_eq!(etag.to_string(), "W/\"abc\"");
+        assert!(ETag::parse("abc").is_err());
+        assert!(ETag::parse("\"a\"b\"").is_err());
+
+        let any = IfNoneMatch::parse("*").unwrap();
+        assert!(any.matches(&EntityTag::strong("x")));
+
+        let tags = IfNoneMatch::parse("\"a\", W/\"b\"").unwrap();
+        assert!(tags.matches(&EntityTag::strong("a")));
+        assert!(tags.matches(&EntityTag::strong("b")));
+        assert!(tags.matches(&EntityTag::weak("a")));
+        assert!(!tags.matches(&EntityTag::strong("c")));
+        assert!(IfNoneMatch::parse("").is_err());
+
+        assert_roundtrip::<ETag>("\"\"");
+        assert_roundtrip::<IfNoneMatch>("*");
+        assert_roundtrip::<IfNoneMatch>("\"a\", W/\"b\"");
+    }
+
+    #[test]
+    fn test_if_modified_since() {
+        let header = IfModifiedSince::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(header.0.unix_timestamp(), 784111777);
+        assert_eq!(header.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(IfModifiedSince::parse("Sunday, 06-Nov-94 08:49:37 GMT").unwrap(), header);
+        assert_eq!(IfModifiedSince::parse("Sun Nov  6 08:49:37 1994").unwrap(), header);
+
+        let header = IfModifiedSince::parse("Thursday, 01-Jan-15 00:00:00 GMT").unwrap();
+        assert_eq!(header.to_string(), "Thu, 01 Jan 2015 00:00:00 GMT");
+
+        assert!(IfModifiedSince::parse("yesterday").is_err());
+        assert!(IfModifiedSince::parse("Sunday, 06-Nov-1994 08:49:37 GMT").is_err());
+        assert!(IfModifiedSince::parse("Sun Nov 6 08:49:37").is_err());
+    }
+
+    #[test]
+    fn test_range() {
+        let range = Range::parse("bytes=0-499, 500-, -200").unwrap();
+        let expected = [ByteRange::FromTo(0, 499), ByteRange::From(500), ByteRange::Last(200)];
+        assert_eq!(range.0, expected);
+        assert_eq!(range.0[0].resolve(100), Some(0..100));
+        assert_eq!(range.0[1].resolve(1000), Some(500..1000));
+        assert_eq!(range.0[1].resolve(500), None);
+        assert_eq!(range.0[2].resolve(100), Some(0..100));
+        assert_eq!(range.0[2].resolve(1000), Some(800..1000));
+
+        assert!(Range::parse("bytes=5-1").is_err());
+        assert!(Range::parse("items=0-1").is_err());
+        assert!(Range::parse("bytes=").is_err());
+        assert_roundtrip::<Range>("bytes=0-499, 500-, -200");
+    }
+
+    #[test]
+    fn test_authorization() {
+        let auth = Authorization::parse("Bearer  abc.def").unwrap();
+        assert_eq!(auth.scheme(), "Bearer");
+        assert_eq!(auth.credentials(), "abc.def");
+        assert!(Authorization::parse("").is_err());
+        assert!(Authorization::parse("B@d abc").is_err());
+        assert_roundtrip::<Authorization>("Basic dXNlcjpwYXNz");
+    }
+
+    #[test]
+    fn test_cache_control() {
+        use CacheDirective::*;
+
+        let cc = CacheControl::parse("no-cache, MAX-AGE=\"60\", x-ext=\"a b\", private").unwrap();
+        let ext = Extension("x-ext".into(), Some("a b".into()));
+        assert_eq!(cc.0, [NoCache, MaxAge(60), ext, Private]);
+        assert_eq!(cc.max_age(), Some(60));
+        assert!(CacheControl::parse("max-age").is_err());
+        assert!(CacheControl::parse("max-age=x").is_err());
+        assert_roundtrip::<CacheControl>("no-store, s-maxage=10, max-stale, x-ext=\"a b\"");
+    }
+
+    #[test]
+    fn test_forwarded() {
+        let header = "for=192.0.2.60;proto=http;by=203.0.113.43, for=\"[2001:db8::1]:80\"";
+        let forwarded = Forwarded::parse(header).unwrap();
+        assert_eq!(forwarded.0.len(), 2);
+        assert_eq!(forwarded.0[0].for_.as_deref(), Some("192.0.2.60"));
+        assert_eq!(forwarded.0[0].proto.as_deref(), Some("http"));
+        assert_eq!(forwarded.0[0].by.as_deref(), Some("203.0.113.43"));
+        assert_eq!(forwarded.0[1].for_.as_deref(), Some("[2001:db8::1]:80"));
+        assert!(Forwarded::parse("for").is_err());
+        assert_roundtrip::<Forwarded>(header);
+    }
+
+    #[test]
+    fn test_accept_language() {
+        let header = AcceptLanguage::parse("en-US, en;q=0.5, zh-Hant-TW;q=0, *;q=0.1").unwrap();
+        assert_eq!(header.0[0], QLanguage("en-US".into(), None));
+        assert_eq!(header.0[2], QLanguage("zh-Hant-TW".into(), Some(0.0)));
+        assert_eq!(header.negotiate(&["fr", "en-GB", "en-US"]), Some(&"en-US"));
+        assert_eq!(header.negotiate(&["fr", "en-GB"]), Some(&"en-GB"));
+        assert_eq!(header.negotiate(&["fr", "zh-Hant"]), Some(&"fr"));
+        assert_eq!(header.negotiate(&["zh-Hant-TW"]), None);
+
+        assert!(AcceptLanguage::parse("").is_err());
+        assert!(AcceptLanguage::parse("en_US").is_err());
+        assert!(AcceptLanguage::parse("en;q=2").is_err());
+        assert!(AcceptLanguage::parse("en;level=1").is_err());
+        assert_roundtrip::<AcceptLanguage>("en-US, en;q=0.5, *;q=0.1");
+    }
+
+    #[test]
+    fn test_link() {
+        let header = "<https://a.b/?page=2>; rel=\"next\", </c,d>; REL=prev; title=\"C, D\"";
+        let link = Link::parse(header).unwrap();
+        assert_eq!(link.0.len(), 2);
+        assert_eq!(link.0[0].uri(), "https://a.b/?page=2");
+        assert_eq!(link.0[0].rel(), Some("next"));
+        assert_eq!(link.0[1].uri(), "/c,d");
+        assert_eq!(link.0[1].rel(), Some("prev"));
+        assert_eq!(link.0[1].param("Title"), Some("C, D"));
+        assert!(Link::parse("/a; rel=next").is_err());
+        assert_roundtrip::<Link>(header);
+    }
+
+    #[test]
+    fn test_into_header() {
+        let header: Header<'static> = ETag(EntityTag::strong("abc")).into();
+        assert_eq!(header.name().as_str(), "ETag");
+        assert_eq!(header.value(), "\"abc\"");
+    }
+}
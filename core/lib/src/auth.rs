@@ -0,0 +1,385 @@
+//! HTTP authentication request guards.
+//!
+//! This module provides request guards for the credentials of the two most
+//! common HTTP authentication schemes, [`Basic`] and [`Bearer`], and a
+//! [`Verified`] guard that checks credentials with an application-provided
+//! [`Verifier`] stored in managed state.
+//!
+//! When the credentials a guard requires are missing, malformed, or rejected,
+//! the guard fails with a status of `401 Unauthorized`. The response produced
+//! by the `401` catcher then includes a `WWW-Authenticate` header challenging
+//! the client to authenticate with the guard's scheme, unless the catcher sets
+//! the header itself.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::collections::HashMap;
+//!
+//! use rocket::Request;
+//! use rocket::auth::{Basic, Verifier, Verified};
+//!
+//! struct Users(HashMap<String, String>);
+//!
+//! #[rocket::async_trait]
+//! impl Verifier for Users {
+//!     type Credentials = Basic;
+//!     type Identity = String;
+//!
+//!     async fn verify(&self, basic: &Basic, _: &Request<'_>) -> Option<String> {
+//!         let password = self.0.get(basic.username())?;
+//!         (password == basic.password()).then(|| basic.username().to_string())
+//!     }
+//!
+//!     fn realm(&self) -> &str {
+//!         "admin area"
+//!     }
+//! }
+//!
+//! #[get("/admin")]
+//! fn admin(user: Verified<Users>) -> String {
+//!     format!("Welcome, {}!", user.0)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let users = vec![("bob".to_string(), "hunter2".to_string())];
+//!     rocket::build()
+//!         .manage(Users(users.into_iter().collect()))
+//!         .mount("/", routes![admin])
+//! }
+//! ```
+
+use std::fmt;
+use std::any::type_name;
+
+use crate::{Request, Rocket, Ignite, Sentinel, State};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome::*;
+use crate::http::{Status, Authorization, TypedHeader};
+
+/// The realm used in challenges by the [`Basic`] and [`Bearer`] guards.
+pub const DEFAULT_REALM: &str = "Rocket";
+
+/// The reason an authentication guard failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// The request has no credentials for the guard's scheme.
+    Missing,
+    /// The request's credentials are malformed.
+    Malformed(String),
+    /// The credentials were rejected by a [`Verifier`].
+    Rejected,
+    /// The [`Verifier`] is not managed.
+    Unmanaged,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Missing => f.write_str("missing credentials"),
+            AuthError::Malformed(reason) => write!(f, "malformed credentials: {}", reason),
+            AuthError::Rejected => f.write_str("rejected credentials"),
+            AuthError::Unmanaged => f.write_str("unmanaged verifier"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// The credentials of an HTTP authentication scheme.
+///
+/// `Credentials` are parsed from an `Authorization` header whose scheme is
+/// [`Credentials::SCHEME`]. Rocket implements `Credentials` for [`Basic`] and
+/// [`Bearer`].
+pub trait Credentials: Sized + Send + Sync {
+    /// The name of the authentication scheme, such as `Basic`. Schemes are
+    /// compared case-insensitively.
+    const SCHEME: &'static str;
+
+    /// Parses the credentials in an `Authorization` header, excluding the
+    /// scheme.
+    fn parse(credentials: &str) -> Result<Self, AuthError>;
+
+    /// Returns the value of a `WWW-Authenticate` header challenging a client
+    /// to authenticate in `realm` after it failed to do so due to `error`.
+    ///
+    /// The default implementation returns `$SCHEME realm="$realm"`.
+    fn challenge(realm: &str, error: &AuthError) -> String {
+        let _ = error;
+        format!("{} realm={}", Self::SCHEME, quote(realm))
+    }
+}
+
+/// Quotes `string` as an HTTP quoted string.
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Parses the credentials in `req` for scheme `C`, recording a challenge for
+/// `realm` if they are missing or malformed.
+fn credentials<C: Credentials>(req: &Request<'_>, realm: &str) -> Result<C, AuthError> {
+    let result = match Authorization::from_headers(req.headers()) {
+        Some(Ok(auth)) if auth.scheme_is(C::SCHEME) => C::parse(auth.credentials()),
+        Some(Ok(_)) | None => Err(AuthError::Missing),
+        Some(Err(e)) => Err(AuthError::Malformed(e.reason().into())),
+    };
+
+    if let Err(ref e) = result {
+        req.add_auth_challenge(C::challenge(realm, e));
+    }
+
+    result
+}
+
+/// The credentials of the `Basic` HTTP authentication scheme: a username and
+/// password.
+///
+/// # Request Guard
+///
+/// As a request guard, `Basic` succeeds if the request has an `Authorization`
+/// header with the `Basic` scheme and base64-encoded, UTF-8 credentials.
+/// Otherwise, it fails with a status of `401 Unauthorized` and challenges the
+/// client to authenticate in the [`DEFAULT_REALM`]. To authenticate in a
+/// different realm or to check the credentials, use a [`Verifier`].
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::auth::Basic;
+///
+/// #[get("/whoami")]
+/// fn whoami(basic: Basic) -> String {
+///     basic.username().to_string()
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Basic {
+    username: String,
+    password: String,
+}
+
+impl Basic {
+    /// The username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// The password.
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+impl Credentials for Basic {
+    const SCHEME: &'static str = "Basic";
+
+    fn parse(credentials: &str) -> Result<Self, AuthError> {
+        let malformed = |reason: &str| AuthError::Malformed(reason.into());
+
+        let mut buf = vec![0; credentials.len()];
+        let decoded = binascii::b64decode(credentials.as_bytes(), &mut buf)
+            .map_err(|_| malformed("invalid base64"))?;
+
+        let decoded = std::str::from_utf8(decoded).map_err(|_| malformed("invalid UTF-8"))?;
+        let mut parts = decoded.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(username), Some(password)) => Ok(Basic {
+                username: username.into(),
+                password: password.into(),
+            }),
+            _ => Err(malformed("missing `:` separator")),
+        }
+    }
+
+    /// Returns `Basic realm="$realm", charset="UTF-8"`.
+    fn challenge(realm: &str, _: &AuthError) -> String {
+        format!("Basic realm={}, charset=\"UTF-8\"", quote(realm))
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Basic {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match credentials(req, DEFAULT_REALM) {
+            Ok(basic) => Success(basic),
+            Err(e) => Failure((Status::Unauthorized, e)),
+        }
+    }
+}
+
+impl fmt::Debug for Basic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Basic")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// The credentials of the `Bearer` HTTP authentication scheme: a token.
+///
+/// # Request Guard
+///
+/// As a request guard, `Bearer` succeeds if the request has an `Authorization`
+/// header with the `Bearer` scheme and a syntactically valid token. Otherwise,
+/// it fails with a status of `401 Unauthorized` and challenges the client to
+/// authenticate in the [`DEFAULT_REALM`]. To authenticate in a different realm
+/// or to check the token, use a [`Verifier`].
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::auth::Bearer;
+///
+/// #[get("/token")]
+/// fn token(bearer: Bearer) -> String {
+///     format!("{} bytes", bearer.token().len())
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bearer {
+    token: String,
+}
+
+impl Bearer {
+    /// The token.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl Credentials for Bearer {
+    const SCHEME: &'static str = "Bearer";
+
+    fn parse(credentials: &str) -> Result<Self, AuthError> {
+        // RFC 6750: 1*( ALPHA / DIGIT / "-" / "." / "_" / "~" / "+" / "/" ) *"="
+        let token = credentials.trim_end_matches('=');
+        let valid = !token.is_empty() && token.bytes().all(|b| {
+            b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~' | b'+' | b'/')
+        });
+
+        match valid {
+            true => Ok(Bearer { token: credentials.into() }),
+            false => Err(AuthError::Malformed("invalid token".into())),
+        }
+    }
+
+    /// Returns `Bearer realm="$realm"` with an `error` parameter of
+    /// `invalid_request` for malformed tokens and `invalid_token` for rejected
+    /// tokens, as described in RFC 6750.
+    fn challenge(realm: &str, error: &AuthError) -> String {
+        let realm = quote(realm);
+        match error {
+            AuthError::Malformed(_) => {
+                format!("Bearer realm={}, error=\"invalid_request\"", realm)
+            }
+            AuthError::Rejected => format!("Bearer realm={}, error=\"invalid_token\"", realm),
+            _ => format!("Bearer realm={}", realm),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Bearer {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match credentials(req, DEFAULT_REALM) {
+            Ok(bearer) => Success(bearer),
+            Err(e) => Failure((Status::Unauthorized, e)),
+        }
+    }
+}
+
+impl fmt::Debug for Bearer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bearer").field("token", &"<redacted>").finish()
+    }
+}
+
+/// Verifies credentials, establishing the identity of the client.
+///
+/// A `Verifier` is [managed](crate::Rocket::manage()) by the application and
+/// used by the [`Verified`] request guard to authenticate requests: the guard
+/// parses the request's [`Credentials`] and calls [`Verifier::verify()`],
+/// succeeding with the returned identity. If the credentials are missing,
+/// malformed, or rejected, the guard fails with a status of `401
+/// Unauthorized` and challenges the client to authenticate in the verifier's
+/// [realm](Verifier::realm()).
+///
+/// See the [module level docs](self) for an example.
+#[crate::async_trait]
+pub trait Verifier: Send + Sync + 'static {
+    /// The credentials verified by this verifier, such as [`Basic`].
+    type Credentials: Credentials;
+
+    /// The identity of a client with verified credentials.
+    type Identity: Send;
+
+    /// Verifies `credentials`, sent with `request`, returning the client's
+    /// identity if they are valid and `None` otherwise.
+    async fn verify(
+        &self,
+        credentials: &Self::Credentials,
+        request: &Request<'_>
+    ) -> Option<Self::Identity>;
+
+    /// The realm to challenge unauthenticated clients to authenticate in.
+    ///
+    /// The default implementation returns [`DEFAULT_REALM`].
+    fn realm(&self) -> &str {
+        DEFAULT_REALM
+    }
+}
+
+/// A request guard for the identity of a client authenticated by the managed
+/// [`Verifier`] `V`.
+///
+/// If `V` is not managed, the guard fails with a status of `500 Internal
+/// Server Error`; as a [`Sentinel`], `Verified<V>` also aborts launch in this
+/// case. See [`Verifier`] for the remaining failure cases and the [module
+/// level docs](self) for an example.
+pub struct Verified<V: Verifier>(pub V::Identity);
+
+#[crate::async_trait]
+impl<'r, V: Verifier> FromRequest<'r> for Verified<V> {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let verifier = match State::<V>::get(req.rocket()) {
+            Some(verifier) => verifier,
+            None => {
+                error_!("Attempted to retrieve unmanaged verifier `{}`!", type_name::<V>());
+                return Failure((Status::InternalServerError, AuthError::Unmanaged));
+            }
+        };
+
+        let credentials = match credentials::<V::Credentials>(req, verifier.realm()) {
+            Ok(credentials) => credentials,
+            Err(e) => return Failure((Status::Unauthorized, e)),
+        };
+
+        match verifier.verify(&credentials, req).await {
+            Some(identity) => Success(Verified(identity)),
+            None => {
+                let error = AuthError::Rejected;
+                req.add_auth_challenge(V::Credentials::challenge(verifier.realm(), &error));
+                Failure((Status::Unauthorized, error))
+            }
+        }
+    }
+}
+
+impl<V: Verifier> Sentinel for Verified<V> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        <&State<V> as Sentinel>::abort(rocket)
+    }
+}
+
+impl<V: Verifier> fmt::Debug for Verified<V> where V::Identity: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Verified").field(&self.0).finish()
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+use std::convert::TryInto;
+
+use state::Container;
+
+use crate::{Route, Catcher};
+use crate::http::uri::Origin;
+use crate::fairing::{Fairing, Fairings};
+
+/// Sets a managed state value, returning its type name if one is already set.
+type StateSetter = Box<dyn FnOnce(&Container![Send + Sync]) -> Result<(), &'static str>>;
+
+/// A composable sub-application: routes, catchers, fairings, and managed state
+/// to be nested in a [`Rocket`](crate::Rocket) application under a base path.
+///
+/// A `Blueprint` is built like a [`Rocket<Build>`](crate::Rocket) with
+/// [`mount()`](Blueprint::mount()), [`register()`](Blueprint::register()),
+/// [`attach()`](Blueprint::attach()), and [`manage()`](Blueprint::manage()),
+/// and then nested into an application with [`Rocket::nest()`] or into
+/// another blueprint with [`Blueprint::nest()`]. Nesting a blueprint at `base`:
+///
+///   * mounts its routes and registers its catchers at `base`, so that a
+///     route mounted at `/charges` in a blueprint nested at `/billing` is
+///     served at `/billing/charges`;
+///
+///   * [scopes](crate::Rocket::attach_at()) its fairings' request and
+///     response callbacks to `base`. Their ignite and liftoff callbacks run as
+///     for any other fairing, with the entire application;
+///
+///   * merges its managed state into the application's.
+///
+/// Conflicts are reported when the application is ignited: routes and
+/// catchers colliding with the application's are reported as usual, and
+/// managed state of a type the application already manages is reported as an
+/// [`ErrorKind::DuplicateState`](crate::error::ErrorKind::DuplicateState).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{Blueprint, State};
+/// use rocket::fairing::AdHoc;
+/// use rocket::http::Header;
+///
+/// struct Ledger(Vec<u64>);
+///
+/// #[get("/charges")]
+/// fn charges(ledger: &State<Ledger>) -> String {
+///     format!("{:?}", ledger.0)
+/// }
+///
+/// #[catch(404)]
+/// fn not_found() -> &'static str {
+///     "no such billing resource"
+/// }
+///
+/// fn billing() -> Blueprint {
+///     Blueprint::new()
+///         .mount("/", routes![charges])
+///         .register("/", catchers![not_found])
+///         .manage(Ledger(vec![10, 20]))
+///         .attach(AdHoc::on_response("Billing", |_, res| Box::pin(async move {
+///             res.set_header(Header::new("X-Service", "billing"));
+///         })))
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().nest("/billing", billing())
+/// }
+/// ```
+#[derive(Default)]
+pub struct Blueprint {
+    pub(crate) routes: Vec<Route>,
+    pub(crate) catchers: Vec<Catcher>,
+    pub(crate) fairings: Fairings,
+    pub(crate) state: Vec<(&'static str, StateSetter)>,
+}
+
+impl Blueprint {
+    /// Creates a new, empty `Blueprint`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Blueprint;
+    ///
+    /// let blueprint = Blueprint::new();
+    /// ```
+    pub fn new() -> Blueprint {
+        Blueprint::default()
+    }
+
+    /// Mounts `routes` at `base` within the blueprint, as with
+    /// [`Rocket::mount()`](crate::Rocket::mount()).
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`Rocket::mount()`](crate::Rocket::mount()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Blueprint;
+    ///
+    /// #[get("/login")]
+    /// fn login() { /* .. */ }
+    ///
+    /// let auth = Blueprint::new().mount("/", routes![login]);
+    /// ```
+    pub fn mount<'a, B, R>(mut self, base: B, routes: R) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let base = crate::rocket::parse_base("route", base);
+        for route in routes.into() {
+            self.routes.push(map_route(&base, route));
+        }
+
+        self
+    }
+
+    /// Registers `catchers` at `base` within the blueprint, as with
+    /// [`Rocket::register()`](crate::Rocket::register()).
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`Rocket::register()`](crate::Rocket::register()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Blueprint;
+    ///
+    /// #[catch(401)]
+    /// fn unauthorized() -> &'static str { "log in first" }
+    ///
+    /// let auth = Blueprint::new().register("/", catchers![unauthorized]);
+    /// ```
+    pub fn register<'a, B, C>(mut self, base: B, catchers: C) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              C: Into<Vec<Catcher>>
+    {
+        let base = crate::rocket::parse_base("catcher", base);
+        for catcher in catchers.into() {
+            self.catchers.push(map_catcher(&base, catcher));
+        }
+
+        self
+    }
+
+    /// Attaches `fairing` to the blueprint. Its request and response callbacks
+    /// run only for requests under the base at which the blueprint is nested.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Blueprint;
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let admin = Blueprint::new()
+    ///     .attach(AdHoc::on_request("Audit", |req, _| Box::pin(async move {
+    ///         println!("admin request: {}", req);
+    ///     })));
+    /// ```
+    pub fn attach<F: Fairing>(mut self, fairing: F) -> Self {
+        self.fairings.add(Box::new(fairing));
+        self
+    }
+
+    /// Attaches `fairing` to the blueprint scoped to `base` within the
+    /// blueprint, as with [`Rocket::attach_at()`](crate::Rocket::attach_at()).
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`Rocket::attach_at()`](crate::Rocket::attach_at()).
+    pub fn attach_at<'a, B, F>(mut self, base: B, fairing: F) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              F: Fairing,
+    {
+        let base = crate::rocket::parse_static_base("fairing", base);
+        self.fairings.add_scoped(Some(base), Box::new(fairing));
+        self
+    }
+
+    /// Adds `state` to the state the blueprint contributes to an application.
+    /// Unlike [`Rocket::manage()`](crate::Rocket::manage()), a duplicate type
+    /// doesn't panic. Instead, it is reported when the application is ignited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Blueprint;
+    ///
+    /// struct Sessions(usize);
+    ///
+    /// let auth = Blueprint::new().manage(Sessions(0));
+    /// ```
+    pub fn manage<T: Send + Sync + 'static>(mut self, state: T) -> Self {
+        let type_name = std::any::type_name::<T>();
+        let setter = move |container: &Container![Send + Sync]| {
+            match container.set(state) {
+                true => Ok(()),
+                false => Err(type_name),
+            }
+        };
+
+        self.state.push((type_name, Box::new(setter)));
+        self
+    }
+
+    /// Nests `blueprint` at `base` within this blueprint. Nesting is
+    /// associative: nesting `b` at `/b` in `a` and `a` at `/a` in an
+    /// application is equivalent to nesting `b` at `/a/b` in the application.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`Rocket::nest()`](crate::Rocket::nest()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Blueprint;
+    ///
+    /// #[get("/")]
+    /// fn invoices() { /* .. */ }
+    ///
+    /// let invoicing = Blueprint::new().mount("/", routes![invoices]);
+    /// let billing = Blueprint::new().nest("/invoices", invoicing);
+    /// ```
+    pub fn nest<'a, B>(mut self, base: B, blueprint: Blueprint) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+    {
+        let base = crate::rocket::parse_static_base("blueprint", base);
+        let (routes, catchers) = (blueprint.routes, blueprint.catchers);
+        self.routes.extend(routes.into_iter().map(|r| map_route(&base, r)));
+        self.catchers.extend(catchers.into_iter().map(|c| map_catcher(&base, c)));
+        self.fairings.append_at(&base, blueprint.fairings);
+        self.state.extend(blueprint.state);
+        self
+    }
+}
+
+pub(crate) fn map_route(base: &Origin<'_>, route: Route) -> Route {
+    route.clone().map_base(|old| format!("{}{}", base, old))
+        .unwrap_or_else(|e| {
+            error!("malformed URI in route {}", route);
+            error_!("{}", e);
+            panic!("aborting due to invalid route URI");
+        })
+}
+
+pub(crate) fn map_catcher(base: &Origin<'_>, catcher: Catcher) -> Catcher {
+    catcher.clone().map_base(|old| format!("{}{}", base, old))
+        .unwrap_or_else(|e| {
+            error!("malformed URI in catcher {}", catcher);
+            error_!("{}", e);
+            panic!("aborting due to invalid catcher URI");
+        })
+}
+
+impl fmt::Debug for Blueprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blueprint")
+            .field("routes", &self.routes)
+            .field("catchers", &self.catchers)
+            .field("fairings", &self.fairings)
+            .field("state", &self.state.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .finish()
+    }
+}
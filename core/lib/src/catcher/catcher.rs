@@ -5,6 +5,7 @@ use crate::response::Response;
 use crate::request::Request;
 use crate::http::{Status, ContentType, uri};
 use crate::catcher::{Handler, BoxFuture};
+use crate::route::Host;
 
 use yansi::Paint;
 
@@ -35,6 +36,10 @@ use yansi::Paint;
 ///
 ///  * It is a default catcher _or_ has a status code matching the error code.
 ///  * Its base is a prefix of the normalized/decoded request URI path.
+///  * Its [`Host`] (if any) matches the request's `Host` header.
+///
+/// If several catchers match, the one with the longest base is preferred.
+/// Among those, a catcher with a host is preferred to one without.
 ///
 /// A _default_ catcher is a catcher with no explicit status code: `None`. The
 /// catcher's _base_ is provided as the first argument to
@@ -113,6 +118,9 @@ pub struct Catcher {
     /// The HTTP status to match against if this route is not `default`.
     pub code: Option<u16>,
 
+    /// The host to match against, if any.
+    pub host: Option<Host>,
+
     /// The catcher's associated error handler.
     pub handler: Box<dyn Handler>,
 }
@@ -166,6 +174,7 @@ impl Catcher {
             name: None,
             base: uri::Origin::new("/", None::<&str>),
             handler: Box::new(handler),
+            host: None,
             code,
         }
     }
@@ -259,9 +268,15 @@ impl fmt::Display for Catcher {
         }
 
         match self.code {
-            Some(code) => write!(f, "{}", Paint::blue(code)),
-            None => write!(f, "{}", Paint::blue("default"))
+            Some(code) => write!(f, "{}", Paint::blue(code))?,
+            None => write!(f, "{}", Paint::blue("default"))?,
         }
+
+        if let Some(ref host) = self.host {
+            write!(f, " @ {}", Paint::magenta(host))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -271,6 +286,7 @@ impl fmt::Debug for Catcher {
             .field("name", &self.name)
             .field("base", &self.base)
             .field("code", &self.code)
+            .field("host", &self.host)
             .finish()
     }
 }
@@ -0,0 +1,143 @@
+use std::fmt;
+use std::str::FromStr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use serde::{de, Serialize, Serializer, Deserialize, Deserializer};
+
+/// A range of IP addresses in CIDR notation, such as `10.0.0.0/8`.
+///
+/// A `Cidr` is used to configure [`Config::trusted_proxies`]. A single address
+/// without a prefix length, such as `127.0.0.1` or `::1`, is a range containing
+/// only that address. IPv4 ranges also contain the IPv4-mapped IPv6 form of
+/// their addresses.
+///
+/// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+///
+/// ## (De)serialization
+///
+/// A `Cidr` serializes and deserializes as a string in CIDR notation.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::Cidr;
+///
+/// let private: Cidr = "10.0.0.0/8".parse().unwrap();
+/// assert!(private.contains("10.1.2.3".parse().unwrap()));
+/// assert!(private.contains("::ffff:10.1.2.3".parse().unwrap()));
+/// assert!(!private.contains("11.0.0.1".parse().unwrap()));
+///
+/// let localhost: Cidr = "::1".parse().unwrap();
+/// assert_eq!(localhost.to_string(), "::1/128");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    /// Creates the range of addresses whose first `prefix_len` bits are equal
+    /// to those of `addr`. Returns `None` if `prefix_len` is greater than the
+    /// number of bits in `addr`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use rocket::config::Cidr;
+    ///
+    /// let cidr = Cidr::new(Ipv4Addr::new(192, 168, 1, 7).into(), 24).unwrap();
+    /// assert_eq!(cidr.to_string(), "192.168.1.0/24");
+    ///
+    /// assert!(Cidr::new(Ipv4Addr::LOCALHOST.into(), 33).is_none());
+    /// ```
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Option<Cidr> {
+        let addr = match addr {
+            IpAddr::V4(v4) if prefix_len <= 32 => {
+                Ipv4Addr::from(u32::from(v4) & mask_u32(prefix_len)).into()
+            }
+            IpAddr::V6(v6) if prefix_len <= 128 => {
+                Ipv6Addr::from(u128::from(v6) & mask_u128(prefix_len)).into()
+            }
+            _ => return None,
+        };
+
+        Some(Cidr { addr, prefix_len })
+    }
+
+    /// The first address in the range.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// The number of leading bits shared by all addresses in the range.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns `true` if `ip` is in the range.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                u32::from(ip) & mask_u32(self.prefix_len) == u32::from(net)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                u128::from(ip) & mask_u128(self.prefix_len) == u128::from(net)
+            }
+            (IpAddr::V4(_), IpAddr::V6(ip)) => match ip.segments() {
+                [0, 0, 0, 0, 0, 0xffff, ..] => self.contains(ip.to_ipv4().unwrap().into()),
+                _ => false,
+            },
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+}
+
+fn mask_u32(prefix_len: u8) -> u32 {
+    u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0)
+}
+
+fn mask_u128(prefix_len: u8) -> u128 {
+    u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0)
+}
+
+impl FromStr for Cidr {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "an IP address or a CIDR range such as `10.0.0.0/8`";
+
+        let mut parts = s.trim().splitn(2, '/');
+        let addr: IpAddr = parts.next().and_then(|a| a.parse().ok()).ok_or(EXPECTED)?;
+        let prefix_len = match parts.next() {
+            Some(len) => len.parse().map_err(|_| EXPECTED)?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+
+        Cidr::new(addr, prefix_len).ok_or(EXPECTED)
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl Serialize for Cidr {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cidr {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(de)?;
+        Cidr::from_str(&string).map_err(|expected| de::Error::invalid_value(
+            de::Unexpected::Str(&string),
+            &expected
+        ))
+    }
+}
@@ -7,7 +7,7 @@ use figment::value::{Map, Dict};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{TlsConfig, LogLevel, Shutdown};
+use crate::config::{TlsConfig, LogLevel, Shutdown, TrailingSlash, Cidr};
 use crate::request::{self, Request, FromRequest};
 use crate::data::Limits;
 
@@ -89,6 +89,21 @@ pub struct Config {
     pub log_level: LogLevel,
     /// Graceful shutdown configuration. **(default: [`Shutdown::default()`])**
     pub shutdown: Shutdown,
+    /// Whether to respond with `405 Method Not Allowed` and an `Allow` header,
+    /// instead of `404 Not Found`, when only routes with other methods match a
+    /// request. **(default: `false`)**
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub method_not_allowed: bool,
+    /// How trailing slashes in request paths are matched against routes.
+    /// **(default: [`TrailingSlash::MatchBoth`])**
+    pub trailing_slash: TrailingSlash,
+    /// Addresses of reverse proxies whose `Forwarded`, `X-Forwarded-For`,
+    /// `X-Forwarded-Proto`, `X-Forwarded-Host`, and `X-Real-IP` headers are
+    /// trusted to describe the original client. Headers from other peers are
+    /// ignored. **(default: `[]`)**
+    ///
+    /// See [`Request::client_ip()`](crate::Request::client_ip()) for details.
+    pub trusted_proxies: Vec<Cidr>,
     /// Whether to use colors and emoji when logging. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub cli_colors: bool,
@@ -152,6 +167,9 @@ impl Config {
             log_level: LogLevel::Normal,
             cli_colors: true,
             shutdown: Shutdown::default(),
+            method_not_allowed: false,
+            trailing_slash: TrailingSlash::default(),
+            trusted_proxies: vec![],
         }
     }
 
@@ -318,6 +336,16 @@ impl Config {
         launch_info_!("log level: {}", Paint::default(self.log_level).bold());
         launch_info_!("cli colors: {}", Paint::default(&self.cli_colors).bold());
         launch_info_!("shutdown: {}", Paint::default(&self.shutdown).bold());
+        launch_info_!("method not allowed: {}", Paint::default(&self.method_not_allowed).bold());
+        launch_info_!("trailing slash: {}", Paint::default(&self.trailing_slash).bold());
+        match self.trusted_proxies.is_empty() {
+            true => launch_info_!("trusted proxies: {}", Paint::default("none").bold()),
+            false => {
+                let proxies = self.trusted_proxies.iter().map(|p| p.to_string());
+                let proxies = proxies.collect::<Vec<_>>().join(", ");
+                launch_info_!("trusted proxies: {}", Paint::default(proxies).bold());
+            }
+        }
 
         // Check for now depreacted config values.
         for (key, replacement) in Self::DEPRECATED_KEYS {
@@ -400,6 +428,18 @@ impl Config {
 
     /// The stringy parameter name for setting/extracting [`Config::shutdown`].
     pub const SHUTDOWN: &'static str = "shutdown";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::method_not_allowed`].
+    pub const METHOD_NOT_ALLOWED: &'static str = "method_not_allowed";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trailing_slash`].
+    pub const TRAILING_SLASH: &'static str = "trailing_slash";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trusted_proxies`].
+    pub const TRUSTED_PROXIES: &'static str = "trusted_proxies";
 }
 
 impl Provider for Config {
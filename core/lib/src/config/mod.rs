@@ -114,6 +114,8 @@
 mod config;
 mod tls;
 mod shutdown;
+mod trailing_slash;
+mod cidr;
 
 #[cfg(feature = "secrets")]
 mod secret_key;
@@ -124,6 +126,8 @@ pub use config::Config;
 pub use crate::log::LogLevel;
 pub use shutdown::Shutdown;
 pub use tls::TlsConfig;
+pub use trailing_slash::TrailingSlash;
+pub use cidr::Cidr;
 
 #[cfg(feature = "secrets")]
 #[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
@@ -0,0 +1,74 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How the router treats a trailing slash in a request path.
+///
+/// A route declared as `/foo/` has a trailing slash while one declared as
+/// `/foo` does not. A request's path has a trailing slash if it is not `/` and
+/// ends with a `/`. The policy determines whether the two must agree for a
+/// request to match a route. Routes whose path ends with a `<trailing..>`
+/// parameter match irrespective of the policy.
+///
+/// The policy is applied consistently: two routes that differ only in their
+/// trailing slash collide under [`TrailingSlash::MatchBoth`] but not under
+/// [`TrailingSlash::Strict`] or [`TrailingSlash::Redirect`], and `uri!`
+/// generates URIs with the trailing slash of the route's declaration.
+///
+/// ## (De)serialization
+///
+/// A `TrailingSlash` serializes and deserializes as a snake case string equal
+/// to the name of the variant: `"strict"`, `"match_both"`, or `"redirect"`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::config::{Config, TrailingSlash};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let figment = Config::figment()
+///         .merge((Config::TRAILING_SLASH, TrailingSlash::Redirect));
+///
+///     rocket::custom(figment)
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    /// A request matches a route only if both or neither have a trailing
+    /// slash: `/foo/` matches `/foo/` but not `/foo`.
+    Strict,
+    /// The trailing slash is ignored: `/foo` and `/foo/` match both `/foo` and
+    /// `/foo/`. This is the default.
+    MatchBoth,
+    /// Like [`TrailingSlash::Strict`], but a request that matches no route is
+    /// responded to with a `308 Permanent Redirect` to the same URI with the
+    /// trailing slash added or removed if some route matches that URI instead.
+    Redirect,
+}
+
+impl TrailingSlash {
+    /// Returns `true` if a request's trailing slash must agree with a route's
+    /// for the route to match.
+    pub(crate) fn is_strict(self) -> bool {
+        self != TrailingSlash::MatchBoth
+    }
+}
+
+impl Default for TrailingSlash {
+    fn default() -> Self {
+        TrailingSlash::MatchBoth
+    }
+}
+
+impl fmt::Display for TrailingSlash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrailingSlash::Strict => "strict".fmt(f),
+            TrailingSlash::MatchBoth => "match both".fmt(f),
+            TrailingSlash::Redirect => "redirect".fmt(f),
+        }
+    }
+}
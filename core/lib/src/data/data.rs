@@ -2,6 +2,13 @@ use crate::tokio::io::AsyncReadExt;
 use crate::data::data_stream::DataStream;
 use crate::data::{ByteUnit, StreamReader};
 
+use std::io::Cursor;
+
+use crate::ext::Chain;
+use crate::tokio::io::{AsyncRead, BufReader};
+use crate::data::{Digest, Digested};
+use crate::data::digest::DigestReader;
+
 /// The number of bytes to read into the "peek" buffer.
 pub const PEEK_BYTES: usize = 512;
 
@@ -86,6 +93,59 @@ impl Data {
         DataStream::new(self.buffer, self.stream, limit.into())
     }
 
+    /// Like [`Data::open()`], but reading more than `limit` bytes from the
+    /// returned stream fails with an error instead of ending the stream.
+    pub(crate) fn open_strict(self, limit: ByteUnit) -> DataStream {
+        DataStream::strict(self.buffer, self.stream, limit.into())
+    }
+
+    /// Returns a `Data` whose stream is the one returned by `map` when invoked
+    /// with a buffered reader of all of the data in `self`.
+    pub(crate) fn map_reader<F, R>(self, map: F) -> Data
+        where F: FnOnce(BufReader<Chain<Cursor<Vec<u8>>, StreamReader>>) -> R,
+              R: AsyncRead + Send + 'static
+    {
+        let raw = Chain::new(Cursor::new(self.buffer), self.stream);
+        Data::from(StreamReader::from_reader(map(BufReader::new(raw))))
+    }
+
+    /// Feeds every byte subsequently read from the returned `Data` to `digest`
+    /// as it is read, returning the new `Data` and a handle to the digest.
+    ///
+    /// This allows a data guard to compute a digest, such as a hash or MAC, of
+    /// the raw body while another data guard consumes it. Once the body has
+    /// been read to completion, the digest's [`Digest::finish()`] method is
+    /// called; if it returns an error, the error is returned in place of the
+    /// end of the body, failing the read. The digest is retrieved with
+    /// [`Digested::into_inner()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, Digest, ToByteUnit};
+    ///
+    /// #[derive(Default)]
+    /// struct Checksum(u8);
+    ///
+    /// impl Digest for Checksum {
+    ///     fn update(&mut self, bytes: &[u8]) {
+    ///         self.0 = bytes.iter().fold(self.0, |sum, b| sum.wrapping_add(*b));
+    ///     }
+    /// }
+    ///
+    /// async fn checksummed(data: Data) -> std::io::Result<(String, u8)> {
+    ///     let (data, digested) = data.digest(Checksum::default());
+    ///     let string = data.open(1.kibibytes()).into_string().await?;
+    ///     let checksum = digested.into_inner().map_or(0, |checksum| checksum.0);
+    ///     Ok((string.into_inner(), checksum))
+    /// }
+    /// ```
+    pub fn digest<D: Digest>(self, digest: D) -> (Data, Digested<D>) {
+        let digested = Digested::new(digest);
+        let handle = digested.handle();
+        (self.map_reader(move |reader| DigestReader::new(reader, handle)), digested)
+    }
+
     /// Retrieve at most `num` bytes from the `peek` buffer without consuming
     /// `self`.
     ///
@@ -41,6 +41,9 @@ use crate::data::{Capped, N};
 /// [`DataStream::stream_to(&mut file)`]: DataStream::stream_to()
 pub struct DataStream {
     pub(crate) chain: Take<Chain<Cursor<Vec<u8>>, StreamReader>>,
+    // Whether exceeding the limit fails reads instead of ending the stream. If
+    // so, `chain` is limited to one byte more than the limit to detect this.
+    strict: bool,
 }
 
 /// An adapter: turns a `T: Stream` (in `StreamKind`) into a `tokio::AsyncRead`.
@@ -59,13 +62,26 @@ enum State {
 /// The kinds of streams we accept as `Data`.
 enum StreamKind {
     Body(hyper::Body),
-    Multipart(multer::Field)
+    Multipart(multer::Field),
+    Reader(Pin<Box<dyn Stream<Item = io::Result<hyper::Bytes>> + Send>>),
 }
 
 impl DataStream {
     pub(crate) fn new(buf: Vec<u8>, stream: StreamReader, limit: u64) -> Self {
         let chain = Chain::new(Cursor::new(buf), stream).take(limit);
-        Self { chain }
+        Self { chain, strict: false }
+    }
+
+    /// Like [`DataStream::new()`], but reads fail with an error once more than
+    /// `limit` bytes have been read instead of the stream ending at `limit`.
+    pub(crate) fn strict(buf: Vec<u8>, stream: StreamReader, limit: u64) -> Self {
+        let chain = Chain::new(Cursor::new(buf), stream).take(limit.saturating_add(1));
+        Self { chain, strict: true }
+    }
+
+    /// The number of bytes that can be read before the limit is reached.
+    fn remaining(&self) -> u64 {
+        self.chain.limit().saturating_sub(self.strict as u64)
     }
 
     /// Whether a previous read exhausted the set limit _and then some_.
@@ -93,7 +109,7 @@ impl DataStream {
     /// ```
     pub fn hint(&self) -> usize {
         let buf_len = self.chain.get_ref().get_ref().0.get_ref().len();
-        std::cmp::min(buf_len, self.chain.limit() as usize)
+        std::cmp::min(buf_len, self.remaining() as usize)
     }
 
     /// A helper method to write the body of the request to any `AsyncWrite`
@@ -230,6 +246,12 @@ impl StreamReader {
     pub fn empty() -> Self {
         Self { inner: StreamKind::Body(hyper::Body::empty()), state: State::Done }
     }
+
+    /// A stream of the bytes read from `reader`.
+    pub fn from_reader<R: AsyncRead + Send + 'static>(reader: R) -> Self {
+        let stream = tokio_util::io::ReaderStream::new(reader);
+        Self { inner: StreamKind::Reader(Box::pin(stream)), state: State::Pending }
+    }
 }
 
 impl From<hyper::Body> for StreamReader {
@@ -251,7 +273,13 @@ impl AsyncRead for DataStream {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        Pin::new(&mut self.chain).poll_read(cx, buf)
+        ready!(Pin::new(&mut self.chain).poll_read(cx, buf))?;
+        if self.strict && self.chain.limit() == 0 {
+            let error = io::Error::new(io::ErrorKind::Other, "data limit exceeded");
+            return Poll::Ready(Err(error));
+        }
+
+        Poll::Ready(Ok(()))
     }
 }
 
@@ -267,6 +295,7 @@ impl Stream for StreamKind {
                 .map_err_ext(|e| io::Error::new(io::ErrorKind::Other, e)),
             StreamKind::Multipart(mp) => Pin::new(mp).poll_next(cx)
                 .map_err_ext(|e| io::Error::new(io::ErrorKind::Other, e)),
+            StreamKind::Reader(stream) => stream.as_mut().poll_next(cx),
         }
     }
 
@@ -274,6 +303,7 @@ impl Stream for StreamKind {
         match self {
             StreamKind::Body(body) => body.size_hint(),
             StreamKind::Multipart(mp) => mp.size_hint(),
+            StreamKind::Reader(stream) => stream.size_hint(),
         }
     }
 }
@@ -0,0 +1,142 @@
+use std::pin::Pin;
+
+use async_compression::tokio::bufread::{GzipDecoder, ZlibDecoder, DeflateDecoder, BrotliDecoder};
+
+use crate::{Request, Data};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::Status;
+use crate::tokio::io::{AsyncBufRead, AsyncRead};
+
+/// Decompresses `gzip`, `deflate`, and `br` encoded request bodies.
+///
+/// When attached, this fairing decodes the body of every request with a
+/// `Content-Encoding` header before any route sees it, so that data guards like
+/// [`Form`](crate::form::Form) and `Json` operate on the decompressed data. The
+/// `Content-Encoding` and `Content-Length` headers, which no longer describe
+/// the body, are removed from the request. Requests with a content coding other
+/// than `gzip` (or `x-gzip`), `deflate`, `br`, and `identity` are responded to
+/// with a `415 Unsupported Media Type`. A body that fails to decode causes an
+/// I/O error when it is read.
+///
+/// Decompression is streaming: data is only decoded as it is read. As
+/// [`Limits`](crate::data::Limits) are applied to the data that is read, they
+/// limit the _decompressed_ size of a body, protecting against decompression
+/// bombs.
+///
+/// This fairing is only available when Rocket's `decompression` feature is
+/// enabled.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::Decompression;
+///
+/// #[post("/", data = "<text>")]
+/// fn echo(text: String) -> String {
+///     text
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(Decompression::fairing())
+///         .mount("/", routes![echo])
+/// }
+/// ```
+pub struct Decompression(());
+
+/// A supported content coding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Coding {
+    Gzip,
+    Deflate,
+    Brotli,
+}
+
+impl Decompression {
+    /// Returns a fairing that decompresses incoming request bodies.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Decompression;
+    ///
+    /// let rocket = rocket::build().attach(Decompression::fairing());
+    /// ```
+    pub fn fairing() -> Decompression {
+        Decompression(())
+    }
+}
+
+impl Coding {
+    /// Parses the codings in the `Content-Encoding` headers of `req`, in the
+    /// order they were applied. Returns the first unsupported coding on error.
+    fn parse_all(req: &Request<'_>) -> Result<Vec<Coding>, String> {
+        let codings = req.headers().get("Content-Encoding").flat_map(|v| v.split(','));
+        let mut parsed = vec![];
+        for coding in codings.map(|c| c.trim()).filter(|c| !c.is_empty()) {
+            match &*coding.to_ascii_lowercase() {
+                "identity" => continue,
+                "gzip" | "x-gzip" => parsed.push(Coding::Gzip),
+                "deflate" => parsed.push(Coding::Deflate),
+                "br" => parsed.push(Coding::Brotli),
+                _ => return Err(coding.into()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Returns a reader that decodes `reader`. `header` is the start of the
+    /// encoded data, used to detect raw, zlib-less `deflate` data.
+    fn decoder<R>(self, reader: R, header: &[u8]) -> Pin<Box<dyn AsyncRead + Send>>
+        where R: AsyncBufRead + Send + 'static
+    {
+        match self {
+            Coding::Gzip => Box::pin(GzipDecoder::new(reader)),
+            Coding::Deflate if is_zlib_header(header) => Box::pin(ZlibDecoder::new(reader)),
+            Coding::Deflate => Box::pin(DeflateDecoder::new(reader)),
+            Coding::Brotli => Box::pin(BrotliDecoder::new(reader)),
+        }
+    }
+}
+
+/// Returns `true` if `header` is a zlib header: the deflate method followed by
+/// a check value making the pair a multiple of 31.
+fn is_zlib_header(header: &[u8]) -> bool {
+    match *header {
+        [cmf, flg] => cmf & 0x0f == 8 && u16::from_be_bytes([cmf, flg]) % 31 == 0,
+        _ => false,
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Decompression {
+    fn info(&self) -> Info {
+        Info { name: "Request Decompression", kind: Kind::Request }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data) {
+        let codings = match Coding::parse_all(req) {
+            Ok(codings) if codings.is_empty() => return,
+            Ok(codings) => codings,
+            Err(coding) => {
+                warn_!("Unsupported request content coding: {}.", coding);
+                req.short_circuit(Status::UnsupportedMediaType);
+                return;
+            }
+        };
+
+        // Codings are listed in the order they were applied; undo in reverse.
+        let mut decoded = std::mem::replace(data, Data::local(vec![]));
+        for coding in codings.into_iter().rev() {
+            let header = decoded.peek(2).await.to_vec();
+            decoded = decoded.map_reader(|reader| coding.decoder(reader, &header));
+        }
+
+        *data = decoded;
+        req.remove_header("Content-Encoding");
+        req.remove_header("Content-Length");
+    }
+}
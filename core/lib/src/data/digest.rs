@@ -0,0 +1,134 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use parking_lot::Mutex;
+use pin_project_lite::pin_project;
+
+use crate::tokio::io::{AsyncRead, ReadBuf};
+
+/// A streaming digest, such as a hash or MAC, of the body of a request.
+///
+/// A `Digest` is fed the raw bytes of a request body, as they are read, by the
+/// [`Data`](crate::data::Data) returned from
+/// [`Data::digest()`](crate::data::Data::digest()). Rocket does not provide
+/// any digest algorithms; implementations typically wrap a type from a crate
+/// like `sha2` or `hmac`.
+///
+/// # Example
+///
+/// A digest that counts the number of bytes in a body, rejecting empty ones:
+///
+/// ```rust
+/// use std::io;
+/// use rocket::data::Digest;
+///
+/// struct Length(usize);
+///
+/// impl Digest for Length {
+///     fn update(&mut self, bytes: &[u8]) {
+///         self.0 += bytes.len();
+///     }
+///
+///     fn finish(&mut self) -> io::Result<()> {
+///         match self.0 {
+///             0 => Err(io::Error::new(io::ErrorKind::InvalidData, "empty body")),
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+/// ```
+pub trait Digest: Send + 'static {
+    /// Feeds the next `bytes` of the body to the digest.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Called once the body has been read to completion. If an error is
+    /// returned, it is returned to the reader in place of the end of the body.
+    ///
+    /// The default implementation returns `Ok(())`.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A handle to a [`Digest`] being computed by [`Data::digest()`].
+///
+/// [`Data::digest()`]: crate::data::Data::digest()
+pub struct Digested<D> {
+    inner: Arc<Mutex<Inner<D>>>,
+}
+
+struct Inner<D> {
+    digest: Option<D>,
+    complete: bool,
+}
+
+impl<D: Digest> Digested<D> {
+    pub(crate) fn new(digest: D) -> Self {
+        let inner = Inner { digest: Some(digest), complete: false };
+        Digested { inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    pub(crate) fn handle(&self) -> Self {
+        Digested { inner: self.inner.clone() }
+    }
+
+    /// Returns `true` if the entire body was fed to the digest and
+    /// [`Digest::finish()`] succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.inner.lock().complete
+    }
+
+    /// Returns the digest if the entire body was fed to it and
+    /// [`Digest::finish()`] succeeded. Otherwise returns `None`.
+    pub fn into_inner(self) -> Option<D> {
+        let mut inner = self.inner.lock();
+        match inner.complete {
+            true => inner.digest.take(),
+            false => None,
+        }
+    }
+}
+
+pin_project! {
+    /// Feeds the bytes read from `reader` to a digest.
+    pub(crate) struct DigestReader<R, D> {
+        #[pin]
+        reader: R,
+        digested: Digested<D>,
+    }
+}
+
+impl<R: AsyncRead, D: Digest> DigestReader<R, D> {
+    pub(crate) fn new(reader: R, digested: Digested<D>) -> Self {
+        DigestReader { reader, digested }
+    }
+}
+
+impl<R: AsyncRead, D: Digest> AsyncRead for DigestReader<R, D> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let me = self.project();
+        let start = buf.filled().len();
+        futures::ready!(me.reader.poll_read(cx, buf))?;
+
+        let mut inner = me.digested.inner.lock();
+        let read = &buf.filled()[start..];
+        if let (false, Some(digest)) = (inner.complete, inner.digest.as_mut()) {
+            if !read.is_empty() {
+                digest.update(read);
+            } else if let Err(e) = digest.finish() {
+                inner.digest = None;
+                return Poll::Ready(Err(e));
+            } else {
+                inner.complete = true;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
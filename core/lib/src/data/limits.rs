@@ -64,8 +64,10 @@ use crate::http::uncased::Uncased;
 /// | `file/$ext`       | _N/A_   | [`TempFile`] | file form field with extension `$ext` |
 /// | `string`          | 8KiB    | [`String`]   | data guard or data form field         |
 /// | `bytes`           | 8KiB    | [`Vec<u8>`]  | data guard                            |
+/// | `service`         | 8KiB    | [`Service`]  | request body streamed to a service    |
 ///
 /// [`TempFile`]: crate::data::TempFile
+/// [`Service`]: crate::tower::ServiceHandler
 ///
 /// # Usage
 ///
@@ -119,6 +121,23 @@ use crate::http::uncased::Uncased;
 ///     }
 /// }
 /// ```
+///
+/// # Per-Route Limits
+///
+/// Configured limits can be overridden for a single route via the `limits`
+/// argument of its route attribute. Both the `&Limits` request guard and
+/// [`Request::limits()`] return the overridden limits for requests to the
+/// route. Names that aren't identifiers are written as strings:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::{Capped, TempFile};
+///
+/// #[post("/upload", data = "<file>", limits(file = "500MiB", "file/pdf" = "1GiB"))]
+/// async fn upload(file: Capped<TempFile<'_>>) -> String {
+///     format!("{} bytes", file.n.written)
+/// }
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Limits {
@@ -290,6 +309,26 @@ impl Limits {
 
         None
     }
+
+    /// Returns a copy of `self` with every limit in `overrides` added or
+    /// replaced. A limit in `overrides` also replaces the limits below it in
+    /// `self`: overriding `file` removes a `file/pdf` limit in `self`.
+    pub(crate) fn overridden_by(&self, overrides: &Limits) -> Limits {
+        let mut limits = self.clone();
+        for (name, limit) in overrides.limits.iter() {
+            let prefix = name.as_str().as_bytes();
+            limits.limits.retain(|(k, _)| {
+                let k = k.as_str().as_bytes();
+                k.len() <= prefix.len()
+                    || k[prefix.len()] != b'/'
+                    || !k[..prefix.len()].eq_ignore_ascii_case(prefix)
+            });
+
+            limits = limits.limit(name.clone(), *limit);
+        }
+
+        limits
+    }
 }
 
 impl fmt::Display for Limits {
@@ -7,6 +7,10 @@ mod data_stream;
 mod from_data;
 mod limits;
 mod temp_file;
+mod digest;
+
+#[cfg(feature = "decompression")]
+mod decompression;
 
 pub use self::data::Data;
 pub use self::data_stream::DataStream;
@@ -15,5 +19,10 @@ pub use self::limits::Limits;
 pub use self::capped::{N, Capped};
 pub use ubyte::{ByteUnit, ToByteUnit};
 pub use temp_file::TempFile;
+pub use self::digest::{Digest, Digested};
+
+#[cfg(feature = "decompression")]
+#[cfg_attr(nightly, doc(cfg(feature = "decompression")))]
+pub use self::decompression::Decompression;
 
 pub(crate) use self::data_stream::StreamReader;
@@ -89,6 +89,9 @@ pub enum ErrorKind {
     SentinelAborts(Vec<crate::sentinel::Sentry>),
     /// The configuration profile is not debug but not secret key is configured.
     InsecureSecretKey(Profile),
+    /// State of the same type was managed more than once by a nested
+    /// [`Blueprint`](crate::Blueprint). Contains the type names.
+    DuplicateState(Vec<&'static str>),
 }
 
 impl From<ErrorKind> for Error {
@@ -150,6 +153,7 @@ impl fmt::Display for ErrorKind {
             ErrorKind::InsecureSecretKey(_) => "insecure secret key config".fmt(f),
             ErrorKind::Config(_) => "failed to extract configuration".fmt(f),
             ErrorKind::SentinelAborts(_) => "sentinel(s) aborted".fmt(f),
+            ErrorKind::DuplicateState(_) => "duplicate managed state".fmt(f),
         }
     }
 }
@@ -236,6 +240,14 @@ impl Drop for Error {
 
                 panic!("aborting due to sentinel-triggered abort(s)");
             }
+            ErrorKind::DuplicateState(ref names) => {
+                error!("Rocket failed to launch due to duplicate managed state:");
+                for name in names {
+                    info_!("{}", Paint::default(name).bold());
+                }
+
+                panic!("aborting due to duplicate managed state");
+            }
         }
     }
 }
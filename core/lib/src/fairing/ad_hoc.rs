@@ -3,7 +3,7 @@ use std::sync::Mutex;
 use futures::future::{Future, BoxFuture, FutureExt};
 
 use crate::{Rocket, Request, Response, Data, Build, Orbit};
-use crate::fairing::{Fairing, Kind, Info, Result};
+use crate::fairing::{Fairing, Kind, Info, Next, Result};
 
 /// A ad-hoc fairing that can be created from a function or closure.
 ///
@@ -12,9 +12,10 @@ use crate::fairing::{Fairing, Kind, Info, Result};
 ///
 /// # Usage
 ///
-/// Use [`AdHoc::on_ignite`], [`AdHoc::on_liftoff`], [`AdHoc::on_request()`], or
-/// [`AdHoc::on_response()`] to create an `AdHoc` structure from a function or
-/// closure. Then, simply attach the structure to the `Rocket` instance.
+/// Use [`AdHoc::on_ignite`], [`AdHoc::on_liftoff`], [`AdHoc::on_request()`],
+/// [`AdHoc::on_dispatch()`], or [`AdHoc::on_response()`] to create an `AdHoc`
+/// structure from a function or closure. Then, simply attach the structure to
+/// the `Rocket` instance.
 ///
 /// # Example
 ///
@@ -62,6 +63,10 @@ enum AdHocKind {
     Request(Box<dyn for<'a> Fn(&'a mut Request<'_>, &'a Data)
         -> BoxFuture<'a, ()> + Send + Sync + 'static>),
 
+    /// An ad-hoc **dispatch** fairing. Called to dispatch a request.
+    Dispatch(Box<dyn for<'r> Fn(&'r Request<'r>, Data, Next<'r>)
+        -> BoxFuture<'r, Response<'r>> + Send + Sync + 'static>),
+
     /// An ad-hoc **response** fairing. Called when a response is ready to be
     /// sent to a client.
     Response(Box<dyn for<'r, 'b> Fn(&'r Request<'_>, &'b mut Response<'r>)
@@ -158,6 +163,34 @@ impl AdHoc {
     // FIXME(rustc): We'd like to allow passing `async fn` to these methods...
     // https://github.com/rust-lang/rust/issues/64552#issuecomment-666084589
 
+    /// Constructs an `AdHoc` dispatch fairing named `name`. The function `f`
+    /// will be called with the request, its data, and the [`Next`] remainder
+    /// of the dispatch, and the returned `Future` will be `await`ed by Rocket
+    /// for the request's response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Instant;
+    ///
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// // A fairing that logs how long each request took to handle.
+    /// let fairing = AdHoc::on_dispatch("Timer", |req, data, next| {
+    ///     Box::pin(async move {
+    ///         let start = Instant::now();
+    ///         let response = next.run(data).await;
+    ///         println!("{} took {:?}", req, start.elapsed());
+    ///         response
+    ///     })
+    /// });
+    /// ```
+    pub fn on_dispatch<F: Send + Sync + 'static>(name: &'static str, f: F) -> AdHoc
+        where F: for<'r> Fn(&'r Request<'r>, Data, Next<'r>) -> BoxFuture<'r, Response<'r>>
+    {
+        AdHoc { name, kind: AdHocKind::Dispatch(Box::new(f)) }
+    }
+
     /// Constructs an `AdHoc` response fairing named `name`. The function `f`
     /// will be called and the returned `Future` will be `await`ed by Rocket
     /// when a response is ready to be sent.
@@ -229,6 +262,7 @@ impl Fairing for AdHoc {
             AdHocKind::Liftoff(_) => Kind::Liftoff,
             AdHocKind::Request(_) => Kind::Request,
             AdHocKind::Response(_) => Kind::Response,
+            AdHocKind::Dispatch(_) => Kind::Dispatch,
         };
 
         Info { name: self.name, kind }
@@ -253,6 +287,18 @@ impl Fairing for AdHoc {
         }
     }
 
+    async fn on_dispatch<'r>(
+        &self,
+        req: &'r Request<'r>,
+        data: Data,
+        next: Next<'r>
+    ) -> Response<'r> {
+        match self.kind {
+            AdHocKind::Dispatch(ref f) => f(req, data, next).await,
+            _ => next.run(data).await,
+        }
+    }
+
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         if let AdHocKind::Response(ref f) = self.kind {
             f(req, res).await
@@ -1,5 +1,8 @@
+use futures::future::BoxFuture;
+
 use crate::{Rocket, Request, Response, Data, Build, Orbit};
-use crate::fairing::{Fairing, Info, Kind};
+use crate::fairing::{Fairing, Info, Kind, Next};
+use crate::http::uri::Origin;
 use crate::log::PaintExt;
 
 use yansi::Paint;
@@ -7,6 +10,8 @@ use yansi::Paint;
 #[derive(Default)]
 pub struct Fairings {
     all_fairings: Vec<Box<dyn Fairing>>,
+    // The base each fairing in `all_fairings` is scoped to, if any.
+    bases: Vec<Option<Origin<'static>>>,
     failures: Vec<Info>,
     // Index into `attach` of last run attach fairing.
     last_launch: usize,
@@ -15,6 +20,7 @@ pub struct Fairings {
     liftoff: Vec<usize>,
     request: Vec<usize>,
     response: Vec<usize>,
+    dispatch: Vec<usize>,
 }
 
 macro_rules! iter {
@@ -31,21 +37,47 @@ impl Fairings {
     }
 
     pub fn add(&mut self, fairing: Box<dyn Fairing>) -> &dyn Fairing {
+        self.add_scoped(None, fairing)
+    }
+
+    pub fn add_scoped(
+        &mut self,
+        base: Option<Origin<'static>>,
+        fairing: Box<dyn Fairing>
+    ) -> &dyn Fairing {
         let kind = fairing.info().kind;
         let index = self.all_fairings.len();
         self.all_fairings.push(fairing);
+        self.bases.push(base);
 
         if kind.is(Kind::Ignite) { self.launch.push(index); }
         if kind.is(Kind::Liftoff) { self.liftoff.push(index); }
         if kind.is(Kind::Request) { self.request.push(index); }
         if kind.is(Kind::Response) { self.response.push(index); }
+        if kind.is(Kind::Dispatch) { self.dispatch.push(index); }
 
         &*self.all_fairings[index]
     }
 
     pub fn append(&mut self, others: &mut Fairings) {
-        for fairing in others.all_fairings.drain(..) {
-            self.add(fairing);
+        let bases = others.bases.drain(..);
+        for (base, fairing) in bases.zip(others.all_fairings.drain(..)) {
+            self.add_scoped(base, fairing);
+        }
+    }
+
+    /// Appends the fairings in `others`, scoping each to `base` joined with
+    /// its existing scope, if any.
+    pub fn append_at(&mut self, base: &Origin<'_>, mut others: Fairings) {
+        let bases = others.bases.drain(..);
+        for (scope, fairing) in bases.zip(others.all_fairings.drain(..)) {
+            let joined = match scope {
+                Some(scope) => format!("{}{}", base.path(), scope.path()),
+                None => base.path().to_string(),
+            };
+
+            let joined = Origin::parse_owned(joined).expect("joined bases are valid");
+            self.add_scoped(Some(joined.into_normalized()), fairing);
         }
     }
 
@@ -83,18 +115,55 @@ impl Fairings {
 
     #[inline(always)]
     pub async fn handle_request(&self, req: &mut Request<'_>, data: &mut Data) {
-        for fairing in iter!(self.request) {
-            fairing.on_request(req, data).await
+        // Scopes are checked as fairings run since one may rewrite the URI.
+        for &i in &self.request {
+            if req.is_short_circuited() {
+                break;
+            }
+
+            if self.is_in_scope(i, req) {
+                self.all_fairings[i].on_request(req, data).await
+            }
         }
     }
 
+    /// Runs the dispatch fairings in scope for `req`, starting with the
+    /// `from`th, around routing `req`.
+    pub fn handle_dispatch<'r>(
+        &'r self,
+        rocket: &'r Rocket<Orbit>,
+        req: &'r Request<'r>,
+        data: Data,
+        from: usize,
+    ) -> BoxFuture<'r, Response<'r>> {
+        // Scopes are checked as fairings run since one may rewrite the URI.
+        for (n, &i) in self.dispatch.iter().enumerate().skip(from) {
+            if self.is_in_scope(i, req) {
+                let next = Next::new(rocket, req, n + 1);
+                return self.all_fairings[i].on_dispatch(req, data, next);
+            }
+        }
+
+        Box::pin(rocket.route_and_process(req, data))
+    }
+
     #[inline(always)]
     pub async fn handle_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        for fairing in iter!(self.response) {
-            fairing.on_response(request, response).await;
+        for &i in &self.response {
+            if self.is_in_scope(i, request) {
+                self.all_fairings[i].on_response(request, response).await;
+            }
         }
     }
 
+    /// Returns `true` if the fairing at `index` applies to `req`: if it isn't
+    /// scoped or its base is a prefix of `req`'s path.
+    fn is_in_scope(&self, index: usize, req: &Request<'_>) -> bool {
+        self.bases[index].as_ref().map_or(true, |base| {
+            base.path_segments().prefix_of(req.uri().path_segments())
+        })
+    }
+
     pub fn audit(&self) -> Result<(), &[Info]> {
         match self.failures.is_empty() {
             true => Ok(()),
@@ -107,9 +176,16 @@ impl Fairings {
             launch_info!("{}{}:", Paint::emoji("📡 "), Paint::magenta("Fairings"));
         }
 
-        for fairing in &self.all_fairings {
-            launch_info_!("{} ({})", Paint::default(fairing.info().name).bold(),
-                Paint::blue(fairing.info().kind).bold());
+        for (fairing, base) in self.all_fairings.iter().zip(self.bases.iter()) {
+            match base {
+                Some(base) => launch_info_!("{} ({}) {}",
+                    Paint::default(fairing.info().name).bold(),
+                    Paint::blue(fairing.info().kind).bold(),
+                    Paint::blue(base).underline()),
+                None => launch_info_!("{} ({})",
+                    Paint::default(fairing.info().name).bold(),
+                    Paint::blue(fairing.info().kind).bold()),
+            }
         }
     }
 }
@@ -125,6 +201,7 @@ impl std::fmt::Debug for Fairings {
             .field("liftoff", &debug_info(iter!(self.liftoff)))
             .field("request", &debug_info(iter!(self.request)))
             .field("response", &debug_info(iter!(self.response)))
+            .field("dispatch", &debug_info(iter!(self.dispatch)))
             .finish()
     }
 }
@@ -39,6 +39,7 @@ pub struct Info {
 ///   * Ignite
 ///   * Liftoff
 ///   * Request
+///   * Dispatch
 ///   * Response
 ///
 /// Two `Kind` structures can be `or`d together to represent a combination. For
@@ -62,6 +63,9 @@ impl Kind {
     /// `Kind` flag representing a request for a 'response' callback.
     pub const Response: Kind = Kind(1 << 3);
 
+    /// `Kind` flag representing a request for a 'dispatch' callback.
+    pub const Dispatch: Kind = Kind(1 << 4);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the kinds in `other` are also in `self`.
     ///
@@ -132,6 +136,7 @@ impl std::fmt::Display for Kind {
         write("ignite", Kind::Ignite)?;
         write("liftoff", Kind::Liftoff)?;
         write("request", Kind::Request)?;
-        write("response", Kind::Response)
+        write("response", Kind::Response)?;
+        write("dispatch", Kind::Dispatch)
     }
 }
@@ -52,25 +52,27 @@ use crate::{Rocket, Request, Response, Data, Build, Orbit};
 mod fairings;
 mod ad_hoc;
 mod info_kind;
+mod next;
+mod short_circuit;
 
 pub(crate) use self::fairings::Fairings;
 pub use self::ad_hoc::AdHoc;
 pub use self::info_kind::{Info, Kind};
+pub use self::next::Next;
+pub use self::short_circuit::ShortCircuit;
 
 /// A type alias for the return `Result` type of [`Fairing::on_ignite()`].
 pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E>;
 
-// We might imagine that a request fairing returns an `Outcome`. If it returns
-// `Success`, we don't do any routing and use that response directly. Same if it
-// returns `Failure`. We only route if it returns `Forward`. I've chosen not to
-// go this direction because I feel like request guards are the correct
-// mechanism to use here. In other words, enabling this at the fairing level
-// encourages implicit handling, a bad practice. Fairings can still, however,
-// return a default `Response` if routing fails via a response fairing. For
-// instance, to automatically handle preflight in CORS, a response fairing can
-// check that the user didn't handle the `OPTIONS` request (404) and return an
-// appropriate response. This allows the users to handle `OPTIONS` requests
-// when they'd like but default to the fairing when they don't want to.
+// Request fairings can respond directly via `Request::short_circuit()` and
+// dispatch fairings can wrap or replace routing, but both are explicit opt-ins.
+// Request guards remain the correct mechanism for most per-request checks:
+// handling at the fairing level encourages implicit handling, a bad practice.
+// Fairings can also return a default `Response` if routing fails via a response
+// fairing. For instance, to automatically handle preflight in CORS, a response
+// fairing can check that the user didn't handle the `OPTIONS` request (404) and
+// return an appropriate response. This allows the users to handle `OPTIONS`
+// requests when they'd like but default to the fairing when they don't want to.
 
 /// Trait implemented by fairings: Rocket's structured middleware.
 ///
@@ -97,12 +99,12 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// ## Fairing Callbacks
 ///
-/// There are four kinds of fairing callbacks: launch, liftoff, request, and
-/// response. A fairing can request any combination of these callbacks through
-/// the `kind` field of the `Info` structure returned from the `info` method.
-/// Rocket will only invoke the callbacks set in the `kind` field.
+/// There are five kinds of fairing callbacks: launch, liftoff, request,
+/// dispatch, and response. A fairing can request any combination of these
+/// callbacks through the `kind` field of the `Info` structure returned from the
+/// `info` method. Rocket will only invoke the callbacks set in the `kind` field.
 ///
-/// The four callback kinds are as follows:
+/// The five callback kinds are as follows:
 ///
 ///   * **Ignite (`on_ignite`)**
 ///
@@ -138,10 +140,26 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     fields. At this point, Rocket has parsed the incoming HTTP request into
 ///     [`Request`] and [`Data`] structures but has not routed the request. A
 ///     request callback can modify the request at will and [`Data::peek()`]
-///     into the incoming data. It may not, however, abort or respond directly
-///     to the request; these issues are better handled via [request guards] or
-///     via response callbacks. Any modifications to a request are persisted and
-///     can potentially alter how a request is routed.
+///     into the incoming data. Any modifications to a request are persisted and
+///     can potentially alter how a request is routed. A request callback may
+///     also respond to the request directly by calling
+///     [`Request::short_circuit()`] with a [`Status`](crate::http::Status) or a
+///     [`Response`]. The remaining request callbacks and routing are then
+///     skipped, but response callbacks still run. Checks that depend on the
+///     matched route are better handled via [request guards].
+///
+///   * **Dispatch (`on_dispatch`)**
+///
+///     A dispatch callback, represented by the [`Fairing::on_dispatch()`]
+///     method, is called after request callbacks to dispatch the request. It
+///     wraps routing: it receives the request, its [`Data`], and a [`Next`]
+///     representing the remainder of the dispatch, and returns the response.
+///     A dispatch callback can thus act before and after the request is
+///     handled, for instance to time handlers or to enforce a timeout, or
+///     produce a response without running [`Next`] at all. Dispatch callbacks
+///     are nested in `attach()` order: the first attached is outermost.
+///     Response callbacks run on the response returned by the outermost
+///     dispatch callback.
 ///
 ///   * **Response (`on_response`)**
 ///
@@ -162,7 +180,7 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// A `Fairing` implementation has one required method: [`info`]. A `Fairing`
 /// can also implement any of the available callbacks: `on_ignite`, `on_liftoff`,
-/// `on_request`, and `on_response`. A `Fairing` _must_ set the appropriate
+/// `on_request`, `on_dispatch`, and `on_response`. A `Fairing` _must_ set the appropriate
 /// callback kind in the `kind` field of the returned `Info` structure from
 /// [`info`] for a callback to actually be called by Rocket.
 ///
@@ -203,7 +221,7 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// ```rust
 /// use rocket::{Rocket, Request, Data, Response, Build, Orbit};
-/// use rocket::fairing::{self, Fairing, Info, Kind};
+/// use rocket::fairing::{self, Fairing, Info, Kind, Next};
 ///
 /// # struct MyType;
 /// #[rocket::async_trait]
@@ -228,6 +246,16 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///         # unimplemented!()
 ///     }
 ///
+///     async fn on_dispatch<'r>(
+///         &self,
+///         req: &'r Request<'r>,
+///         data: Data,
+///         next: Next<'r>
+///     ) -> Response<'r> {
+///         /* ... */
+///         # unimplemented!()
+///     }
+///
 ///     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
 ///         /* ... */
 ///         # unimplemented!()
@@ -456,6 +484,28 @@ pub trait Fairing: Send + Sync + 'static {
     ///
     /// The default implementation of this method does nothing.
     async fn on_response<'r>(&self, _req: &'r Request<'_>, _res: &mut Response<'r>) {}
+
+    /// The dispatch callback.
+    ///
+    /// This method is called to dispatch a request, after request callbacks
+    /// have run, if `Kind::Dispatch` is in the `kind` field of the `Info`
+    /// structure for this fairing. The `&Request` parameter is the request to
+    /// dispatch, the `Data` parameter is the request's body data, and `next`
+    /// is the remainder of the dispatch: later dispatch callbacks, routing,
+    /// and error catching. The callback returns the request's response, which
+    /// is typically the response from running `next`.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method runs `next` with `data`.
+    async fn on_dispatch<'r>(
+        &self,
+        _req: &'r Request<'r>,
+        data: Data,
+        next: Next<'r>
+    ) -> Response<'r> {
+        next.run(data).await
+    }
 }
 
 #[crate::async_trait]
@@ -484,4 +534,14 @@ impl<T: Fairing> Fairing for std::sync::Arc<T> {
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         (self as &T).on_response(req, res).await
     }
+
+    #[inline]
+    async fn on_dispatch<'r>(
+        &self,
+        req: &'r Request<'r>,
+        data: Data,
+        next: Next<'r>
+    ) -> Response<'r> {
+        (self as &T).on_dispatch(req, data, next).await
+    }
 }
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use futures::future::BoxFuture;
+
+use crate::{Rocket, Orbit, Request, Response, Data};
+
+/// The remainder of a request's dispatch, as passed to a
+/// [dispatch callback](crate::fairing::Fairing::on_dispatch()).
+///
+/// [`Next::run()`] runs the remaining dispatch callbacks, if any, and then
+/// routes the request, invoking its handlers and any error catchers, returning
+/// the resulting response. A dispatch callback may run the remainder exactly
+/// once, which requires the request's [`Data`], or not at all and produce a
+/// response of its own.
+pub struct Next<'r> {
+    rocket: &'r Rocket<Orbit>,
+    request: &'r Request<'r>,
+    from: usize,
+}
+
+impl<'r> Next<'r> {
+    pub(crate) fn new(rocket: &'r Rocket<Orbit>, request: &'r Request<'r>, from: usize) -> Self {
+        Next { rocket, request, from }
+    }
+
+    /// Runs the remainder of the dispatch with `data` as the request's body
+    /// data, returning the response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let passthrough = AdHoc::on_dispatch("Passthrough", |_, data, next| {
+    ///     Box::pin(async move { next.run(data).await })
+    /// });
+    /// ```
+    pub fn run(self, data: Data) -> BoxFuture<'r, Response<'r>> {
+        self.rocket.fairings.handle_dispatch(self.rocket, self.request, data, self.from)
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next").field("request", &self.request).finish()
+    }
+}
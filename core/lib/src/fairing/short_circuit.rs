@@ -0,0 +1,39 @@
+use crate::Response;
+use crate::http::Status;
+
+/// An early response to a request, set by a request fairing via
+/// [`Request::short_circuit()`](crate::Request::short_circuit()).
+///
+/// A `ShortCircuit` is either a [`Status`], in which case the error catcher
+/// for the status is invoked, or a complete [`Response`], which is used as-is.
+/// Both convert into a `ShortCircuit` via `From`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::ShortCircuit;
+/// use rocket::http::Status;
+/// use rocket::Response;
+///
+/// let status = ShortCircuit::from(Status::ServiceUnavailable);
+/// let response = ShortCircuit::from(Response::build().status(Status::Ok).finalize());
+/// ```
+#[derive(Debug)]
+pub enum ShortCircuit {
+    /// Respond with the error catcher for the status.
+    Status(Status),
+    /// Respond with the response.
+    Response(Response<'static>),
+}
+
+impl From<Status> for ShortCircuit {
+    fn from(status: Status) -> Self {
+        ShortCircuit::Status(status)
+    }
+}
+
+impl From<Response<'static>> for ShortCircuit {
+    fn from(response: Response<'static>) -> Self {
+        ShortCircuit::Response(response)
+    }
+}
@@ -368,6 +368,7 @@ mod context;
 mod strict;
 mod lenient;
 mod parser;
+mod multipart;
 pub mod validate;
 pub mod name;
 pub mod error;
@@ -392,6 +393,7 @@ pub use form::*;
 pub use context::*;
 pub use strict::*;
 pub use lenient::*;
+pub use multipart::{Multipart, MultipartField};
 
 #[doc(hidden)]
 pub mod prelude {
@@ -0,0 +1,185 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::Request;
+use crate::data::{ByteUnit, Data, DataStream, FromData, Limits, Outcome};
+use crate::form::{Error, name::FileName};
+use crate::http::{ContentType, HeaderMap};
+use crate::tokio::io::{AsyncRead, ReadBuf};
+
+/// A data guard for streaming `multipart/form-data` fields one at a time.
+///
+/// Unlike [`Form`](crate::form::Form), which parses every field of a form
+/// before a handler runs, `Multipart` hands the handler each field, in order,
+/// as an async stream of bytes along with its name, file name, `Content-Type`,
+/// and headers. Nothing is buffered in memory or to disk, making `Multipart`
+/// suitable for streaming large uploads directly to their destination.
+///
+/// `Multipart` forwards if the request's `Content-Type` isn't
+/// `multipart/form-data` and fails with `400 Bad Request` if it lacks a
+/// boundary.
+///
+/// # Limits
+///
+/// The entire body is limited by the `data-form` limit. The data of each field
+/// is additionally limited, as with [`TempFile`](crate::data::TempFile), by
+/// the `file/$ext` limit, where `$ext` is the extension of the field's
+/// `Content-Type`, or else the `file` limit. Fields without a `Content-Type`
+/// are limited by the `string` limit. The limit of a field is available via
+/// [`MultipartField::limit()`]. Reading more than a field's limit from the
+/// field fails with an [`io::Error`](std::io::Error) rather than ending the
+/// field's data early.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::Multipart;
+/// use rocket::http::Status;
+///
+/// #[post("/upload", data = "<form>")]
+/// async fn upload(mut form: Multipart) -> Result<String, Status> {
+///     let mut uploaded = vec![];
+///     while let Some(field) = form.next_field().await.map_err(|e| e.status())? {
+///         let name = field.name().unwrap_or_default().to_string();
+///
+///         // A real application might stream to object storage instead. This
+///         // fails if the field's data exceeds its limit.
+///         let n = field.into_stream()
+///             .stream_to(rocket::tokio::io::sink())
+///             .await
+///             .map_err(|_| Status::BadRequest)?;
+///
+///         uploaded.push(format!("{}: {}", name, n.written));
+///     }
+///
+///     Ok(uploaded.join(", "))
+/// }
+/// ```
+pub struct Multipart {
+    source: multer::Multipart,
+    limits: Limits,
+}
+
+/// A field of a [`Multipart`] form.
+///
+/// The field's data is read by reading from the field itself, which implements
+/// [`AsyncRead`], or from the [`DataStream`] returned by
+/// [`MultipartField::into_stream()`]. Either way, reading more than the
+/// field's [`limit()`](MultipartField::limit()) fails with an error.
+pub struct MultipartField {
+    name: Option<String>,
+    file_name: Option<String>,
+    content_type: Option<ContentType>,
+    headers: HeaderMap<'static>,
+    limit: ByteUnit,
+    stream: DataStream,
+}
+
+impl Multipart {
+    /// Returns the next field of the form, or `None` if there are no more.
+    ///
+    /// Fields must be read in order: any unread data of the previous field is
+    /// discarded when the next one is requested.
+    pub async fn next_field(&mut self) -> Result<Option<MultipartField>, Error<'static>> {
+        let field = match self.source.next_field().await? {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+
+        trace_!("streaming multipart field: {:?}", field);
+        let content_type: Option<ContentType> = field.content_type()
+            .and_then(|m| m.as_ref().parse().ok());
+
+        let limit = match content_type {
+            Some(ref content_type) => content_type.extension()
+                .and_then(|ext| self.limits.find(&["file", ext.as_str()]))
+                .or_else(|| self.limits.get("file"))
+                .unwrap_or(Limits::FILE),
+            None => self.limits.get("string").unwrap_or(Limits::STRING),
+        };
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in field.headers().iter() {
+            if let Ok(value) = value.to_str() {
+                headers.add_raw(name.as_str().to_string(), value.to_string());
+            }
+        }
+
+        Ok(Some(MultipartField {
+            name: field.name().map(|s| s.to_string()),
+            file_name: field.file_name().map(|s| s.to_string()),
+            content_type,
+            headers,
+            limit,
+            stream: Data::from(field).open_strict(limit),
+        }))
+    }
+}
+
+impl MultipartField {
+    /// The name of the field, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The file name of the field, if it has one.
+    pub fn file_name(&self) -> Option<&FileName> {
+        self.file_name.as_ref().map(FileName::new)
+    }
+
+    /// The `Content-Type` of the field, if it has a valid one.
+    pub fn content_type(&self) -> Option<&ContentType> {
+        self.content_type.as_ref()
+    }
+
+    /// All of the headers of the field.
+    pub fn headers(&self) -> &HeaderMap<'static> {
+        &self.headers
+    }
+
+    /// The limit on the size of the field's data.
+    pub fn limit(&self) -> ByteUnit {
+        self.limit
+    }
+
+    /// Consumes `self` and returns the field's data stream. Reading more than
+    /// [`limit()`](MultipartField::limit()) from the stream fails with an error.
+    pub fn into_stream(self) -> DataStream {
+        self.stream
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromData<'r> for Multipart {
+    type Error = Error<'r>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data) -> Outcome<Self, Error<'r>> {
+        if !req.content_type().map_or(false, |ct| ct.is_form_data()) {
+            return Outcome::Forward(data);
+        }
+
+        let boundary = match req.content_type().and_then(|ct| ct.param("boundary")) {
+            Some(boundary) => boundary,
+            None => {
+                let error = Error::from(multer::Error::NoBoundary);
+                return Outcome::Failure((error.status(), error));
+            }
+        };
+
+        let limit = req.limits().get("data-form").unwrap_or(Limits::DATA_FORM);
+        let source = multer::Multipart::with_reader(data.open(limit), boundary);
+        Outcome::Success(Multipart { source, limits: req.limits().clone() })
+    }
+}
+
+impl AsyncRead for MultipartField {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
@@ -68,10 +68,13 @@
 //!
 //! ## Features
 //!
-//! There are two optional, disabled-by-default features:
+//! There are four optional, disabled-by-default features:
 //!
 //!   * **secrets:** Enables support for [private cookies].
 //!   * **tls:** Enables support for [TLS].
+//!   * **decompression:** Enables [request body decompression].
+//!   * **operations:** Records the [`Operation`] of routes generated by route
+//!     attributes.
 //!
 //! The features can be enabled in `Rocket.toml`:
 //!
@@ -80,6 +83,8 @@
 //! rocket = { version = "0.5.0-dev", features = ["secrets", "tls"] }
 //! ```
 //!
+//! [request body decompression]: crate::data::Decompression
+//! [`Operation`]: crate::schema::Operation
 //! [private cookies]: https://rocket.rs/master/guide/requests/#private-cookies
 //! [TLS]: https://rocket.rs/master/guide/configuration/#tls
 //!
@@ -109,6 +114,7 @@
 pub use futures;
 pub use tokio;
 pub use figment;
+pub use time;
 
 #[doc(hidden)]
 #[macro_use] pub mod log;
@@ -124,6 +130,9 @@ pub mod fairing;
 pub mod error;
 pub mod catcher;
 pub mod route;
+pub mod schema;
+pub mod tower;
+pub mod auth;
 
 // Reexport of HTTP everything.
 pub mod http {
@@ -149,6 +158,7 @@ mod cookies;
 mod rocket;
 mod router;
 mod phase;
+mod blueprint;
 
 #[doc(inline)] pub use crate::response::Response;
 #[doc(inline)] pub use crate::data::Data;
@@ -161,6 +171,7 @@ mod phase;
 #[doc(inline)] pub use sentinel::Sentinel;
 #[doc(inline)] pub use rocket_codegen::*;
 pub use crate::rocket::Rocket;
+pub use crate::blueprint::Blueprint;
 pub use crate::request::Request;
 pub use crate::shutdown::Shutdown;
 pub use crate::state::State;
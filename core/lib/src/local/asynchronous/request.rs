@@ -2,8 +2,10 @@ use std::fmt;
 use std::convert::TryInto;
 
 use crate::{Request, Data};
+use crate::request::{Malformed, RequestError};
 use crate::http::{Status, Method};
 use crate::http::uri::Origin;
+use crate::http::ext::IntoOwned;
 
 use super::{Client, LocalResponse};
 
@@ -87,6 +89,15 @@ impl<'c> LocalRequest<'c> {
             // _shouldn't_ error. Check that now and error only if not.
             if self.inner().uri() == invalid {
                 error!("invalid request URI: {:?}", invalid.path());
+                let error = match Origin::parse(invalid.path().as_str()) {
+                    Err(e) => RequestError::UriParse(e.into_owned()),
+                    Ok(_) => RequestError::InvalidUri,
+                };
+
+                let method = self.request.method().to_string();
+                let malformed = Malformed::new(method, invalid.to_string(), vec![error]);
+                self.request.set_malformed(malformed);
+
                 return LocalResponse::new(self.request, move |req| {
                     rocket.handle_error(Status::BadRequest, req)
                 }).await
@@ -2,8 +2,9 @@ use state::Container;
 use figment::Figment;
 
 use crate::{Catcher, Config, Rocket, Route, Shutdown};
-use crate::router::Router;
+use crate::router::{Router, SharedRouter};
 use crate::fairing::Fairings;
+use crate::tower::Layers;
 
 mod private {
     pub trait Sealed {  }
@@ -84,6 +85,11 @@ phases! {
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
         pub(crate) state: Container![Send + Sync],
+        // Type names of nested blueprint state that was already managed.
+        pub(crate) duplicate_state: Vec<&'static str>,
+        // Type names of state managed by nested blueprints.
+        pub(crate) blueprint_state: Vec<&'static str>,
+        pub(crate) layers: Layers,
     }
 
     /// The second launch [`Phase`]: post-build but pre-orbit.
@@ -98,6 +104,7 @@ phases! {
         pub(crate) config: Config,
         pub(crate) state: Container![Send + Sync],
         pub(crate) shutdown: Shutdown,
+        pub(crate) layers: Layers,
     }
 
     /// The final launch [`Phase`].
@@ -105,11 +112,12 @@ phases! {
     /// An instance of `Rocket` in this phase is typed as [`Rocket<Orbit>`] and
     /// represents a running application. See [`Rocket#orbit`] for full details.
     Orbit (#[derive(Debug)] Orbiting) {
-        pub(crate) router: Router,
+        pub(crate) router: std::sync::Arc<SharedRouter>,
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
         pub(crate) config: Config,
         pub(crate) state: Container![Send + Sync],
         pub(crate) shutdown: Shutdown,
+        pub(crate) layers: Layers,
     }
 }
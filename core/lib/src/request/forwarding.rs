@@ -0,0 +1,112 @@
+use std::net::{IpAddr, SocketAddr};
+
+use crate::config::Cidr;
+use crate::http::{HeaderMap, TypedHeader, Forwarded};
+use crate::http::uncased::UncasedStr;
+
+/// The names of the headers used to compute a [`Forwarding`].
+const FORWARDING_HEADERS: &[&str] = &[
+    "Forwarded", "X-Forwarded-For", "X-Forwarded-Proto", "X-Forwarded-Host", "X-Real-IP"
+];
+
+/// What trusted proxies report about the client that made a request.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Forwarding {
+    /// The client's IP address, if forwarded by a trusted proxy.
+    pub client_ip: Option<IpAddr>,
+    /// The protocol the client used, lowercased, if forwarded.
+    pub proto: Option<String>,
+    /// The host the client requested, if forwarded.
+    pub host: Option<String>,
+}
+
+impl Forwarding {
+    /// Returns `true` if the header named `name` is used to compute a
+    /// `Forwarding`.
+    pub fn is_forwarding_header(name: &UncasedStr) -> bool {
+        FORWARDING_HEADERS.iter().any(|header| name == *header)
+    }
+
+    /// Computes what the proxies in `trusted` report about the client of a
+    /// request from `remote` with headers `headers`.
+    ///
+    /// Proxies append the address of the node they received a request from to
+    /// the end of the `Forwarded` or `X-Forwarded-For` headers. Starting with
+    /// the remote peer, the chain of addresses is walked from right to left for
+    /// as long as the current address is trusted. The client is the first
+    /// untrusted address, the leftmost address if all are trusted, or the last
+    /// trusted address if the next address is obfuscated or malformed.
+    pub fn resolve(remote: Option<SocketAddr>, headers: &HeaderMap<'_>, trusted: &[Cidr]) -> Self {
+        let is_trusted = |ip: IpAddr| trusted.iter().any(|cidr| cidr.contains(ip));
+        let remote = match remote {
+            Some(remote) if is_trusted(remote.ip()) => remote.ip(),
+            _ => return Forwarding::default(),
+        };
+
+        // Prefer the standard header; if it is malformed, trust nothing in it.
+        match Forwarded::from_headers(headers) {
+            Some(Ok(forwarded)) => {
+                let mut fwd = Forwarding { client_ip: Some(remote), ..Default::default() };
+                for element in forwarded.0.iter().rev() {
+                    if !fwd.client_ip.map_or(false, is_trusted) {
+                        break;
+                    }
+
+                    fwd.proto = element.proto.as_ref().map(|p| p.to_ascii_lowercase());
+                    fwd.host = element.host.clone();
+                    match element.for_.as_deref().and_then(parse_node) {
+                        Some(ip) => fwd.client_ip = Some(ip),
+                        None => break,
+                    }
+                }
+
+                return fwd;
+            }
+            Some(Err(e)) => {
+                warn_!("{}", e);
+                return Forwarding::default();
+            }
+            None => { /* fall back to the de-facto headers */ }
+        }
+
+        let list = |name| headers.get(name).flat_map(|v| v.split(',')).map(|v| v.trim());
+        let mut client_ip = remote;
+        let mut hops = 0;
+        for node in list("X-Forwarded-For").collect::<Vec<_>>().into_iter().rev() {
+            if !is_trusted(client_ip) {
+                break;
+            }
+
+            match parse_node(node) {
+                Some(ip) => { client_ip = ip; hops += 1; }
+                None => break,
+            }
+        }
+
+        if hops == 0 {
+            let real_ip = headers.get_one("X-Real-IP").and_then(|ip| ip.trim().parse().ok());
+            client_ip = real_ip.unwrap_or(remote);
+        }
+
+        // The value for the hop the client made, or the one nearest to it.
+        let value = |name| -> Option<String> {
+            let values = list(name).collect::<Vec<_>>();
+            let i = values.len().saturating_sub(hops.max(1));
+            values.get(i).map(|v| v.to_string())
+        };
+
+        Forwarding {
+            client_ip: Some(client_ip),
+            proto: value("X-Forwarded-Proto").map(|p| p.to_ascii_lowercase()),
+            host: value("X-Forwarded-Host"),
+        }
+    }
+}
+
+/// Parses the IP address in an `X-Forwarded-For` entry or a `Forwarded` `for`
+/// parameter, which may be bracketed and may include a port.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    node.parse::<IpAddr>().ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| node.strip_prefix('[')?.strip_suffix(']')?.parse().ok())
+}
@@ -6,6 +6,8 @@ use crate::outcome::{self, IntoOutcome};
 use crate::outcome::Outcome::*;
 
 use crate::http::{Status, ContentType, Accept, Method, CookieJar, uri::Origin};
+use crate::http::{TypedHeader, InvalidHeader, IfModifiedSince};
+use crate::request::typed::typed_header;
 
 /// Type alias for the `Outcome` of a `FromRequest` conversion.
 pub type Outcome<S, E> = outcome::Outcome<S, (Status, E), ()>;
@@ -156,10 +158,30 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///     Extracts the [`ContentType`] from the incoming request. If the request
 ///     didn't specify a Content-Type, the request is forwarded.
 ///
+///   * **AcceptLanguage, Authorization, CacheControl, ETag, Forwarded,
+///     IfNoneMatch, Link, Range**
+///
+///     Parses the corresponding [`TypedHeader`] from the incoming request. If
+///     the header is not present, the request is forwarded. If the header is
+///     present but fails to parse, the request fails with a status of `400 Bad
+///     Request` and the [`InvalidHeader`] error.
+///
+///   * **IfModifiedSince**
+///
+///     Parses the [`IfModifiedSince`] header from the incoming request. If the
+///     header is not present or isn't a valid HTTP date, the request is
+///     forwarded.
+///
+///   * **[`Typed<T>`]** _where_ **T: TypedHeader**
+///
+///     Parses `T` from the incoming request as described above for the built-in
+///     typed headers.
+///
 ///   * **IpAddr**
 ///
-///     Extracts the client ip address of the incoming request as an [`IpAddr`].
-///     If the client's IP address is not known, the request is forwarded.
+///     Extracts the client ip address of the incoming request as an [`IpAddr`]
+///     via [`Request::client_ip()`]. If the client's IP address is not known,
+///     the request is forwarded.
 ///
 ///   * **SocketAddr**
 ///
@@ -185,6 +207,7 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///     forwarded.
 ///
 /// [`Config`]: crate::config::Config
+/// [`Typed<T>`]: crate::request::Typed
 ///
 /// # Example
 ///
@@ -446,6 +469,36 @@ impl<'r> FromRequest<'r> for &'r ContentType {
     }
 }
 
+macro_rules! typed_header_guards {
+    ($($T:ident),*) => ($(
+        #[crate::async_trait]
+        impl<'r> FromRequest<'r> for crate::http::$T {
+            type Error = InvalidHeader;
+
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+                typed_header(request)
+            }
+        }
+    )*)
+}
+
+typed_header_guards! {
+    AcceptLanguage, Authorization, CacheControl, ETag, Forwarded, IfNoneMatch, Link, Range
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for IfModifiedSince {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        // An invalid date must be ignored as if the header weren't present.
+        match Self::from_headers(request.headers()) {
+            Some(Ok(header)) => Success(header),
+            _ => Forward(())
+        }
+    }
+}
+
 #[crate::async_trait]
 impl<'r> FromRequest<'r> for IpAddr {
     type Error = std::convert::Infallible;
@@ -0,0 +1,254 @@
+use std::fmt;
+
+use serde::{Serialize, Serializer, Deserialize};
+
+use crate::{Request, Rocket, Ignite, Sentinel};
+use crate::request::{self, FromRequest};
+use crate::outcome::Outcome::*;
+use crate::http::{Status, TypedHeader, AcceptLanguage};
+
+/// The locales an application supports and how clients may override the
+/// negotiated locale.
+///
+/// `Locales` must be [managed](crate::Rocket::manage()) for the [`Locale`]
+/// request guard to work. The first supported locale is the _fallback_, used
+/// when a client accepts none of the supported locales or doesn't say which
+/// it accepts.
+///
+/// ## (De)serialization
+///
+/// `Locales` (de)serializes as a dictionary with keys `supported`, a list of
+/// language tags, and `cookie` and `query`, the optional names of the override
+/// cookie and query parameter. As such, it can be read from configuration:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fairing::AdHoc;
+/// use rocket::request::Locales;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     // In `Rocket.toml`:
+///     //
+///     // [default.locales]
+///     // supported = ["en-US", "fr", "de"]
+///     // cookie = "lang"
+///     rocket::build().attach(AdHoc::try_on_ignite("Locales", |rocket| async {
+///         match rocket.figment().extract_inner::<Locales>("locales") {
+///             Ok(locales) => Ok(rocket.manage(locales)),
+///             Err(_) => Err(rocket),
+///         }
+///     }))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Locales {
+    supported: Vec<String>,
+    #[serde(default)]
+    cookie: Option<String>,
+    #[serde(default)]
+    query: Option<String>,
+}
+
+impl Locales {
+    /// Creates `Locales` supporting the language tags in `supported`, the first
+    /// of which is the fallback, without any overrides.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Locales;
+    ///
+    /// let locales = Locales::new(["en-US", "fr", "de"]);
+    /// assert_eq!(locales.fallback(), Some("en-US"));
+    /// ```
+    pub fn new<I, S>(supported: I) -> Locales
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let supported = supported.into_iter().map(Into::into).collect();
+        Locales { supported, cookie: None, query: None }
+    }
+
+    /// Allows clients to override the negotiated locale with the value of the
+    /// cookie named `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Locales;
+    ///
+    /// let locales = Locales::new(["en-US", "fr"]).cookie("lang");
+    /// ```
+    pub fn cookie<N: Into<String>>(mut self, name: N) -> Self {
+        self.cookie = Some(name.into());
+        self
+    }
+
+    /// Allows clients to override the negotiated locale, as well as the cookie
+    /// override, with the value of the query parameter named `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Locales;
+    ///
+    /// let locales = Locales::new(["en-US", "fr"]).query("lang");
+    /// ```
+    pub fn query<N: Into<String>>(mut self, name: N) -> Self {
+        self.query = Some(name.into());
+        self
+    }
+
+    /// The supported language tags.
+    pub fn supported(&self) -> &[String] {
+        &self.supported
+    }
+
+    /// The fallback language tag: the first supported one, if any.
+    pub fn fallback(&self) -> Option<&str> {
+        self.supported.first().map(|s| s.as_str())
+    }
+
+    /// Selects the locale for `req`. Returns `None` if no locales are
+    /// supported.
+    ///
+    /// The locale is, in order of preference: the supported locale best
+    /// matching the query parameter override, the cookie override, or the
+    /// request's `Accept-Language` header, as determined by
+    /// [`AcceptLanguage::negotiate()`], or else the fallback. Invalid and
+    /// unsupported overrides are ignored, as is an invalid `Accept-Language`
+    /// header.
+    pub fn negotiate(&self, req: &Request<'_>) -> Option<Locale> {
+        let query = self.query.as_ref()
+            .and_then(|name| req.query_value::<&str>(name))
+            .and_then(|value| value.ok());
+
+        let cookie = self.cookie.as_ref().and_then(|name| req.cookies().get(name));
+        let accept_language = req.headers().get_one(AcceptLanguage::NAME);
+
+        let selected = query.into_iter()
+            .chain(cookie.map(|c| c.value()))
+            .chain(accept_language)
+            .filter_map(|value| AcceptLanguage::parse(value).ok())
+            .find_map(|accepted| accepted.negotiate(&self.supported).cloned());
+
+        match selected {
+            Some(tag) => Some(Locale { tag, fallback: false }),
+            None => self.fallback().map(|tag| Locale { tag: tag.into(), fallback: true }),
+        }
+    }
+}
+
+/// A request guard for the locale of a request.
+///
+/// The locale is selected from the application's managed [`Locales`] by
+/// [`Locales::negotiate()`]. If `Locales` is not managed or empty, the guard
+/// fails with a status of `500 Internal Server Error`; as a [`Sentinel`],
+/// `Locale` also aborts launch in this case. Otherwise, the guard always
+/// succeeds.
+///
+/// A `Locale` serializes as its language tag. The locale of a request can also
+/// be retrieved outside of a guard via [`Locale::of()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{Locale, Locales};
+///
+/// #[get("/")]
+/// fn hello(locale: Locale) -> &'static str {
+///     match locale.tag() {
+///         "fr" => "Bonjour!",
+///         "de" => "Hallo!",
+///         _ => "Hello!",
+///     }
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .manage(Locales::new(["en-US", "fr", "de"]).cookie("lang").query("lang"))
+///         .mount("/", routes![hello])
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    tag: String,
+    fallback: bool,
+}
+
+impl Locale {
+    /// The supported language tag selected for the request.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if no supported locale matched the request and the
+    /// fallback was selected.
+    pub fn is_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// Returns the locale for `req` if [`Locales`] are managed and non-empty.
+    /// The locale is computed at most once per request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    /// use rocket::request::Locale;
+    ///
+    /// fn is_french(req: &Request<'_>) -> bool {
+    ///     Locale::of(req).map_or(false, |locale| locale.tag() == "fr")
+    /// }
+    /// ```
+    pub fn of<'r>(req: &'r Request<'_>) -> Option<&'r Locale> {
+        struct CachedLocale(Option<Locale>);
+
+        req.local_cache(|| {
+            let locales = req.rocket().state::<Locales>();
+            CachedLocale(locales.and_then(|locales| locales.negotiate(req)))
+        }).0.as_ref()
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Locale {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match Locale::of(req) {
+            Some(locale) => Success(locale.clone()),
+            None => {
+                error_!("Attempted to negotiate a locale without managed `Locales`.");
+                Failure((Status::InternalServerError, ()))
+            }
+        }
+    }
+}
+
+impl Sentinel for Locale {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        if rocket.state::<Locales>().map_or(true, |l| l.fallback().is_none()) {
+            let locales = yansi::Paint::default("Locales").bold();
+            error!("launching with a `Locale` guard but no managed `{}`.", locales);
+            info_!("Using `Locale` requires managing non-empty `Locales` with `.manage()`.");
+            return true;
+        }
+
+        false
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.tag)
+    }
+}
+
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(&self.tag)
+    }
+}
@@ -3,13 +3,18 @@
 mod request;
 mod from_param;
 mod from_request;
+mod forwarding;
+mod locale;
+mod typed;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::request::Request;
+pub use self::request::{Request, Malformed, RequestError};
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::from_param::{FromParam, FromSegments};
+pub use self::locale::{Locale, Locales};
+pub use self::typed::Typed;
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
@@ -1,4 +1,4 @@
-use std::{ops::RangeFrom, sync::Arc};
+use std::{ops::RangeFrom, sync::{Arc, Mutex}};
 use std::net::{IpAddr, SocketAddr};
 use std::future::Future;
 use std::fmt;
@@ -14,10 +14,14 @@ use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
 use crate::form::{self, ValueField, FromForm};
 
 use crate::{Rocket, Route, Orbit};
+use crate::router::{Router, RouteId};
 use crate::http::{hyper, uri::{Origin, Segments}, uncased::UncasedStr};
 use crate::http::{Method, Header, HeaderMap};
+use crate::http::ext::IntoOwned;
 use crate::http::{ContentType, Accept, MediaType, CookieJar, Cookie};
 use crate::data::Limits;
+use crate::fairing::ShortCircuit;
+use crate::request::forwarding::Forwarding;
 
 /// The type of an incoming web request.
 ///
@@ -35,10 +39,12 @@ pub struct Request<'r> {
 
 pub(crate) struct RequestState<'r> {
     pub rocket: &'r Rocket<Orbit>,
-    pub route: Atomic<Option<&'r Route>>,
+    pub router: Arc<Router>,
+    pub route: Atomic<Option<RouteId>>,
     pub cookies: CookieJar<'r>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
+    pub forwarding: Storage<Forwarding>,
     pub cache: Arc<Container![Send + Sync]>,
 }
 
@@ -58,10 +64,12 @@ impl RequestState<'_> {
     fn clone(&self) -> Self {
         RequestState {
             rocket: self.rocket,
+            router: self.router.clone(),
             route: Atomic::new(self.route.load(Ordering::Acquire)),
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
+            forwarding: self.forwarding.clone(),
             cache: self.cache.clone(),
         }
     }
@@ -82,10 +90,12 @@ impl<'r> Request<'r> {
             remote: None,
             state: RequestState {
                 rocket,
+                router: rocket.router.get(),
                 route: Atomic::new(None),
                 cookies: CookieJar::new(rocket.config()),
                 accept: Storage::new(),
                 content_type: Storage::new(),
+                forwarding: Storage::new(),
                 cache: Arc::new(<Container![Send + Sync]>::new()),
             }
         }
@@ -222,11 +232,17 @@ impl<'r> Request<'r> {
     #[inline(always)]
     pub fn set_remote(&mut self, address: SocketAddr) {
         self.remote = Some(address);
+        self.state.forwarding = Storage::new();
     }
 
     /// Returns the IP address in the "X-Real-IP" header of the request if such
     /// a header exists and contains a valid IP address.
     ///
+    /// The header is returned regardless of which peer sent it. As such, any
+    /// client can set it to an arbitrary value. Use [`Request::client_ip()`],
+    /// which only considers the header when it is set by a [trusted
+    /// proxy](crate::Config::trusted_proxies), to identify the client instead.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -250,36 +266,109 @@ impl<'r> Request<'r> {
             })
     }
 
-    /// Attempts to return the client's IP address by first inspecting the
-    /// "X-Real-IP" header and then using the remote connection's IP address.
+    /// Attempts to return the client's IP address, as reported by trusted
+    /// proxies if the request was forwarded by one, and as the remote
+    /// connection's IP address otherwise.
+    ///
+    /// If the remote address is in [`Config::trusted_proxies`], the client is
+    /// identified by the `Forwarded` header if there is one and by the
+    /// `X-Forwarded-For` header otherwise. Proxies append the address they
+    /// received a request from to these headers, so the addresses are
+    /// considered from right to left, as long as the previous address was
+    /// trusted. The first untrusted address is the client's. If no address is
+    /// present, the "X-Real-IP" header is used. Headers sent by untrusted peers
+    /// are ignored: the remote connection's IP address is returned instead.
+    ///
+    /// If the address of the remote connection is not known, `None` is
+    /// returned.
     ///
-    /// If the "X-Real-IP" header exists and contains a valid IP address, that
-    /// address is returned. Otherwise, if the address of the remote connection
-    /// is known, that address is returned. Otherwise, `None` is returned.
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
     ///
     /// # Example
     ///
     /// ```rust
     /// # use rocket::http::Header;
-    /// # use std::net::{SocketAddr, IpAddr, Ipv4Addr};
-    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
-    /// # let mut req = c.get("/");
-    /// # let request = req.inner_mut();
+    /// # use rocket::local::blocking::Client;
+    /// use rocket::Config;
     ///
-    /// // starting without an "X-Real-IP" header or remote addresss
+    /// let config = Config {
+    ///     trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+    ///     ..Config::debug_default()
+    /// };
+    ///
+    /// # let client = Client::debug(rocket::custom(config)).unwrap();
+    /// # let mut req = client.get("/");
+    /// # let request = req.inner_mut();
+    /// // starting without any headers or a remote address
     /// assert!(request.client_ip().is_none());
     ///
     /// // add a remote address; this is done by Rocket automatically
     /// request.set_remote("127.0.0.1:8000".parse().unwrap());
     /// assert_eq!(request.client_ip(), Some("127.0.0.1".parse().unwrap()));
     ///
-    /// // now with an X-Real-IP header
-    /// request.add_header(Header::new("X-Real-IP", "8.8.8.8"));
+    /// // the remote isn't trusted, so the header is ignored
+    /// request.add_header(Header::new("X-Forwarded-For", "8.8.8.8, 10.0.0.2"));
+    /// assert_eq!(request.client_ip(), Some("127.0.0.1".parse().unwrap()));
+    ///
+    /// // the remote and `10.0.0.2` are trusted, so `8.8.8.8` is the client
+    /// request.set_remote("10.0.0.1:8000".parse().unwrap());
     /// assert_eq!(request.client_ip(), Some("8.8.8.8".parse().unwrap()));
     /// ```
     #[inline]
     pub fn client_ip(&self) -> Option<IpAddr> {
-        self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
+        self.forwarding().client_ip.or_else(|| self.remote().map(|r| r.ip()))
+    }
+
+    /// Returns the scheme the client used to make the request, lowercased: the
+    /// protocol reported by a [trusted proxy](crate::Config::trusted_proxies)
+    /// in the `Forwarded` or `X-Forwarded-Proto` header if there is one, and
+    /// `https` or `http` depending on whether TLS is enabled otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = c.get("/");
+    /// assert_eq!(req.scheme(), "http");
+    /// ```
+    pub fn scheme(&self) -> &str {
+        match self.forwarding().proto.as_deref() {
+            Some(proto) => proto,
+            None if self.rocket().config().tls_enabled() => "https",
+            None => "http",
+        }
+    }
+
+    /// Returns the host the client requested, without the port. This is the
+    /// host reported by a [trusted proxy](crate::Config::trusted_proxies) in
+    /// the `Forwarded` or `X-Forwarded-Host` header if there is one, and the
+    /// value of the "Host" header otherwise, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Header;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let req = c.get("/");
+    /// assert_eq!(req.host(), None);
+    ///
+    /// let req = req.header(Header::new("Host", "rocket.rs:8000"));
+    /// assert_eq!(req.host(), Some("rocket.rs"));
+    /// ```
+    pub fn host(&self) -> Option<&str> {
+        self.forwarding().host.as_deref()
+            .or_else(|| self.headers().get_one("Host"))
+            .map(crate::route::strip_port)
+    }
+
+    /// Returns what trusted proxies report about the client, computing it if
+    /// necessary.
+    fn forwarding(&self) -> &Forwarding {
+        self.state.forwarding.get_or_set(|| {
+            let trusted = &self.rocket().config().trusted_proxies;
+            Forwarding::resolve(self.remote(), self.headers(), trusted)
+        })
     }
 
     /// Returns a wrapped borrow to the cookies in `self`.
@@ -381,6 +470,29 @@ impl<'r> Request<'r> {
         self.headers.replace(header);
     }
 
+    /// Removes all headers with name `name` from `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::ContentType;
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    ///
+    /// request.add_header(ContentType::HTML);
+    /// assert_eq!(request.content_type(), Some(&ContentType::HTML));
+    ///
+    /// request.remove_header("Content-Type");
+    /// assert!(request.headers().is_empty());
+    /// assert_eq!(request.content_type(), None);
+    /// ```
+    #[inline]
+    pub fn remove_header(&mut self, name: &str) {
+        self.bust_header_cache(UncasedStr::new(name), true);
+        self.headers.remove(name);
+    }
+
     /// Returns the Content-Type header of `self`. If the header is not present,
     /// returns `None`.
     ///
@@ -495,9 +607,11 @@ impl<'r> Request<'r> {
         &self.state.rocket
     }
 
-    /// Returns the configured application data limits.
+    /// Returns the application data limits in effect for this request.
     ///
-    /// This is convenience function equivalent to:
+    /// These are the configured limits, equivalent to the following, with any
+    /// limits set via the `limits` argument of the matched route's attribute
+    /// overriding them:
     ///
     /// ```rust
     /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
@@ -506,6 +620,12 @@ impl<'r> Request<'r> {
     /// # ;
     /// ```
     ///
+    /// **Note:** The returned limits borrow from `self`, not from `'r`, as
+    /// route-specific limits are owned by the router snapshot `self` was routed
+    /// against. Code that returned `&'r Limits` from a `&Request<'r>` must
+    /// borrow the request for `'r` instead, as in `&'r Request<'_>`, or clone
+    /// the limits.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -519,9 +639,13 @@ impl<'r> Request<'r> {
     /// // Retrieve the limit for files with extension `.pdf`; etails to 1MiB.
     /// assert_eq!(request.limits().get("file/pdf"), Some(1.mebibytes()));
     /// ```
-    #[inline(always)]
-    pub fn limits(&self) -> &'r Limits {
-        &self.rocket().config().limits
+    #[inline]
+    pub fn limits(&self) -> &Limits {
+        let config = &self.rocket().config().limits;
+        match self.route() {
+            Some(route) => route.limits_under(config),
+            None => config,
+        }
     }
 
     /// Get the presently matched route, if any.
@@ -530,6 +654,12 @@ impl<'r> Request<'r> {
     /// invoked. This method returns `None` _before_ routing has commenced; this
     /// includes during request fairing callbacks.
     ///
+    /// **Note:** The returned route borrows from `self`, not from `'r`: it is
+    /// owned by the snapshot of the router, taken when the request arrived,
+    /// that `self` was routed against, and so lives only as long as `self`.
+    /// Code that returned `&'r Route` from a `&Request<'r>` must borrow the
+    /// request for `'r` instead, as in `&'r Request<'_>`, or clone the route.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -538,8 +668,82 @@ impl<'r> Request<'r> {
     /// let route = request.route();
     /// ```
     #[inline(always)]
-    pub fn route(&self) -> Option<&'r Route> {
-        self.state.route.load(Ordering::Acquire)
+    pub fn route(&self) -> Option<&Route> {
+        self.state.route.load(Ordering::Acquire).and_then(|id| self.state.router.get(id))
+    }
+
+    /// Returns information about the original incoming request if `self` is a
+    /// stand-in for a request that failed to parse. Otherwise returns `None`.
+    ///
+    /// Rocket only ever passes such a stand-in request to the `400` error
+    /// catcher. See [`Malformed`](crate::request::Malformed) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// // A well-formed request is never malformed.
+    /// assert!(request.malformed().is_none());
+    /// ```
+    #[inline]
+    pub fn malformed(&self) -> Option<&Malformed> {
+        self.state.cache.try_get()
+    }
+
+    /// Returns the methods the request's target allows if Rocket is responding
+    /// to `self` with a `405 Method Not Allowed`. Otherwise returns `None`.
+    ///
+    /// Rocket only ever sets the allowed methods, and invokes the `405`
+    /// catcher, when [`Config::method_not_allowed`] is enabled and no route
+    /// with the request's method, but some route with another method, matches
+    /// the request's path. Rocket also sets the `Allow` response header.
+    ///
+    /// [`Config::method_not_allowed`]: crate::Config::method_not_allowed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(405)]
+    /// fn method_not_allowed(req: &Request) -> String {
+    ///     let allowed = req.allowed_methods().unwrap_or(&[]);
+    ///     format!("try one of: {:?}", allowed)
+    /// }
+    /// ```
+    #[inline]
+    pub fn allowed_methods(&self) -> Option<&[Method]> {
+        self.state.cache.try_get::<AllowedMethods>().map(|a| &*a.0)
+    }
+
+    /// Responds to `self` early, without routing it. Intended for use by
+    /// [request fairings](crate::fairing::Fairing::on_request()).
+    ///
+    /// Once a request is short-circuited, the remaining request fairings are
+    /// not run and the request is not routed. Instead, if `response` is a
+    /// [`Status`](crate::http::Status), the error catcher for the status is
+    /// invoked; otherwise, the [`Response`](crate::Response) is used directly.
+    /// In either case, response fairings run as usual. Calling this method
+    /// more than once replaces the previous early response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::http::Status;
+    ///
+    /// let maintenance = AdHoc::on_request("Maintenance", |req, _| Box::pin(async move {
+    ///     req.short_circuit(Status::ServiceUnavailable);
+    /// }));
+    /// ```
+    pub fn short_circuit<S: Into<ShortCircuit>>(&mut self, response: S) {
+        let response = Some(response.into());
+        match self.state.cache.try_get::<EarlyResponse>() {
+            Some(early) => *early.0.lock().unwrap_or_else(|e| e.into_inner()) = response,
+            None => { self.state.cache.set(EarlyResponse(Mutex::new(response))); }
+        }
     }
 
     /// Invokes the request guard implementation for `T`, returning its outcome.
@@ -773,7 +977,9 @@ impl<'r> Request<'r> {
 impl<'r> Request<'r> {
     /// Resets the cached value (if any) for the header with name `name`.
     fn bust_header_cache(&mut self, name: &UncasedStr, replace: bool) {
-        if name == "Content-Type" {
+        if Forwarding::is_forwarding_header(name) {
+            self.state.forwarding = Storage::new();
+        } else if name == "Content-Type" {
             if self.content_type().is_none() || replace {
                 self.state.content_type = Storage::new();
             }
@@ -809,9 +1015,10 @@ impl<'r> Request<'r> {
     }
 
     /// Set `self`'s parameters given that the route used to reach this request
-    /// was `route`. Use during routing when attempting a given route.
+    /// was the route identified by `route` in `self`'s router snapshot. Use
+    /// during routing when attempting a given route.
     #[inline(always)]
-    pub(crate) fn set_route(&self, route: &'r Route) {
+    pub(crate) fn set_route(&self, route: RouteId) {
         self.state.route.store(Some(route), Ordering::Release)
     }
 
@@ -827,27 +1034,37 @@ impl<'r> Request<'r> {
     }
 
     /// Convert from Hyper types into a Rocket Request.
+    ///
+    /// If the method or URI cannot be parsed, a stand-in `Request` with the
+    /// original headers and a [`Malformed`] in its local cache is returned as
+    /// an `Err`. That request is only suitable for error catchers.
     pub(crate) fn from_hyp(
         rocket: &'r Rocket<Orbit>,
         h_method: hyper::Method,
         h_headers: hyper::HeaderMap<hyper::HeaderValue>,
         h_uri: &'r hyper::Uri,
         h_addr: SocketAddr,
-    ) -> Result<Request<'r>, Error<'r>> {
+    ) -> Result<Request<'r>, Request<'r>> {
         // Get a copy of the URI (only supports path-and-query) for later use.
+        // We need to re-parse the URI since we don't trust Hyper... :(
         let uri = match (h_uri.scheme(), h_uri.authority(), h_uri.path_and_query()) {
-            (None, None, Some(path_query)) => path_query.as_str(),
-            _ => return Err(Error::InvalidUri(h_uri)),
+            (None, None, Some(path_query)) => Origin::parse(path_query.as_str())
+                .map_err(|e| RequestError::UriParse(e.into_owned())),
+            _ => Err(RequestError::InvalidUri),
         };
 
         // Ensure that the method is known. TODO: Allow made-up methods?
-        let method = match Method::from_hyp(&h_method) {
-            Some(method) => method,
-            None => return Err(Error::BadMethod(h_method))
-        };
+        let method = Method::from_hyp(&h_method).ok_or(RequestError::BadMethod);
 
-        // We need to re-parse the URI since we don't trust Hyper... :(
-        let uri = Origin::parse(uri)?;
+        // If either failed to parse, construct a stand-in request that can be
+        // passed to error catchers, remembering every reason the real one
+        // failed: the URI's first, then the method's.
+        let (method, uri, errors) = match (method, uri) {
+            (Ok(method), Ok(uri)) => (method, uri, vec![]),
+            (Ok(method), Err(e)) => (method, Origin::dummy(), vec![e]),
+            (Err(e), Ok(_)) => (Method::Get, Origin::dummy(), vec![e]),
+            (Err(m), Err(u)) => (Method::Get, Origin::dummy(), vec![u, m]),
+        };
 
         // Construct the request object.
         let mut request = Request::new(rocket, method, uri);
@@ -876,32 +1093,178 @@ impl<'r> Request<'r> {
             request.add_header(header);
         }
 
-        Ok(request)
+        if errors.is_empty() {
+            return Ok(request);
+        }
+
+        let uri = h_uri.to_string();
+        request.set_malformed(Malformed::new(h_method.to_string(), uri, errors));
+        Err(request)
+    }
+
+    /// Records that `self` is a stand-in for a request that failed to parse.
+    pub(crate) fn set_malformed(&self, malformed: Malformed) {
+        self.state.cache.set(malformed);
+    }
+
+    /// Returns `true` if `self` has been short-circuited.
+    pub(crate) fn is_short_circuited(&self) -> bool {
+        self.state.cache.try_get::<EarlyResponse>().map_or(false, |early| {
+            early.0.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+        })
+    }
+
+    /// Takes the early response set by [`Request::short_circuit()`], if any.
+    pub(crate) fn take_short_circuit(&self) -> Option<ShortCircuit> {
+        let early = self.state.cache.try_get::<EarlyResponse>()?;
+        early.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Records the methods allowed for `self`'s target. See
+    /// [`Request::allowed_methods()`].
+    pub(crate) fn set_allowed_methods(&self, methods: Vec<Method>) {
+        self.state.cache.set(AllowedMethods(methods));
+    }
+
+    /// Records an authentication challenge to send in a `WWW-Authenticate`
+    /// header if `self` is responded to with a `401 Unauthorized`.
+    pub(crate) fn add_auth_challenge(&self, challenge: String) {
+        if self.state.cache.try_get::<AuthChallenges>().is_none() {
+            self.state.cache.set(AuthChallenges(Mutex::new(vec![])));
+        }
+
+        if let Some(challenges) = self.state.cache.try_get::<AuthChallenges>() {
+            let mut challenges = challenges.0.lock().unwrap_or_else(|e| e.into_inner());
+            if !challenges.contains(&challenge) {
+                challenges.push(challenge);
+            }
+        }
+    }
+
+    /// The challenges recorded via [`Request::add_auth_challenge()`].
+    pub(crate) fn auth_challenges(&self) -> Vec<String> {
+        self.state.cache.try_get::<AuthChallenges>()
+            .map(|c| c.0.lock().unwrap_or_else(|e| e.into_inner()).clone())
+            .unwrap_or_default()
     }
 }
 
+/// The methods allowed for a request's target. See
+/// [`Request::allowed_methods()`].
+struct AllowedMethods(Vec<Method>);
+
+/// The early response to a request. See [`Request::short_circuit()`].
+struct EarlyResponse(Mutex<Option<ShortCircuit>>);
+
+/// The authentication challenges for a request. See
+/// [`Request::add_auth_challenge()`].
+struct AuthChallenges(Mutex<Vec<String>>);
+
+/// Information about an incoming request that Rocket failed to parse.
+///
+/// When an incoming request's method or URI cannot be parsed, Rocket cannot
+/// route it. Instead, it invokes the `400 Bad Request` [catcher] with a
+/// stand-in [`Request`] that carries the original headers and remote address
+/// but a dummy method and URI. The original, raw method and URI along with the
+/// reason parsing failed are available via [`Request::malformed()`], which
+/// returns `Some` _only_ for these stand-in requests.
+///
+/// [catcher]: crate::Catcher
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+///
+/// #[catch(400)]
+/// fn bad_request(req: &Request) -> String {
+///     match req.malformed() {
+///         Some(bad) => format!("Unparseable {} request to {}: {}",
+///             bad.method(), bad.uri(), bad.error()),
+///         None => format!("Bad request to {}.", req.uri()),
+///     }
+/// }
+/// ```
 #[derive(Debug)]
-pub(crate) enum Error<'r> {
-    InvalidUri(&'r hyper::Uri),
-    UriParse(crate::http::uri::Error<'r>),
-    BadMethod(hyper::Method),
+pub struct Malformed {
+    method: String,
+    uri: String,
+    errors: Vec<RequestError>,
 }
 
-impl fmt::Display for Error<'_> {
-    /// Pretty prints a Request. This is primarily used by Rocket's logging
-    /// infrastructure.
+impl Malformed {
+    /// `errors` must be non-empty.
+    pub(crate) fn new(method: String, uri: String, errors: Vec<RequestError>) -> Self {
+        debug_assert!(!errors.is_empty());
+        Malformed { method, uri, errors }
+    }
+
+    /// The method of the incoming request, as sent by the client.
+    #[inline(always)]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The URI of the incoming request, as sent by the client.
+    #[inline(always)]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// The reason the incoming request could not be parsed. If both the URI
+    /// and the method are invalid, this is the URI's error; see
+    /// [`Malformed::errors()`] for both.
+    #[inline(always)]
+    pub fn error(&self) -> &RequestError {
+        &self.errors[0]
+    }
+
+    /// Every reason the incoming request could not be parsed: the URI's error,
+    /// if any, followed by the method's, if any.
+    #[inline(always)]
+    pub fn errors(&self) -> &[RequestError] {
+        &self.errors
+    }
+}
+
+/// The reason an incoming request failed to parse. See [`Malformed`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RequestError {
+    /// The request URI is not in origin form, i.e, it has a scheme or
+    /// authority or is missing a path.
+    InvalidUri,
+    /// The request URI failed to parse as an origin URI.
+    UriParse(crate::http::uri::Error<'static>),
+    /// The request method is invalid or unrecognized.
+    BadMethod,
+}
+
+impl fmt::Display for RequestError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::InvalidUri(u) => write!(f, "invalid origin URI: {}", u),
-            Error::UriParse(u) => write!(f, "URI `{}` failed to parse as origin", u),
-            Error::BadMethod(m) => write!(f, "invalid or unrecognized method: {}", m),
+            RequestError::InvalidUri => write!(f, "URI is not an origin URI"),
+            RequestError::UriParse(e) => write!(f, "URI failed to parse: {}", e),
+            RequestError::BadMethod => write!(f, "invalid or unrecognized method"),
         }
     }
 }
 
-impl<'r> From<crate::http::uri::Error<'r>> for Error<'r> {
-    fn from(uri_parse: crate::http::uri::Error<'r>) -> Self {
-        Error::UriParse(uri_parse)
+impl std::error::Error for RequestError {}
+
+impl fmt::Display for Malformed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: ", self.method, self.uri)?;
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            write!(f, "{}", error)?;
+        }
+
+        Ok(())
     }
 }
 
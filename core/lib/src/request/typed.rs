@@ -0,0 +1,98 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::Request;
+use crate::request::{FromRequest, Outcome};
+use crate::outcome::Outcome::*;
+use crate::http::{Status, TypedHeader, InvalidHeader};
+
+/// A request guard for any [`TypedHeader`].
+///
+/// Rocket's built-in typed headers are request guards on their own. `Typed`
+/// makes any other typed header, including those defined by applications, a
+/// request guard with the same behavior: if the header is missing, the guard
+/// forwards; if it is present but fails to parse, the guard fails with a
+/// status of `400 Bad Request` and the [`InvalidHeader`] error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::fmt;
+///
+/// use rocket::http::{TypedHeader, InvalidHeader};
+/// use rocket::request::Typed;
+///
+/// struct ApiVersion(u8);
+///
+/// impl TypedHeader for ApiVersion {
+///     const NAME: &'static str = "X-Api-Version";
+///
+///     fn parse(value: &str) -> Result<Self, InvalidHeader> {
+///         value.parse()
+///             .map(ApiVersion)
+///             .map_err(|_| InvalidHeader::new(Self::NAME, "expected a version number"))
+///     }
+/// }
+///
+/// impl fmt::Display for ApiVersion {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn index(version: Typed<ApiVersion>) -> String {
+///     format!("API version {}", version.into_inner())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Typed<T>(pub T);
+
+impl<T> Typed<T> {
+    /// Consumes `self` and returns the typed header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{ETag, EntityTag};
+    /// use rocket::request::Typed;
+    ///
+    /// let etag = Typed(ETag(EntityTag::strong("v1")));
+    /// assert_eq!(etag.into_inner(), ETag(EntityTag::strong("v1")));
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Typed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Typed<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Parses the typed header `T` from `request`, forwarding if it is missing.
+pub(crate) fn typed_header<T: TypedHeader>(request: &Request<'_>) -> Outcome<T, InvalidHeader> {
+    match T::from_headers(request.headers()) {
+        Some(Ok(header)) => Success(header),
+        Some(Err(e)) => Failure((Status::BadRequest, e)),
+        None => Forward(())
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: TypedHeader + Send> FromRequest<'r> for Typed<T> {
+    type Error = InvalidHeader;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        typed_header(request).map(Typed)
+    }
+}
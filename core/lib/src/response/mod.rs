@@ -29,6 +29,7 @@ mod named_file;
 mod response;
 mod debug;
 mod body;
+mod negotiate;
 
 pub(crate) mod flash;
 
@@ -46,6 +47,7 @@ pub use self::redirect::Redirect;
 pub use self::flash::Flash;
 pub use self::named_file::NamedFile;
 pub use self::debug::Debug;
+pub use self::negotiate::Negotiate;
 
 /// Type alias for the `Result` of a [`Responder::respond_to()`] call.
 pub type Result<'r> = std::result::Result<Response<'r>, crate::http::Status>;
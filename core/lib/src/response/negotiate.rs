@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::request::Request;
+use crate::response::{self, Response, Responder};
+use crate::http::{ContentType, MediaType, Status};
+
+type Respond = Box<dyn for<'r> FnOnce(&'r Request<'_>) -> response::Result<'static> + Send>;
+
+/// Responds with one of several representations of a resource, selected by
+/// the request's `Accept` header.
+///
+/// Each representation is _offered_ with a media type via [`Negotiate::offer()`].
+/// When responding, the offered media types are negotiated against the
+/// request's `Accept` header as described in [`Accept::negotiate()`]: the
+/// offer the client most prefers wins, with ties going to the offer made
+/// first. If the request has no `Accept` header, the first offer wins.
+///
+/// The selected representation's responder is used to respond, its
+/// `Content-Type` is set to the offered media type, and a `Vary: Accept`
+/// header is added. Responders for other representations are never invoked.
+/// If the client accepts none of the offered media types, `Negotiate`
+/// responds with an `Err` of `Status::NotAcceptable`, which is handled by the
+/// `406` catcher.
+///
+/// [`Accept::negotiate()`]: crate::http::Accept::negotiate()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::MediaType;
+/// use rocket::response::Negotiate;
+///
+/// #[get("/greeting")]
+/// fn greeting() -> Negotiate {
+///     Negotiate::new()
+///         .offer(MediaType::JSON, r#"{ "greeting": "Hello!" }"#)
+///         .offer(MediaType::HTML, "<p>Hello!</p>")
+///         .offer(MediaType::Plain, "Hello!")
+/// }
+/// ```
+#[derive(Default)]
+pub struct Negotiate {
+    offers: Vec<(MediaType, Respond)>,
+}
+
+impl Negotiate {
+    /// Creates a `Negotiate` without any offers. Unless offers are made,
+    /// responding with it always fails with `406 Not Acceptable`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Negotiate;
+    ///
+    /// let negotiate = Negotiate::new();
+    /// ```
+    pub fn new() -> Negotiate {
+        Negotiate::default()
+    }
+
+    /// Offers the representation `responder` with media type `media_type`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::MediaType;
+    /// use rocket::response::Negotiate;
+    ///
+    /// let negotiate = Negotiate::new()
+    ///     .offer(MediaType::Plain, "Hello!")
+    ///     .offer(MediaType::MsgPack, vec![0xa6, b'H', b'e', b'l', b'l', b'o', b'!']);
+    /// ```
+    pub fn offer<R>(mut self, media_type: MediaType, responder: R) -> Self
+        where R: for<'r> Responder<'r, 'static> + Send + 'static
+    {
+        let respond: Respond = Box::new(move |req: &Request<'_>| responder.respond_to(req));
+        self.offers.push((media_type, respond));
+        self
+    }
+}
+
+/// Responds with the offer the client most prefers, setting its Content-Type
+/// and adding `Vary: Accept`. Returns an `Err` of `Status::NotAcceptable` if
+/// no offer is acceptable.
+impl<'r> Responder<'r, 'static> for Negotiate {
+    fn respond_to(mut self, req: &'r Request<'_>) -> response::Result<'static> {
+        let offered: Vec<MediaType> = self.offers.iter().map(|(mt, _)| mt.clone()).collect();
+        let selected = match req.accept() {
+            Some(accept) => accept.negotiate(&offered),
+            None => offered.first(),
+        };
+
+        match selected.and_then(|s| offered.iter().position(|mt| std::ptr::eq(mt, s))) {
+            Some(i) => {
+                let (media_type, respond) = self.offers.swap_remove(i);
+                Response::build()
+                    .merge(respond(req)?)
+                    .header(ContentType::from(media_type))
+                    .raw_header_adjoin("Vary", "Accept")
+                    .ok()
+            }
+            None => {
+                let offered: Vec<_> = offered.iter().map(|mt| mt.to_string()).collect();
+                warn_!("No acceptable response among: {}.", offered.join(", "));
+                Err(Status::NotAcceptable)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Negotiate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offered: Vec<_> = self.offers.iter().map(|(mt, _)| mt).collect();
+        f.debug_struct("Negotiate").field("offers", &offered).finish()
+    }
+}
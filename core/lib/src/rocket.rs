@@ -6,13 +6,18 @@ use yansi::Paint;
 use either::Either;
 use figment::{Figment, Provider};
 
-use crate::{Catcher, Config, Route, Shutdown, sentinel};
-use crate::router::Router;
+use crate::{Blueprint, Catcher, Config, Route, Shutdown, sentinel};
+use crate::blueprint::{map_route, map_catcher};
+use crate::router::{Router, SharedRouter};
+use crate::route::{Host, RouteTable, Routing};
 use crate::trip_wire::TripWire;
 use crate::fairing::{Fairing, Fairings};
+use crate::tower::{Layer, Service, HyperService, HyperRequest, HyperResponse, BoxError};
+use crate::tower::RocketService;
 use crate::phase::{Phase, Build, Building, Ignite, Igniting, Orbit, Orbiting};
 use crate::phase::{Stateful, StateRef, State};
-use crate::http::uri::{self, Origin};
+use crate::request::Request;
+use crate::http::{Method, uri::{self, Origin}};
 use crate::http::ext::IntoOwned;
 use crate::error::{Error, ErrorKind};
 use crate::log::PaintExt;
@@ -213,19 +218,7 @@ impl Rocket<Build> {
               F: Fn(&mut Self, T),
               T: Clone + fmt::Display,
     {
-        let mut base = base.clone().try_into()
-            .map(|origin| origin.into_owned())
-            .unwrap_or_else(|e| {
-                error!("invalid {} base: {}", kind, Paint::white(&base));
-                error_!("{}", e);
-                panic!("aborting due to {} base error", kind);
-            });
-
-        if base.query().is_some() {
-            warn!("query in {} base '{}' is ignored", kind, Paint::white(&base));
-            base.clear_query();
-        }
-
+        let base = parse_base(kind, base);
         for unmounted_item in items {
             let item = m(&base, unmounted_item.clone())
                 .unwrap_or_else(|e| {
@@ -304,6 +297,48 @@ impl Rocket<Build> {
             |r, route| r.0.routes.push(route))
     }
 
+    /// Mounts all of the routes in the supplied vector at the given `base`
+    /// path, constraining each to requests for `host`. Any host constraint the
+    /// routes already carry is replaced. See [`Host`] for host pattern syntax.
+    ///
+    /// Routes mounted this way only collide with other routes whose hosts
+    /// overlap with `host` or which have no host.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Rocket::mount()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// #[get("/")]
+    /// fn admin() -> &'static str { "Admin dashboard." }
+    ///
+    /// #[get("/")]
+    /// fn tenant() -> &'static str { "Tenant home." }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount_host("admin.example.com", "/", routes![admin])
+    ///         .mount_host("*.tenants.example.com", "/", routes![tenant])
+    /// }
+    /// ```
+    pub fn mount_host<'a, H, B, R>(self, host: H, base: B, routes: R) -> Self
+        where H: Into<Host>,
+              B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let host = host.into();
+        let routes: Vec<_> = routes.into().into_iter()
+            .map(|mut route| { route.host = Some(host.clone()); route })
+            .collect();
+
+        self.mount(base, routes)
+    }
+
     /// Registers all of the catchers in the supplied vector, scoped to `base`.
     ///
     /// # Panics
@@ -342,6 +377,43 @@ impl Rocket<Build> {
             |r, catcher| r.0.catchers.push(catcher))
     }
 
+    /// Registers all of the catchers in the supplied vector, scoped to `base`
+    /// and to requests for `host`. Any host constraint the catchers already
+    /// carry is replaced. See [`Host`] for host pattern syntax.
+    ///
+    /// When several catchers match an error, catchers with a host are preferred
+    /// over catchers with the same base but no host.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Rocket::register()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// #[catch(404)]
+    /// fn admin_not_found() -> &'static str { "No such admin page." }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().register_host("admin.example.com", "/", catchers![admin_not_found])
+    /// }
+    /// ```
+    pub fn register_host<'a, H, B, C>(self, host: H, base: B, catchers: C) -> Self
+        where H: Into<Host>,
+              B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              C: Into<Vec<Catcher>>
+    {
+        let host = host.into();
+        let catchers: Vec<_> = catchers.into().into_iter()
+            .map(|mut catcher| { catcher.host = Some(host.clone()); catcher })
+            .collect();
+
+        self.register(base, catchers)
+    }
+
     /// Add `state` to the state managed by this instance of Rocket.
     ///
     /// This method can be called any number of times as long as each call
@@ -354,7 +426,9 @@ impl Rocket<Build> {
     ///
     /// # Panics
     ///
-    /// Panics if state of type `T` is already being managed.
+    /// Panics if state of type `T` is already being managed, unless it is
+    /// managed by a [nested](Rocket::nest()) blueprint, in which case the
+    /// conflict is reported as an error on [ignition](Rocket::ignite()).
     ///
     /// # Example
     ///
@@ -383,11 +457,17 @@ impl Rocket<Build> {
     ///         .mount("/", routes![int, string])
     /// }
     /// ```
-    pub fn manage<T>(self, state: T) -> Self
+    pub fn manage<T>(mut self, state: T) -> Self
         where T: Send + Sync + 'static
     {
         let type_name = std::any::type_name::<T>();
         if !self.state.set(state) {
+            // Like any conflict with a blueprint, this is reported at ignition.
+            if self.blueprint_state.contains(&type_name) {
+                self.0.duplicate_state.push(type_name);
+                return self;
+            }
+
             error!("state for type '{}' is already being managed", type_name);
             panic!("aborting due to duplicately managed state");
         }
@@ -418,6 +498,167 @@ impl Rocket<Build> {
         self
     }
 
+    /// Attaches a fairing to this instance of Rocket scoped to `base`.
+    ///
+    /// The fairing's request and response callbacks run only for requests
+    /// whose path has `base` as a prefix, segment-wise, as with catchers
+    /// [registered](Rocket::register()) at `base`: a fairing scoped to `/api`
+    /// runs for `/api` and `/api/items` but not for `/apis` or `/static`.
+    /// Request fairings observe any URI rewrites made by fairings that ran
+    /// before them. The fairing's ignite and liftoff callbacks are unaffected
+    /// and run as for any other fairing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid static path: a valid origin URI without
+    /// dynamic parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::http::Header;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .attach_at("/api", AdHoc::on_response("API Version", |_, res| {
+    ///             Box::pin(async move {
+    ///                 res.set_header(Header::new("X-Api-Version", "2"));
+    ///             })
+    ///         }))
+    /// }
+    /// ```
+    pub fn attach_at<'a, B, F>(mut self, base: B, fairing: F) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              F: Fairing,
+    {
+        let base = parse_static_base("fairing", base);
+        self.fairings.add_scoped(Some(base), Box::new(fairing));
+        self
+    }
+
+    /// Wraps the `hyper` service Rocket serves connections with in the tower
+    /// `layer`. See [`tower`](crate::tower) for details.
+    ///
+    /// Layers are applied in the order they're added: the first layer added
+    /// is outermost and sees requests first and responses last. An error
+    /// returned by a layered service, such as a timeout elapsing, is logged
+    /// and answered with a `503 Service Unavailable` response.
+    ///
+    /// Each layer is applied exactly once, when Rocket starts serving, and the
+    /// resulting service is cloned for every connection. State shared by
+    /// clones of a layered service is thus shared by all connections.
+    ///
+    /// # Example
+    ///
+    /// A layer that adds an `X-Served-By` header to every response:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use std::task::{Context, Poll};
+    ///
+    /// use rocket::futures::future::BoxFuture;
+    /// use rocket::tower::{Layer, Service, HyperService, HyperRequest, HyperResponse, BoxError};
+    ///
+    /// struct ServedBy;
+    ///
+    /// impl Layer<HyperService> for ServedBy {
+    ///     type Service = ServedByService;
+    ///
+    ///     fn layer(&self, inner: HyperService) -> Self::Service {
+    ///         ServedByService(inner)
+    ///     }
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct ServedByService(HyperService);
+    ///
+    /// impl Service<HyperRequest> for ServedByService {
+    ///     type Response = HyperResponse;
+    ///     type Error = BoxError;
+    ///     type Future = BoxFuture<'static, Result<HyperResponse, BoxError>>;
+    ///
+    ///     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+    ///         self.0.poll_ready(cx)
+    ///     }
+    ///
+    ///     fn call(&mut self, request: HyperRequest) -> Self::Future {
+    ///         let response = self.0.call(request);
+    ///         Box::pin(async move {
+    ///             let mut response = response.await?;
+    ///             response.headers_mut().insert("X-Served-By", "rocket".parse()?);
+    ///             Ok(response)
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().layer(ServedBy)
+    /// }
+    /// ```
+    pub fn layer<L>(mut self, layer: L) -> Self
+        where L: Layer<HyperService> + Send + Sync + 'static,
+              L::Service: Service<HyperRequest, Response = HyperResponse> + Clone + Send + 'static,
+              <L::Service as Service<HyperRequest>>::Error: Into<BoxError>,
+              <L::Service as Service<HyperRequest>>::Future: Send + 'static,
+    {
+        self.0.layers.push(layer);
+        self
+    }
+
+    /// Nests the routes, catchers, fairings, and managed state of `blueprint`
+    /// at `base`. See [`Blueprint`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid static path: a valid origin URI without
+    /// dynamic parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Blueprint;
+    ///
+    /// #[get("/")]
+    /// fn charges() -> &'static str { "charges" }
+    ///
+    /// #[get("/")]
+    /// fn login() -> &'static str { "login" }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let billing = Blueprint::new().mount("/charges", routes![charges]);
+    ///     let auth = Blueprint::new().mount("/login", routes![login]);
+    ///
+    ///     rocket::build()
+    ///         .nest("/billing", billing)
+    ///         .nest("/auth", auth)
+    /// }
+    /// ```
+    pub fn nest<'a, B>(mut self, base: B, blueprint: Blueprint) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+    {
+        let base = parse_static_base("blueprint", base);
+        let (routes, catchers) = (blueprint.routes, blueprint.catchers);
+        self.0.routes.extend(routes.into_iter().map(|r| map_route(&base, r)));
+        self.0.catchers.extend(catchers.into_iter().map(|c| map_catcher(&base, c)));
+        self.fairings.append_at(&base, blueprint.fairings);
+        for (type_name, set_state) in blueprint.state {
+            self.0.blueprint_state.push(type_name);
+            if let Err(type_name) = set_state(&self.state) {
+                self.0.duplicate_state.push(type_name);
+            }
+        }
+
+        self
+    }
+
     /// Returns a `Future` that transitions this instance of `Rocket` into the
     /// _ignite_ phase.
     ///
@@ -486,11 +727,17 @@ impl Rocket<Build> {
             }
         };
 
+        // Check for state that was managed more than once.
+        if !self.duplicate_state.is_empty() {
+            let duplicates = std::mem::take(&mut self.0.duplicate_state);
+            return Err(ErrorKind::DuplicateState(duplicates).into());
+        }
+
         // Initialize the router; check for collisions.
         let mut router = Router::new();
         self.routes.clone().into_iter().for_each(|r| router.add_route(r));
         self.catchers.clone().into_iter().for_each(|c| router.add_catcher(c));
-        router.finalize().map_err(ErrorKind::Collisions)?;
+        router.finalize(config.trailing_slash).map_err(ErrorKind::Collisions)?;
 
         // Finally, freeze managed state.
         self.state.freeze();
@@ -509,6 +756,7 @@ impl Rocket<Build> {
             figment: self.0.figment,
             fairings: self.0.fairings,
             state: self.0.state,
+            layers: self.0.layers,
         });
 
         // Query the sentinels, abort if requested.
@@ -519,6 +767,41 @@ impl Rocket<Build> {
     }
 }
 
+/// Parses `base` into a normalized origin URI without a query or dynamic
+/// parameters, panicking if it's invalid.
+pub(crate) fn parse_static_base<'a, B>(kind: &str, base: B) -> Origin<'static>
+    where B: TryInto<Origin<'a>> + Clone + fmt::Display, B::Error: fmt::Display
+{
+    let base = parse_base(kind, base);
+    if base.path_segments().any(|s| s.starts_with('<')) {
+        error!("invalid {} base: {}", kind, Paint::white(&base));
+        error_!("{} bases cannot contain dynamic parameters", kind);
+        panic!("aborting due to {} base error", kind);
+    }
+
+    base.into_normalized()
+}
+
+/// Parses `base` into an origin URI without a query, panicking if it's invalid.
+pub(crate) fn parse_base<'a, B>(kind: &str, base: B) -> Origin<'static>
+    where B: TryInto<Origin<'a>> + Clone + fmt::Display, B::Error: fmt::Display
+{
+    let mut base = base.clone().try_into()
+        .map(|origin| origin.into_owned())
+        .unwrap_or_else(|e| {
+            error!("invalid {} base: {}", kind, Paint::white(&base));
+            error_!("{}", e);
+            panic!("aborting due to {} base error", kind);
+        });
+
+    if base.query().is_some() {
+        warn!("query in {} base '{}' is ignored", kind, Paint::white(&base));
+        base.clear_query();
+    }
+
+    base
+}
+
 fn log_items<T, I, B, O>(e: &str, t: &str, items: I, base: B, origin: O)
     where T: fmt::Display + Copy, I: Iterator<Item = T>,
           B: Fn(&T) -> &Origin<'_>, O: Fn(&T) -> &Origin<'_>
@@ -553,6 +836,32 @@ impl Rocket<Ignite> {
         &self.config
     }
 
+    /// Returns a [`RouteTable`] describing the finalized routes: their
+    /// methods, full paths, ranks, formats, and handler names and locations.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// #[get("/hello")]
+    /// fn hello() -> &'static str { "Hello, world!" }
+    ///
+    /// # rocket::async_test(async {
+    /// let rocket = rocket::build()
+    ///     .mount("/", routes![hello])
+    ///     .mount("/hi", routes![hello])
+    ///     .ignite().await
+    ///     .unwrap();
+    ///
+    /// let table = rocket.route_table();
+    /// let paths: Vec<_> = table.entries().iter().map(|e| e.path.as_str()).collect();
+    /// assert_eq!(paths, &["/hello", "/hi/hello"]);
+    /// # });
+    /// ```
+    pub fn route_table(&self) -> RouteTable {
+        RouteTable::new(self.router.routes())
+    }
+
     /// Returns a handle which can be used to trigger a shutdown and detect a
     /// triggered shutdown.
     ///
@@ -589,14 +898,44 @@ impl Rocket<Ignite> {
         self.shutdown.clone()
     }
 
+    /// Launches the instance as a tower [`Service`], returning a
+    /// [`RocketService`](crate::tower::RocketService) that serves requests
+    /// with the application without binding to a port. Liftoff fairings are
+    /// run before the service is returned.
+    ///
+    /// Requests served by the returned service are handled exactly as if they
+    /// were received by Rocket's own server except that
+    /// [layers](Rocket::layer()) are not applied.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// #[rocket::main]
+    /// async fn main() -> Result<(), rocket::Error> {
+    ///     let rocket = rocket::build().ignite().await?;
+    ///     let service = rocket.into_service().await;
+    ///     /* embed `service` in another server */
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn into_service(self) -> RocketService {
+        let rocket = self.into_orbit();
+        rocket.fairings.handle_liftoff(&rocket).await;
+        launch_info!("{}{}", Paint::emoji("🚀 "),
+            Paint::default("Rocket has launched as a service").bold());
+
+        RocketService::new(rocket)
+    }
+
     fn into_orbit(self) -> Rocket<Orbit> {
         Rocket(Orbiting {
-            router: self.0.router,
+            router: std::sync::Arc::new(SharedRouter::new(self.0.router)),
             fairings: self.0.fairings,
             figment: self.0.figment,
             config: self.0.config,
             state: self.0.state,
             shutdown: self.0.shutdown,
+            layers: self.0.layers,
         })
     }
 
@@ -676,12 +1015,99 @@ impl Rocket<Orbit> {
     pub fn shutdown(&self) -> Shutdown {
         self.shutdown.clone()
     }
+
+    /// Returns the methods, in a stable order, of the mounted routes whose
+    /// path, query, and host match `req`, irrespective of `req`'s method and
+    /// format. `HEAD` is included whenever `GET` is since Rocket handles `HEAD`
+    /// requests automatically.
+    ///
+    /// This is useful, for instance, to answer `OPTIONS` requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Method;
+    /// use rocket::local::blocking::Client;
+    ///
+    /// #[get("/item")] fn get_item() { }
+    /// #[put("/item")] fn put_item() { }
+    ///
+    /// let rocket = rocket::build().mount("/", routes![get_item, put_item]);
+    /// let client = Client::debug(rocket).unwrap();
+    /// let request = client.delete("/item");
+    /// let methods = client.rocket().route_methods(&request);
+    /// assert_eq!(methods, &[Method::Get, Method::Head, Method::Put]);
+    /// ```
+    pub fn route_methods(&self, req: &Request<'_>) -> Vec<Method> {
+        self.router.get().methods(req)
+    }
+
+    /// Returns the number of routes that match `req`, found with the router's
+    /// trees or, if `linear`, by checking every route. Used by benchmarks.
+    #[doc(hidden)]
+    #[cfg(feature = "__bench")]
+    pub fn __count_matching_routes<'r>(&self, req: &'r Request<'r>, linear: bool) -> usize {
+        let router = self.router.get();
+        match linear {
+            true => router.route_linear(req).count(),
+            false => router.route(req).count(),
+        }
+    }
+
+    /// Returns a [`RouteTable`] describing the finalized routes. See
+    /// [`Rocket::<Ignite>::route_table()`](Rocket::route_table()) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::blocking::Client;
+    ///
+    /// #[get("/hello")]
+    /// fn hello() -> &'static str { "Hello, world!" }
+    ///
+    /// let client = Client::debug(rocket::build().mount("/", routes![hello])).unwrap();
+    /// assert_eq!(client.rocket().route_table().entries()[0].path, "/hello");
+    /// ```
+    pub fn route_table(&self) -> RouteTable {
+        RouteTable::new(self.router.get().routes())
+    }
+
+    /// Returns a handle which can be used to add and remove routes while Rocket
+    /// is running. See [`Routing`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// #[get("/")]
+    /// fn plugin() -> &'static str { "plugin" }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .attach(AdHoc::on_liftoff("Plugins", |rocket| Box::pin(async move {
+    ///             let routing = rocket.routing();
+    ///             routing.mount("/plugin", routes![plugin]).expect("no collisions");
+    ///         })))
+    /// }
+    /// ```
+    pub fn routing(&self) -> Routing {
+        Routing::new(self.router.clone(), self.config.trailing_slash)
+    }
 }
 
 impl<P: Phase> Rocket<P> {
     /// Returns an iterator over all of the routes mounted on this instance of
     /// Rocket. The order is unspecified.
     ///
+    /// Once Rocket is in orbit, this is a snapshot of the routes as they were at
+    /// launch: it does not change as routes are added or removed via
+    /// [`Routing`]. Use [`Routing::routes()`] to retrieve the current routes.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -706,7 +1132,7 @@ impl<P: Phase> Rocket<P> {
         match self.0.as_state_ref() {
             StateRef::Build(p) => Either::Left(p.routes.iter()),
             StateRef::Ignite(p) => Either::Right(p.router.routes()),
-            StateRef::Orbit(p) => Either::Right(p.router.routes()),
+            StateRef::Orbit(p) => Either::Right(p.router.launched().routes()),
         }
     }
 
@@ -737,7 +1163,7 @@ impl<P: Phase> Rocket<P> {
         match self.0.as_state_ref() {
             StateRef::Build(p) => Either::Left(p.catchers.iter()),
             StateRef::Ignite(p) => Either::Right(p.router.catchers()),
-            StateRef::Orbit(p) => Either::Right(p.router.catchers()),
+            StateRef::Orbit(p) => Either::Right(p.router.launched().catchers()),
         }
     }
 
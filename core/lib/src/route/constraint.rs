@@ -0,0 +1,238 @@
+use std::fmt;
+
+use regex::Regex;
+use regex_syntax::hir::{self, Hir, HirKind, ClassUnicode, ClassUnicodeRange};
+
+/// A constraint on the value of a dynamic path segment, as in `<id:u64>` or
+/// `<name:[a-z]+>`.
+///
+/// A constraint is either the name of a primitive type, in which case a segment
+/// satisfies the constraint if it parses as that type, or a regular expression,
+/// in which case a segment satisfies the constraint if the expression matches
+/// the _entire_ (percent-decoded) segment.
+#[derive(Debug, Clone)]
+pub(crate) enum Constraint {
+    Type(&'static str, fn(&str) -> bool, Chars),
+    Regex(Regex, Chars),
+}
+
+/// An over-approximation of the characters in any non-empty string matching a
+/// constraint: every such string begins with a character in `first` and
+/// consists only of characters in `all`.
+#[derive(Debug, Clone)]
+pub(crate) struct Chars {
+    first: ClassUnicode,
+    all: ClassUnicode,
+}
+
+// The types must match those recognized by the route attribute in codegen.
+macro_rules! types {
+    ($($name:ident: $chars:expr),* $(,)?) => (
+        const TYPES: &[(&str, fn(&str) -> bool, &str)] = &[
+            $((stringify!($name), |s| s.parse::<$name>().is_ok(), $chars)),*
+        ];
+    )
+}
+
+const UNSIGNED: &str = "+0123456789";
+const SIGNED: &str = "+-0123456789";
+
+types! {
+    u8: UNSIGNED, u16: UNSIGNED, u32: UNSIGNED, u64: UNSIGNED, u128: UNSIGNED,
+    usize: UNSIGNED,
+    i8: SIGNED, i16: SIGNED, i32: SIGNED, i64: SIGNED, i128: SIGNED,
+    isize: SIGNED,
+    bool: "aeflrstu",
+    // Floats also parse `inf`, `NaN`, and so on; we don't bother narrowing.
+    f32: "", f64: "",
+}
+
+impl Constraint {
+    /// Parses `source`. If `source` is the name of a known type, the
+    /// constraint is a type constraint. Otherwise it is a regular expression.
+    pub fn parse(source: &str) -> Result<Constraint, regex::Error> {
+        if let Some(&(name, check, chars)) = TYPES.iter().find(|(n, ..)| *n == source) {
+            let chars = match chars.is_empty() {
+                true => Chars::any(),
+                false => Chars::of(chars),
+            };
+
+            return Ok(Constraint::Type(name, check, chars));
+        }
+
+        let regex = Regex::new(&format!("^(?:{})$", source))?;
+        let chars = regex_syntax::Parser::new()
+            .parse(source)
+            .map(|hir| Chars::from(&hir).0)
+            .unwrap_or_else(|_| Chars::any());
+
+        Ok(Constraint::Regex(regex, chars))
+    }
+
+    /// Returns `true` if the decoded path segment `segment` satisfies `self`.
+    pub fn matches(&self, segment: &str) -> bool {
+        match self {
+            Constraint::Type(_, check, _) => check(segment),
+            Constraint::Regex(regex, _) => regex.is_match(segment),
+        }
+    }
+
+    /// Returns `true` if there may exist a segment that satisfies both `self`
+    /// and `other`. Never returns `false` if such a segment exists.
+    pub fn overlaps(&self, other: &Constraint) -> bool {
+        // `bool` admits exactly two values, so we can check directly.
+        for (a, b) in &[(self, other), (other, self)] {
+            if let Constraint::Type("bool", ..) = a {
+                return b.matches("true") || b.matches("false");
+            }
+        }
+
+        let (a, b) = (self.chars(), other.chars());
+        intersects(&a.first, &b.first) && intersects(&a.all, &b.all)
+    }
+
+    fn chars(&self) -> &Chars {
+        match self {
+            Constraint::Type(_, _, chars) | Constraint::Regex(_, chars) => chars,
+        }
+    }
+}
+
+fn intersects(a: &ClassUnicode, b: &ClassUnicode) -> bool {
+    let mut intersection = a.clone();
+    intersection.intersect(b);
+    intersection.iter().next().is_some()
+}
+
+impl Chars {
+    fn none() -> Chars {
+        Chars { first: ClassUnicode::empty(), all: ClassUnicode::empty() }
+    }
+
+    fn any() -> Chars {
+        let any = ClassUnicode::new(vec![ClassUnicodeRange::new('\0', char::MAX)]);
+        Chars { first: any.clone(), all: any }
+    }
+
+    fn of(chars: &str) -> Chars {
+        let class = ClassUnicode::new(chars.chars().map(|c| ClassUnicodeRange::new(c, c)));
+        Chars { first: class.clone(), all: class }
+    }
+
+    fn union(&mut self, other: &Chars) {
+        self.first.union(&other.first);
+        self.all.union(&other.all);
+    }
+
+    /// Returns the characters of `hir` and whether `hir` matches the empty
+    /// string.
+    fn from(hir: &Hir) -> (Chars, bool) {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Anchor(_) | HirKind::WordBoundary(_) => {
+                (Chars::none(), true)
+            }
+            HirKind::Literal(hir::Literal::Unicode(c)) => (Chars::of(&c.to_string()), false),
+            HirKind::Class(hir::Class::Unicode(class)) => {
+                (Chars { first: class.clone(), all: class.clone() }, false)
+            }
+            HirKind::Literal(hir::Literal::Byte(_)) | HirKind::Class(hir::Class::Bytes(_)) => {
+                (Chars::any(), false)
+            }
+            HirKind::Repetition(rep) => {
+                let (chars, nullable) = Chars::from(&rep.hir);
+                let optional = match rep.kind {
+                    hir::RepetitionKind::ZeroOrOne | hir::RepetitionKind::ZeroOrMore => true,
+                    hir::RepetitionKind::OneOrMore => false,
+                    hir::RepetitionKind::Range(ref range) => match *range {
+                        hir::RepetitionRange::Exactly(n)
+                            | hir::RepetitionRange::AtLeast(n)
+                            | hir::RepetitionRange::Bounded(n, _) => n == 0,
+                    }
+                };
+
+                (chars, nullable || optional)
+            }
+            HirKind::Group(group) => Chars::from(&group.hir),
+            HirKind::Concat(hirs) => {
+                let (mut chars, mut nullable) = (Chars::none(), true);
+                for (sub, sub_nullable) in hirs.iter().map(Chars::from) {
+                    // Only a prefix of empty-matching expressions can be skipped
+                    // to reach the first character.
+                    if nullable {
+                        chars.first.union(&sub.first);
+                    }
+
+                    chars.all.union(&sub.all);
+                    nullable &= sub_nullable;
+                }
+
+                (chars, nullable)
+            }
+            HirKind::Alternation(hirs) => {
+                let (mut chars, mut nullable) = (Chars::none(), false);
+                for (sub, sub_nullable) in hirs.iter().map(Chars::from) {
+                    chars.union(&sub);
+                    nullable |= sub_nullable;
+                }
+
+                (chars, nullable)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Type(name, ..) => name.fmt(f),
+            Constraint::Regex(regex, _) => {
+                let source = regex.as_str();
+                source[4..source.len() - 2].fmt(f)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Constraint;
+
+    fn overlap(a: &str, b: &str) -> bool {
+        let (a, b) = (Constraint::parse(a).unwrap(), Constraint::parse(b).unwrap());
+        assert_eq!(a.overlaps(&b), b.overlaps(&a));
+        a.overlaps(&b)
+    }
+
+    #[test]
+    fn test_matches() {
+        let c = Constraint::parse("u8").unwrap();
+        assert!(c.matches("0") && c.matches("255") && !c.matches("256"));
+        assert!(!c.matches("-1") && !c.matches("a"));
+
+        let c = Constraint::parse("[a-z]+").unwrap();
+        assert!(c.matches("abc") && !c.matches("abc1") && !c.matches("1abc"));
+
+        let c = Constraint::parse("bool").unwrap();
+        assert!(c.matches("true") && c.matches("false") && !c.matches("1"));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(overlap("u8", "u64"));
+        assert!(overlap("u8", "i8"));
+        assert!(overlap("u64", "[0-9]+"));
+        assert!(overlap("u64", "a|1"));
+        assert!(overlap("[a-z]+", "[a-c]*"));
+        assert!(overlap("f64", "[a-z]+"));
+        assert!(overlap("bool", "t.*"));
+        assert!(overlap("(a)?[0-9]", "[0-9]+"));
+
+        assert!(!overlap("u64", "[a-z]+"));
+        assert!(!overlap("i64", "[a-z][a-z0-9-]*"));
+        assert!(!overlap("bool", "u8"));
+        assert!(!overlap("bool", "[a-z]{1,3}"));
+        assert!(!overlap("[a-f]+", "[g-z]+"));
+        assert!(!overlap("a[0-9]*", "b[0-9]*"));
+        assert!(!overlap("user-[0-9]+", "team-[0-9]+"));
+    }
+}
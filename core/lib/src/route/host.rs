@@ -0,0 +1,219 @@
+use std::fmt;
+use std::borrow::Cow;
+
+/// A host pattern that constrains the requests a route or catcher matches.
+///
+/// A `Host` is either an exact host name, like `admin.example.com`, or a
+/// wildcard pattern, like `*.example.com`, which matches any host with at least
+/// one label preceding the suffix: `a.example.com` and `a.b.example.com` but
+/// _not_ `example.com`. Matching is case-insensitive and ignores the port, if
+/// any, in the request's `Host` header.
+///
+/// Host constraints are set by mounting routes with [`Rocket::mount_host()`],
+/// registering catchers with [`Rocket::register_host()`], or via the `host`
+/// route attribute argument:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[get("/", host = "admin.example.com")]
+/// fn admin_index() -> &'static str { "admin" }
+///
+/// #[get("/")]
+/// fn api_index() -> &'static str { "api" }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![admin_index])
+///         .mount_host("*.api.example.com", "/", routes![api_index])
+/// }
+/// ```
+///
+/// A route or catcher without a host constraint matches requests for any host.
+///
+/// [`Rocket::mount_host()`]: crate::Rocket::mount_host()
+/// [`Rocket::register_host()`]: crate::Rocket::register_host()
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Host(Cow<'static, str>);
+
+impl Host {
+    /// Creates a new host pattern from `pattern`. If `pattern` begins with
+    /// `*.`, it is a wildcard pattern. Otherwise it matches exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` isn't a valid host pattern: a host name made of
+    /// dot-separated labels of ASCII letters, digits, and inner hyphens, or a
+    /// bracketed IPv6 address, optionally preceded by `*.` in the case of a
+    /// host name. Ports are not allowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Host;
+    ///
+    /// let host = Host::new("*.example.com");
+    /// assert!(host.matches("a.example.com"));
+    /// assert!(host.matches("a.b.EXAMPLE.com:8000"));
+    /// assert!(!host.matches("example.com"));
+    ///
+    /// let host = Host::new("example.com");
+    /// assert!(host.matches("example.com"));
+    /// assert!(!host.matches("a.example.com"));
+    /// ```
+    pub fn new<S: Into<Cow<'static, str>>>(pattern: S) -> Host {
+        let pattern = pattern.into();
+        if let Err(e) = validate(&pattern) {
+            panic!("invalid host pattern `{}`: {}", pattern, e);
+        }
+
+        Host(pattern)
+    }
+
+    /// Returns the pattern as a string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Host;
+    ///
+    /// assert_eq!(Host::new("*.example.com").as_str(), "*.example.com");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if this is a wildcard pattern.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Host;
+    ///
+    /// assert!(Host::new("*.example.com").is_wildcard());
+    /// assert!(!Host::new("example.com").is_wildcard());
+    /// ```
+    pub fn is_wildcard(&self) -> bool {
+        self.suffix().is_some()
+    }
+
+    /// The `.example.com` in `*.example.com`, if this is a wildcard.
+    fn suffix(&self) -> Option<&str> {
+        self.0.strip_prefix('*').filter(|s| s.starts_with('.'))
+    }
+
+    /// Returns `true` if `host`, which may include a port, matches `self`.
+    ///
+    /// See the [type-level docs](Host) for an example.
+    pub fn matches(&self, host: &str) -> bool {
+        let host = strip_port(host);
+        match self.suffix() {
+            Some(suffix) => ends_with_ignore_case(host, suffix) && host.len() > suffix.len(),
+            None => host.eq_ignore_ascii_case(&self.0),
+        }
+    }
+
+    /// Returns `true` if there exists a host that matches both `self` and
+    /// `other`.
+    pub(crate) fn overlaps(&self, other: &Host) -> bool {
+        match (self.suffix(), other.suffix()) {
+            (Some(a), Some(b)) => ends_with_ignore_case(a, b) || ends_with_ignore_case(b, a),
+            (Some(_), None) => self.matches(other.as_str()),
+            (None, Some(_)) => other.matches(self.as_str()),
+            (None, None) => self.0.eq_ignore_ascii_case(&other.0),
+        }
+    }
+}
+
+/// Checks that `pattern` is a valid host pattern, as documented in
+/// [`Host::new()`]. Kept in sync with the check in codegen.
+fn validate(pattern: &str) -> Result<(), &'static str> {
+    if pattern.starts_with('[') {
+        let valid = pattern.len() > 2
+            && pattern.ends_with(']')
+            && pattern[1..pattern.len() - 1].bytes().all(|b| b.is_ascii_hexdigit() || b == b':');
+
+        return if valid { Ok(()) } else { Err("expected a bracketed IPv6 address") };
+    }
+
+    let name = pattern.strip_prefix("*.").unwrap_or(pattern);
+    if name.is_empty() {
+        return Err("expected a host name");
+    } else if name.len() > 253 {
+        return Err("host names are at most 253 characters long");
+    } else if name.contains(':') {
+        return Err("host patterns cannot contain a port");
+    } else if name.contains('*') {
+        return Err("`*` is only allowed as the first label, as in `*.example.com`");
+    }
+
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err("host name labels must be between 1 and 63 characters long");
+        } else if label.starts_with('-') || label.ends_with('-') {
+            return Err("host name labels cannot begin or end with `-`");
+        } else if !label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+            return Err("host name labels can only contain ASCII letters, digits, and `-`");
+        }
+    }
+
+    Ok(())
+}
+
+/// Strips the `:port`, if any, from a `Host` header value.
+pub(crate) fn strip_port(host: &str) -> &str {
+    // An IPv6 literal is bracketed: `[::1]:8000`.
+    if host.starts_with('[') {
+        return host.find(']').map_or(host, |i| &host[..=i]);
+    }
+
+    host.rfind(':').map_or(host, |i| &host[..i])
+}
+
+fn ends_with_ignore_case(string: &str, suffix: &str) -> bool {
+    let (string, suffix) = (string.as_bytes(), suffix.as_bytes());
+    string.len() >= suffix.len()
+        && string[string.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<&'static str> for Host {
+    fn from(pattern: &'static str) -> Self {
+        Host::new(pattern)
+    }
+}
+
+impl From<String> for Host {
+    fn from(pattern: String) -> Self {
+        Host::new(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+
+    #[test]
+    fn test_validate() {
+        for valid in &["example.com", "*.example.com", "a-b.C0m", "localhost", "[::1]"] {
+            assert!(validate(valid).is_ok(), "{}", valid);
+        }
+
+        for invalid in &["", "*", "*.", "*foo", "a.*.b", "foo:80", "*.a:80", "a..b", "a.", "-a.b",
+            "a_b.com", "[::1]:80", "[]", "[zz]"]
+        {
+            assert!(validate(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
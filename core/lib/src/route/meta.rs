@@ -0,0 +1,158 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Typed metadata attached to a [`Route`](crate::Route).
+///
+/// A `Meta` holds at most one value of any given type. Values are typically
+/// set via the `meta` argument to a route attribute and read back by guards,
+/// fairings, and catchers through [`Request::route()`]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::fairing::AdHoc;
+/// use rocket::http::Header;
+///
+/// struct Deprecated { sunset: &'static str }
+///
+/// #[get("/v1/items", meta(Deprecated { sunset: "Sat, 01 Jan 2028 00:00:00 GMT" }))]
+/// fn items() { /* .. */ }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![items])
+///         .attach(AdHoc::on_response("Sunset", |req, res| Box::pin(async move {
+///             let meta = req.route().and_then(|r| r.meta.get::<Deprecated>());
+///             if let Some(deprecated) = meta {
+///                 res.set_header(Header::new("Deprecation", "true"));
+///                 res.set_header(Header::new("Sunset", deprecated.sunset));
+///             }
+///         })))
+/// }
+/// ```
+///
+/// Note that a request is routed _after_ request fairings run, so only
+/// response fairings observe the route and its metadata.
+///
+/// Metadata can also be set directly on a `Route`:
+///
+/// ```rust
+/// use rocket::Route;
+/// use rocket::http::Method;
+/// # use rocket::route::dummy_handler as handler;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Scope(&'static str);
+///
+/// let mut route = Route::new(Method::Get, "/admin", handler);
+/// route.meta.set(Scope("admin"));
+/// assert_eq!(route.meta.get::<Scope>(), Some(&Scope("admin")));
+/// ```
+///
+/// [`Request::route()`]: crate::Request::route()
+#[derive(Clone, Default)]
+pub struct Meta {
+    map: HashMap<TypeId, (&'static str, Arc<dyn Any + Send + Sync>)>,
+}
+
+impl Meta {
+    /// Creates a new, empty `Meta`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Meta;
+    ///
+    /// let meta = Meta::new();
+    /// assert!(meta.is_empty());
+    /// ```
+    pub fn new() -> Meta {
+        Meta::default()
+    }
+
+    /// Sets the value of type `T` to `value`, replacing any existing value of
+    /// type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Meta;
+    ///
+    /// struct Public;
+    /// struct Scope(&'static str);
+    ///
+    /// let mut meta = Meta::new();
+    /// meta.set(Public);
+    /// meta.set(Scope("user"));
+    /// meta.set(Scope("admin"));
+    /// assert_eq!(meta.get::<Scope>().unwrap().0, "admin");
+    /// ```
+    pub fn set<T: Send + Sync + 'static>(&mut self, value: T) {
+        let value = (std::any::type_name::<T>(), Arc::new(value) as Arc<dyn Any + Send + Sync>);
+        self.map.insert(TypeId::of::<T>(), value);
+    }
+
+    /// Returns the value of type `T`, if one has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Meta;
+    ///
+    /// struct Scope(&'static str);
+    ///
+    /// let mut meta = Meta::new();
+    /// assert!(meta.get::<Scope>().is_none());
+    ///
+    /// meta.set(Scope("admin"));
+    /// assert_eq!(meta.get::<Scope>().unwrap().0, "admin");
+    /// ```
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map.get(&TypeId::of::<T>()).and_then(|(_, value)| value.downcast_ref())
+    }
+
+    /// Returns `true` if a value of type `T` has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Meta;
+    ///
+    /// struct Public;
+    ///
+    /// let mut meta = Meta::new();
+    /// assert!(!meta.contains::<Public>());
+    ///
+    /// meta.set(Public);
+    /// assert!(meta.contains::<Public>());
+    /// ```
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns `true` if no values have been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::route::Meta;
+    ///
+    /// let mut meta = Meta::new();
+    /// assert!(meta.is_empty());
+    ///
+    /// meta.set(10usize);
+    /// assert!(!meta.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl fmt::Debug for Meta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.map.values().map(|(name, _)| name)).finish()
+    }
+}
@@ -4,9 +4,20 @@ mod route;
 mod handler;
 mod uri;
 mod segment;
+mod host;
+mod constraint;
+mod meta;
+mod table;
+mod routing;
 
 pub use route::*;
 pub use handler::*;
 pub use uri::*;
+pub use host::Host;
+pub use meta::Meta;
+pub use table::{RouteTable, RouteEntry, SourceLocation};
+pub use routing::Routing;
 
 pub(crate) use segment::Segment;
+pub(crate) use constraint::Constraint;
+pub(crate) use host::strip_port;
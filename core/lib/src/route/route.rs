@@ -3,9 +3,11 @@ use std::convert::From;
 use std::borrow::Cow;
 
 use yansi::Paint;
+use state::Storage;
 
 use crate::http::{uri, Method, MediaType};
-use crate::route::{Handler, RouteUri, BoxFuture};
+use crate::data::Limits;
+use crate::route::{Handler, RouteUri, Host, Meta, SourceLocation, BoxFuture};
 use crate::sentinel::Sentry;
 
 /// A request handling route.
@@ -50,9 +52,15 @@ use crate::sentinel::Sentry;
 ///     - Non-specific `Accept` header components (`*`) match anything.
 ///   * All static components in the route's path match the corresponding
 ///     components in the same position in the incoming request.
+///   * All constrained dynamic components in the route's path, like `<id:u64>`
+///     or `<name:[a-z]+>`, are satisfied by the corresponding components in
+///     the incoming request. A type constraint is satisfied by any component
+///     that parses as the type; a regular expression must match the entire
+///     component.
 ///   * All static components in the route's query string are also in the
 ///     request query string, though in any position. If there is no query
 ///     in the route, requests with and without queries match.
+///   * The route's [`Host`] (if any) matches the request's `Host` header.
 ///
 /// Rocket routes requests to matching routes.
 ///
@@ -70,6 +78,15 @@ use crate::sentinel::Sentry;
 /// parameters are allowed -- queries do not directly impact whether two routes
 /// collide.
 ///
+/// Dynamic path components with constraints collide only if some component can
+/// satisfy both. Thus, `/<id:u64>` and `/<name:[a-z]+>` do not collide, while
+/// `/<id:u64>` and `/<name>` do. Overlap between regular expressions is
+/// approximated conservatively by the characters each expression can match.
+///
+/// Routes with host constraints collide only if some host matches both
+/// constraints. A route _without_ a host constraint matches every host and thus
+/// collides with otherwise colliding routes regardless of their host.
+///
 /// ## Resolving Collisions
 ///
 /// Collisions are resolved through _ranking_. Routes with lower ranks have
@@ -188,8 +205,22 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The host this route matches against, if any.
+    pub host: Option<Host>,
+    /// Typed metadata, as set via the `meta` route attribute argument.
+    pub meta: Meta,
+    /// Data limits overriding the configured limits for requests routed to
+    /// this route, as set via the `limits` route attribute argument. A limit
+    /// also overrides the configured limits below it: a `file` limit here
+    /// takes precedence over a configured `file/pdf` limit.
+    pub limits: Option<Limits>,
+    /// The source location of the route's handler, if known. Set for routes
+    /// generated by the route attributes.
+    pub location: Option<SourceLocation>,
     /// The discovered sentinels.
     pub(crate) sentinels: Vec<Sentry>,
+    /// The configured limits overridden by `limits`, computed on first use.
+    pub(crate) merged_limits: Storage<Limits>,
 }
 
 impl Route {
@@ -198,7 +229,9 @@ impl Route {
     ///
     /// # Panics
     ///
-    /// Panics if `path` is not a valid Rocket route URI.
+    /// Panics if `path` is not a valid Rocket route URI, including if a
+    /// dynamic path parameter's constraint is not a valid regular expression
+    /// or is applied to a trailing parameter, as in `<path..:[a-z]+>`.
     ///
     /// # Example
     ///
@@ -223,7 +256,9 @@ impl Route {
     ///
     /// # Panics
     ///
-    /// Panics if `path` is not a valid Rocket route URI.
+    /// Panics if `path` is not a valid Rocket route URI, including if a
+    /// dynamic path parameter's constraint is not a valid regular expression
+    /// or is applied to a trailing parameter, as in `<path..:[a-z]+>`.
     ///
     /// # Example
     ///
@@ -250,7 +285,12 @@ impl Route {
         Route {
             name: None,
             format: None,
+            host: None,
+            meta: Meta::new(),
+            limits: None,
+            location: None,
             sentinels: Vec::new(),
+            merged_limits: Storage::new(),
             handler: Box::new(handler),
             rank, uri, method,
         }
@@ -288,6 +328,15 @@ impl Route {
         self.uri = RouteUri::try_new(&base, &self.uri.unmounted_origin.to_string())?;
         Ok(self)
     }
+
+    /// Returns the limits in effect for requests to this route: `config`, the
+    /// configured limits, with any limits in `self.limits` overriding them.
+    pub(crate) fn limits_under<'a>(&'a self, config: &'a Limits) -> &'a Limits {
+        match self.limits {
+            Some(ref limits) => self.merged_limits.get_or_set(|| config.overridden_by(limits)),
+            None => config,
+        }
+    }
 }
 
 impl fmt::Display for Route {
@@ -311,6 +360,10 @@ impl fmt::Display for Route {
             write!(f, " {}", Paint::yellow(format))?;
         }
 
+        if let Some(ref host) = self.host {
+            write!(f, " @ {}", Paint::magenta(host))?;
+        }
+
         Ok(())
     }
 }
@@ -323,6 +376,10 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("host", &self.host)
+            .field("meta", &self.meta)
+            .field("limits", &self.limits)
+            .field("location", &self.location)
             .finish()
     }
 }
@@ -342,6 +399,14 @@ pub struct StaticInfo {
     pub handler: for<'r> fn(&'r crate::Request<'_>, crate::Data) -> BoxFuture<'r>,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The route's host constraint, if any.
+    pub host: Option<&'static str>,
+    /// The route's metadata.
+    pub meta: Meta,
+    /// The route's limits, if any.
+    pub limits: Option<Limits>,
+    /// The source location of the annotated function.
+    pub location: SourceLocation,
     /// Route-derived sentinels, if any.
     /// This isn't `&'static [SentryInfo]` because `type_name()` isn't `const`.
     pub sentinels: Vec<Sentry>,
@@ -359,7 +424,12 @@ impl From<StaticInfo> for Route {
             handler: Box::new(info.handler),
             rank: info.rank.unwrap_or_else(|| uri.default_rank()),
             format: info.format,
+            host: info.host.map(Host::new),
+            meta: info.meta,
+            limits: info.limits,
+            location: Some(info.location),
             sentinels: info.sentinels.into_iter().collect(),
+            merged_limits: Storage::new(),
             uri,
         }
     }
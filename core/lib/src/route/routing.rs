@@ -0,0 +1,225 @@
+use std::fmt;
+use std::sync::Arc;
+use std::convert::TryInto;
+
+use crate::Route;
+use crate::config::TrailingSlash;
+use crate::router::SharedRouter;
+use crate::request::{FromRequest, Outcome, Request};
+use crate::http::uri::Origin;
+
+/// A handle to add and remove routes while Rocket is running.
+///
+/// Once Rocket is in orbit, the set of routes established at ignition can be
+/// changed through a `Routing` handle, retrieved via [`Rocket::routing()`]
+/// or as a request guard. Handles are cheap to clone and can be moved to other
+/// tasks.
+///
+/// Every change is atomic: a request is routed either entirely before or
+/// entirely after a change. Requests already in flight when a change is made
+/// are unaffected, even if the route handling them is removed. Added routes
+/// are checked for [collisions](crate::Route#collisions) against the routes
+/// already present, using the configured [`TrailingSlash`] policy, and a change
+/// that introduces a collision is rejected as a whole.
+///
+/// Unlike at ignition, the [sentinels](crate::Sentinel) of routes added at
+/// runtime are not checked. Each change copies the set of routes, so `Routing`
+/// is intended for routes that change occasionally, such as routes loaded from
+/// configuration. The routes as they were at launch remain available via
+/// [`Rocket::routes()`](crate::Rocket::routes()); the current routes are
+/// returned by [`Routing::routes()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::route::Routing;
+/// use rocket::local::blocking::Client;
+///
+/// #[get("/")]
+/// fn plugin() -> &'static str { "plugin" }
+///
+/// #[post("/plugins/<name>")]
+/// fn enable(name: &str, routing: Routing) -> Status {
+///     match routing.mount(format!("/plugins/{}", name), routes![plugin]) {
+///         Ok(()) => Status::Created,
+///         Err(_) => Status::Conflict,
+///     }
+/// }
+///
+/// #[delete("/plugins/<name>")]
+/// fn disable(name: &str, routing: Routing) -> Status {
+///     let base = format!("/plugins/{}", name);
+///     match routing.remove(|route| route.uri.base() == base).len() {
+///         0 => Status::NotFound,
+///         _ => Status::NoContent,
+///     }
+/// }
+///
+/// let rocket = rocket::build().mount("/", routes![enable, disable]);
+/// let client = Client::debug(rocket).unwrap();
+/// assert_eq!(client.get("/plugins/hello").dispatch().status(), Status::NotFound);
+///
+/// assert_eq!(client.post("/plugins/hello").dispatch().status(), Status::Created);
+/// assert_eq!(client.post("/plugins/hello").dispatch().status(), Status::Conflict);
+/// assert_eq!(client.get("/plugins/hello").dispatch().into_string().unwrap(), "plugin");
+///
+/// assert_eq!(client.delete("/plugins/hello").dispatch().status(), Status::NoContent);
+/// assert_eq!(client.get("/plugins/hello").dispatch().status(), Status::NotFound);
+/// ```
+///
+/// [`Rocket::routing()`]: crate::Rocket::routing()
+#[derive(Clone)]
+pub struct Routing {
+    router: Arc<SharedRouter>,
+    slash: TrailingSlash,
+}
+
+impl Routing {
+    pub(crate) fn new(router: Arc<SharedRouter>, slash: TrailingSlash) -> Routing {
+        Routing { router, slash }
+    }
+
+    /// Returns a copy of the current routes. The order is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::blocking::Client;
+    ///
+    /// #[get("/")]
+    /// fn index() -> &'static str { "index" }
+    ///
+    /// let client = Client::debug(rocket::build()).unwrap();
+    /// let routing = client.rocket().routing();
+    /// assert!(routing.routes().is_empty());
+    ///
+    /// routing.add(routes![index]).unwrap();
+    /// assert_eq!(routing.routes()[0].uri, "/");
+    /// ```
+    pub fn routes(&self) -> Vec<Route> {
+        self.router.get().routes().cloned().collect()
+    }
+
+    /// Adds `routes`, which are used as-is: their bases are unchanged.
+    ///
+    /// Returns the colliding pairs of routes, with no change made, if any of
+    /// `routes` collides with another route.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::blocking::Client;
+    ///
+    /// #[get("/")]
+    /// fn index() -> &'static str { "index" }
+    ///
+    /// let client = Client::debug(rocket::build()).unwrap();
+    /// let routing = client.rocket().routing();
+    /// assert!(routing.add(routes![index]).is_ok());
+    /// assert_eq!(routing.add(routes![index]).unwrap_err().len(), 1);
+    /// assert_eq!(client.get("/").dispatch().into_string().unwrap(), "index");
+    /// ```
+    pub fn add<R: Into<Vec<Route>>>(&self, routes: R) -> Result<(), Vec<(Route, Route)>> {
+        let (routes, mut collisions) = (routes.into(), vec![]);
+        self.router.update(|current| {
+            let mut router = current.rebuild(|_| true, routes);
+            match router.finalize(self.slash) {
+                Ok(()) => Some(router),
+                Err(e) => {
+                    collisions = e.routes;
+                    None
+                }
+            }
+        });
+
+        match collisions.is_empty() {
+            true => Ok(()),
+            false => Err(collisions),
+        }
+    }
+
+    /// Adds `routes` mounted at `base`, as with [`Rocket::mount()`].
+    ///
+    /// Returns the colliding pairs of routes, with no change made, if any of
+    /// the mounted `routes` collides with another route.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`Rocket::mount()`].
+    ///
+    /// [`Rocket::mount()`]: crate::Rocket::mount()
+    pub fn mount<'a, B, R>(&self, base: B, routes: R) -> Result<(), Vec<(Route, Route)>>
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let base = crate::rocket::parse_base("route", base);
+        let routes = routes.into().into_iter()
+            .map(|route| {
+                route.clone().map_base(|old| format!("{}{}", base, old))
+                    .unwrap_or_else(|e| {
+                        error!("malformed URI in route {}", route);
+                        error_!("{}", e);
+                        panic!("aborting due to invalid route URI");
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        self.add(routes)
+    }
+
+    /// Removes every route for which `f` returns `true`. Returns the removed
+    /// routes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Status;
+    /// use rocket::local::blocking::Client;
+    ///
+    /// #[get("/")]
+    /// fn index() -> &'static str { "index" }
+    ///
+    /// let client = Client::debug(rocket::build().mount("/", routes![index])).unwrap();
+    /// let removed = client.rocket().routing().remove(|r| r.name.as_deref() == Some("index"));
+    /// assert_eq!(removed.len(), 1);
+    /// assert_eq!(client.get("/").dispatch().status(), Status::NotFound);
+    /// ```
+    pub fn remove<F: FnMut(&Route) -> bool>(&self, mut f: F) -> Vec<Route> {
+        let mut removed = vec![];
+        self.router.update(|current| {
+            let router = current.rebuild(|route| match f(route) {
+                true => { removed.push(route.clone()); false }
+                false => true,
+            }, vec![]);
+
+            match removed.is_empty() {
+                true => None,
+                false => Some(router),
+            }
+        });
+
+        removed
+    }
+}
+
+impl fmt::Debug for Routing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Routing").field("slash", &self.slash).finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Routing {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(request.rocket().routing())
+    }
+}
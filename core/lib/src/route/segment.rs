@@ -1,28 +1,42 @@
 use crate::http::RawStr;
+use crate::route::Constraint;
 
 #[derive(Debug, Clone)]
 pub struct Segment {
     pub value: String,
     pub dynamic: bool,
     pub trailing: bool,
+    pub constraint: Option<Constraint>,
 }
 
 impl Segment {
     pub fn from(segment: &RawStr) -> Self {
+        let segment = segment.as_str();
         let mut value = segment;
         let mut dynamic = false;
         let mut trailing = false;
+        let mut constraint = None;
 
         if segment.starts_with('<') && segment.ends_with('>') {
             dynamic = true;
             value = &segment[1..(segment.len() - 1)];
 
+            if let Some(i) = value.find(':') {
+                let source = &value[(i + 1)..];
+                value = &value[..i];
+                constraint = match Constraint::parse(source) {
+                    Ok(constraint) => Some(constraint),
+                    Err(e) => panic!("invalid constraint in `{}`: {}", segment, e),
+                };
+            }
+
             if value.ends_with("..") {
                 trailing = true;
                 value = &value[..(value.len() - 2)];
+                assert!(constraint.is_none(), "trailing `{}` cannot be constrained", segment);
             }
         }
 
-        Segment { value: value.to_string(), dynamic, trailing }
+        Segment { value: value.to_string(), dynamic, trailing, constraint }
     }
 }
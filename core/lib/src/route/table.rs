@@ -0,0 +1,189 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{Request, Data, Route, Config};
+use crate::http::{Method, ContentType};
+use crate::route::{Handler, Outcome};
+
+/// A snapshot of the routes of a Rocket instance, for inspection and export.
+///
+/// A `RouteTable` is retrieved via [`Rocket::route_table()`] on an ignited or
+/// orbiting instance. It lists one [`RouteEntry`] per route, ordered by path,
+/// then by method, then by rank.
+///
+/// A `RouteTable` is machine-readable in two forms:
+///
+///   * It implements [`Serialize`](serde::Serialize), serializing as a
+///     sequence of [`RouteEntry`] maps, so it can be written out as JSON or any
+///     other `serde` format.
+///
+///   * Its [`Display`](fmt::Display) implementation writes a tab-separated
+///     table with a header line and one line per route, with `-` in empty
+///     columns, suitable for `diff`, `cut`, and friends.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[get("/hello/<name>?<lang>")]
+/// fn hello(name: &str, lang: Option<&str>) { /* .. */ }
+///
+/// # rocket::async_test(async {
+/// let rocket = rocket::build()
+///     .mount("/", routes![hello])
+///     .ignite().await
+///     .unwrap();
+///
+/// let table = rocket.route_table();
+/// let entry = &table.entries()[0];
+/// assert_eq!(entry.method, "GET");
+/// assert_eq!(entry.path, "/hello/<name>");
+/// assert_eq!(entry.query, &["<lang>"]);
+/// assert_eq!(entry.name.as_deref(), Some("hello"));
+/// assert!(entry.location.as_ref().unwrap().file.ends_with(".rs"));
+///
+/// let text = table.to_string();
+/// assert!(text.lines().nth(1).unwrap().starts_with("GET\t/hello/<name>\t<lang>\t"));
+/// # });
+/// ```
+///
+/// # Serving
+///
+/// [`RouteTable::routes()`] returns a route that serves the route table of the
+/// running instance as text, in the debug profile only:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::route::RouteTable;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().mount("/_routes", RouteTable::routes())
+/// }
+/// ```
+///
+/// [`Rocket::route_table()`]: crate::Rocket::route_table()
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct RouteTable {
+    entries: Vec<RouteEntry>,
+}
+
+/// An entry in a [`RouteTable`]: the properties of a single route.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RouteEntry {
+    /// The route's method, like `GET`.
+    pub method: &'static str,
+    /// The route's full path, including the mount point, like `/hello/<name>`.
+    pub path: String,
+    /// The route's query segments, like `<lang>` or `page=1`.
+    pub query: Vec<String>,
+    /// The route's rank.
+    pub rank: isize,
+    /// The media type the route matches against, if any.
+    pub format: Option<String>,
+    /// The host pattern the route matches against, if any.
+    pub host: Option<String>,
+    /// The route's name, typically the name of the handler function.
+    pub name: Option<String>,
+    /// The source location of the route's handler, if known.
+    pub location: Option<SourceLocation>,
+}
+
+/// The location of a route's handler in source code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceLocation {
+    /// The file, as reported by [`file!()`].
+    pub file: &'static str,
+    /// The line, starting at 1.
+    pub line: u32,
+    /// The column, starting at 1.
+    pub column: u32,
+}
+
+impl RouteTable {
+    /// Returns the table of `routes`.
+    pub(crate) fn new<'a, I: Iterator<Item = &'a Route>>(routes: I) -> RouteTable {
+        let mut entries: Vec<_> = routes.map(RouteEntry::from).collect();
+        entries.sort_by(|a, b| {
+            (&a.path, a.method, a.rank).cmp(&(&b.path, b.method, b.rank))
+        });
+
+        RouteTable { entries }
+    }
+
+    /// Returns the entries in the table.
+    pub fn entries(&self) -> &[RouteEntry] {
+        &self.entries
+    }
+
+    /// Returns a `GET /` route that responds with the route table of the
+    /// running instance as text when the active profile is the
+    /// [debug profile](Config::DEBUG_PROFILE). Otherwise, it forwards.
+    ///
+    /// See [Serving](#serving) for an example.
+    pub fn routes() -> Vec<Route> {
+        let mut route = Route::new(Method::Get, "/", TableHandler);
+        route.name = Some("route_table".into());
+        vec![route]
+    }
+}
+
+impl From<&Route> for RouteEntry {
+    fn from(route: &Route) -> Self {
+        RouteEntry {
+            method: route.method.as_str(),
+            path: route.uri.path().to_string(),
+            query: route.uri.query()
+                .map(|q| q.split('&').map(String::from).collect())
+                .unwrap_or_default(),
+            rank: route.rank,
+            format: route.format.as_ref().map(|f| f.to_string()),
+            host: route.host.as_ref().map(|h| h.to_string()),
+            name: route.name.as_ref().map(|n| n.to_string()),
+            location: route.location,
+        }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+impl fmt::Display for RouteTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn or_dash<T: fmt::Display>(value: Option<T>) -> String {
+            value.map_or_else(|| "-".into(), |v| v.to_string())
+        }
+
+        writeln!(f, "method\tpath\tquery\trank\tformat\thost\tname\tlocation")?;
+        for entry in &self.entries {
+            let query = Some(entry.query.join("&")).filter(|q| !q.is_empty());
+            writeln!(f, "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                entry.method, entry.path, or_dash(query), entry.rank,
+                or_dash(entry.format.as_ref()), or_dash(entry.host.as_ref()),
+                or_dash(entry.name.as_ref()), or_dash(entry.location.as_ref()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Serves the route table in the debug profile.
+#[derive(Clone)]
+struct TableHandler;
+
+#[crate::async_trait]
+impl Handler for TableHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        if req.rocket().config().profile != Config::DEBUG_PROFILE {
+            return Outcome::forward(data);
+        }
+
+        let table = req.rocket().route_table().to_string();
+        Outcome::from(req, (ContentType::Plain, table))
+    }
+}
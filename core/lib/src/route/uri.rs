@@ -93,6 +93,8 @@ pub(crate) struct Metadata {
     pub query_color: Option<Color>,
     /// Whether the path has a `<trailing..>` parameter.
     pub trailing_path: bool,
+    /// Whether the path ends with a `/`, as in `/foo/`.
+    pub trailing_slash: bool,
 }
 
 type Result<T, E = uri::Error<'static>> = std::result::Result<T, E>;
@@ -110,16 +112,23 @@ impl<'a> RouteUri<'a> {
 
         base.clear_query();
 
-        let unmounted_origin = Origin::parse_route(uri)
-            .map_err(|e| e.into_owned())?
-            .into_normalized()
-            .into_owned();
+        let unmounted_origin = Origin::parse_route(uri).map_err(|e| e.into_owned())?;
+        let path = unmounted_origin.path().as_str();
+        let trailing_slash = path.len() > 1 && path.ends_with('/');
+        let unmounted_origin = unmounted_origin.into_normalized().into_owned();
 
         let origin = Origin::parse_route(&format!("{}/{}", base, unmounted_origin))
             .map_err(|e| e.into_owned())?
             .into_normalized()
             .into_owned();
 
+        // Normalization strips a trailing slash. Put it back: it's significant
+        // under the strict and redirecting trailing slash policies.
+        let (unmounted_origin, origin) = match trailing_slash {
+            true => (with_trailing_slash(&unmounted_origin)?, with_trailing_slash(&origin)?),
+            false => (unmounted_origin, origin),
+        };
+
         let source = origin.to_string().into();
         let metadata = Metadata::from(&base, &origin);
 
@@ -246,6 +255,7 @@ impl Metadata {
             .collect::<Vec<_>>();
 
         let path_segs = origin.raw_path_segments()
+            .filter(|s| !s.is_empty())
             .map(Segment::from)
             .collect::<Vec<_>>();
 
@@ -277,14 +287,28 @@ impl Metadata {
         });
 
         let trailing_path = path_segs.last().map_or(false, |p| p.trailing);
+        let path = origin.path().as_str();
+        let trailing_slash = path.len() > 1 && path.ends_with('/');
 
         Metadata {
-            static_query_fields, path_color, query_color, trailing_path,
+            static_query_fields, path_color, query_color, trailing_path, trailing_slash,
             path_segs, query_segs, base_segs,
         }
     }
 }
 
+/// Appends a `/` to the path of the normalized `origin`.
+fn with_trailing_slash(origin: &Origin<'_>) -> Result<Origin<'static>> {
+    let uri = match origin.query() {
+        Some(query) => format!("{}/?{}", origin.path(), query),
+        None => format!("{}/", origin.path()),
+    };
+
+    Origin::parse_route(&uri)
+        .map(|origin| origin.into_owned())
+        .map_err(|e| e.into_owned())
+}
+
 impl<'a> std::ops::Deref for RouteUri<'a> {
     type Target = Origin<'a>;
 
@@ -1,8 +1,9 @@
 use crate::catcher::Catcher;
-use crate::route::{Route, Color};
+use crate::route::{Route, Color, Host, Segment, Constraint};
 
 use crate::http::{MediaType, Status};
 use crate::request::Request;
+use crate::config::TrailingSlash;
 
 pub trait Collide<T = Self> {
     fn collides_with(&self, other: &T) -> bool;
@@ -21,7 +22,7 @@ impl Collide for MediaType {
     }
 }
 
-fn paths_collide(route: &Route, other: &Route) -> bool {
+fn paths_collide(route: &Route, other: &Route, slash: TrailingSlash) -> bool {
     let a_segments = &route.uri.metadata.path_segs;
     let b_segments = &other.uri.metadata.path_segs;
     for (seg_a, seg_b) in a_segments.iter().zip(b_segments.iter()) {
@@ -29,18 +30,40 @@ fn paths_collide(route: &Route, other: &Route) -> bool {
             return true;
         }
 
-        if seg_a.dynamic || seg_b.dynamic {
-            continue;
-        }
-
-        if seg_a.value != seg_b.value {
+        if !seg_a.collides_with(seg_b) {
             return false;
         }
     }
 
     a_segments.get(b_segments.len()).map_or(false, |s| s.trailing)
         || b_segments.get(a_segments.len()).map_or(false, |s| s.trailing)
-        || a_segments.len() == b_segments.len()
+        || (a_segments.len() == b_segments.len() && slashes_collide(route, other, slash))
+}
+
+fn slashes_collide(route: &Route, other: &Route, slash: TrailingSlash) -> bool {
+    !slash.is_strict() || route.uri.metadata.trailing_slash == other.uri.metadata.trailing_slash
+}
+
+impl Collide for Constraint {
+    fn collides_with(&self, other: &Self) -> bool {
+        self.overlaps(other)
+    }
+}
+
+impl Collide for Segment {
+    /// Two non-trailing segments collide if some request segment matches both.
+    /// A dynamic segment matches any value satisfying its constraint, if any.
+    fn collides_with(&self, other: &Self) -> bool {
+        match (self.dynamic, other.dynamic) {
+            (false, false) => self.value == other.value,
+            (true, false) => self.constraint.as_ref().map_or(true, |c| c.matches(&other.value)),
+            (false, true) => other.constraint.as_ref().map_or(true, |c| c.matches(&self.value)),
+            (true, true) => match (self.constraint.as_ref(), other.constraint.as_ref()) {
+                (Some(a), Some(b)) => a.collides_with(b),
+                _ => true
+            }
+        }
+    }
 }
 
 fn formats_collide(route: &Route, other: &Route) -> bool {
@@ -61,6 +84,20 @@ fn formats_collide(route: &Route, other: &Route) -> bool {
     }
 }
 
+impl Collide for Host {
+    fn collides_with(&self, other: &Self) -> bool {
+        self.overlaps(other)
+    }
+}
+
+fn hosts_collide(route: &Route, other: &Route) -> bool {
+    // A route without a host matches requests to any host.
+    match (route.host.as_ref(), other.host.as_ref()) {
+        (Some(a), Some(b)) => a.collides_with(b),
+        _ => true
+    }
+}
+
 impl Collide for Route {
     /// Determines if two routes can match against some request. That is, if two
     /// routes `collide`, there exists a request that can match against both
@@ -74,11 +111,27 @@ impl Collide for Route {
     ///
     /// Because query parsing is lenient, and dynamic query parameters can be
     /// missing, queries do not impact whether two routes collide.
+    ///
+    /// Routes with hosts collide only if some host matches both. Routes
+    /// without a host match every host.
+    ///
+    /// Trailing slashes are ignored, as under [`TrailingSlash::MatchBoth`].
+    /// See [`Route::collides_under()`] to check under another policy.
     fn collides_with(&self, other: &Route) -> bool {
+        self.collides_under(other, TrailingSlash::MatchBoth)
+    }
+}
+
+impl Route {
+    /// Like [`Route::collides_with()`], but with trailing slashes matched
+    /// according to `slash`. Under a strict policy, routes that differ only in
+    /// their trailing slash do not collide.
+    pub(crate) fn collides_under(&self, other: &Route, slash: TrailingSlash) -> bool {
         self.method == other.method
             && self.rank == other.rank
-            && paths_collide(self, other)
+            && paths_collide(self, other, slash)
             && formats_collide(self, other)
+            && hosts_collide(self, other)
     }
 }
 
@@ -93,22 +146,41 @@ impl Route {
     ///     - If route doesn't specify format, it gets requests for any format.
     ///   * All static components in the route's path match the corresponding
     ///     components in the same position in the incoming request.
+    ///   * All constrained dynamic components in the route's path are
+    ///     satisfied by the corresponding components in the incoming request.
     ///   * All static components in the route's query string are also in the
     ///     request query string, though in any position. If there is no query
     ///     in the route, requests with/without queries match.
+    ///   * The route's host (if any) matches the request's host.
     pub(crate) fn matches(&self, req: &Request<'_>) -> bool {
         self.method == req.method()
-            && paths_match(self, req)
-            && queries_match(self, req)
+            && self.matches_target(req)
             && formats_match(self, req)
     }
+
+    /// Determines if this route's path, query, and host match those of `req`,
+    /// irrespective of method and format.
+    pub(crate) fn matches_target(&self, req: &Request<'_>) -> bool {
+        paths_match(self, req)
+            && queries_match(self, req)
+            && host_matches(self.host.as_ref(), req)
+    }
 }
 
 fn paths_match(route: &Route, req: &Request<'_>) -> bool {
-    let route_segments = &route.uri.metadata.path_segs;
+    let metadata = &route.uri.metadata;
+    let route_segments = &metadata.path_segs;
     let req_segments = req.uri().path_segments();
 
-    if route.uri.metadata.trailing_path {
+    // A `<trailing..>` parameter absorbs any trailing slash.
+    if !metadata.trailing_path && req.rocket().config().trailing_slash.is_strict() {
+        let path = req.uri().path();
+        if metadata.trailing_slash != (path.len() > 1 && path.ends_with('/')) {
+            return false;
+        }
+    }
+
+    if metadata.trailing_path {
         // The last route segment can be trailing, which is allowed to be empty.
         // So we can have one more segment in `route` than in `req` and match.
         // ok if: req_segments.len() >= route_segments.len() - 1
@@ -119,7 +191,8 @@ fn paths_match(route: &Route, req: &Request<'_>) -> bool {
         return false;
     }
 
-    if route.uri.metadata.path_color == Color::Wild {
+    let constrained = route_segments.iter().any(|s| s.constraint.is_some());
+    if metadata.path_color == Color::Wild && !constrained {
         return true;
     }
 
@@ -128,7 +201,12 @@ fn paths_match(route: &Route, req: &Request<'_>) -> bool {
             return true;
         }
 
-        if !(route_seg.dynamic || route_seg.value == req_seg) {
+        let matches = match route_seg.dynamic {
+            true => route_seg.constraint.as_ref().map_or(true, |c| c.matches(req_seg)),
+            false => route_seg.value == req_seg,
+        };
+
+        if !matches {
             return false;
         }
     }
@@ -154,6 +232,13 @@ fn queries_match(route: &Route, req: &Request<'_>) -> bool {
     true
 }
 
+fn host_matches(host: Option<&Host>, req: &Request<'_>) -> bool {
+    match host {
+        Some(host) => req.host().map_or(false, |h| host.matches(h)),
+        None => true
+    }
+}
+
 fn formats_match(route: &Route, request: &Request<'_>) -> bool {
     if !route.method.supports_payload() {
         route.format.as_ref()
@@ -179,9 +264,20 @@ impl Collide for Catcher {
     ///
     ///  * Have the same base.
     ///  * Have the same status code or are both defaults.
+    ///  * Have overlapping hosts or are both without a host.
+    ///
+    /// A catcher with a host is preferred to one without, so the two never
+    /// collide.
     fn collides_with(&self, other: &Self) -> bool {
+        let hosts_collide = match (self.host.as_ref(), other.host.as_ref()) {
+            (Some(a), Some(b)) => a.collides_with(b),
+            (None, None) => true,
+            _ => false,
+        };
+
         self.code == other.code
             && self.base.path_segments().eq(other.base.path_segments())
+            && hosts_collide
     }
 }
 
@@ -191,9 +287,11 @@ impl Catcher {
     ///
     ///  * It is a default catcher _or_ has a code of `status`.
     ///  * Its base is a prefix of the normalized/decoded `req.path()`.
+    ///  * Its host (if any) matches the request's host.
     pub(crate) fn matches(&self, status: Status, req: &Request<'_>) -> bool {
         self.code.map_or(true, |code| code == status.code)
             && self.base.path_segments().prefix_of(req.uri().path_segments())
+            && host_matches(self.host.as_ref(), req)
     }
 }
 
@@ -224,7 +322,7 @@ mod tests {
     fn s_s_collide(a: &'static str, b: &'static str) -> bool {
         let a = Route::new(Get, a, dummy_handler);
         let b = Route::new(Get, b, dummy_handler);
-        paths_collide(&a, &b)
+        paths_collide(&a, &b, TrailingSlash::MatchBoth)
     }
 
     #[test]
@@ -280,6 +378,46 @@ mod tests {
         assert!(unranked_collide("/<a..>", "//////"));
     }
 
+    #[test]
+    fn constrained_param_collisions() {
+        assert!(unranked_collide("/<a:u64>", "/<b>"));
+        assert!(unranked_collide("/<a:u64>", "/<b:u8>"));
+        assert!(unranked_collide("/<a:u64>", "/10"));
+        assert!(unranked_collide("/<a:[a-z]+>", "/<b:[a-c]*>"));
+        assert!(unranked_collide("/<a:[a-z]+>", "/abc"));
+        assert!(unranked_collide("/<a:[a-z]+>/<b..>", "/<c:[a-z]+>/d"));
+
+        assert!(!unranked_collide("/<a:u64>", "/<b:[a-z]+>"));
+        assert!(!unranked_collide("/<a:u64>", "/abc"));
+        assert!(!unranked_collide("/<a:bool>", "/<b:i32>"));
+        assert!(!unranked_collide("/<a:[a-z]+>", "/ABC"));
+        assert!(!unranked_collide("/x/<a:[0-9]+>", "/x/<b:[a-z-]+>"));
+        assert!(!unranked_collide("/<a:user-[0-9]+>/x", "/<b:team-[0-9]+>/x"));
+    }
+
+    #[test]
+    fn trailing_slash_collisions() {
+        fn collide(a: &'static str, b: &'static str, slash: TrailingSlash) -> bool {
+            let route_a = Route::ranked(0, Get, a, dummy_handler);
+            route_a.collides_under(&Route::ranked(0, Get, b, dummy_handler), slash)
+        }
+
+        for &slash in &[TrailingSlash::Strict, TrailingSlash::Redirect] {
+            assert!(collide("/a/", "/a/", slash));
+            assert!(collide("/<a>/", "/b/", slash));
+            assert!(collide("/a/<b..>", "/a/b/", slash));
+            assert!(collide("/a/<b..>", "/a/b", slash));
+            assert!(collide("/", "/<a..>", slash));
+
+            assert!(!collide("/a", "/a/", slash));
+            assert!(!collide("/<a>", "/b/", slash));
+            assert!(!collide("/a/<b>/", "/a/<c>", slash));
+        }
+
+        assert!(collide("/a", "/a/", TrailingSlash::MatchBoth));
+        assert!(collide("/a/<b>/", "/a/<c>", TrailingSlash::MatchBoth));
+    }
+
     #[test]
     fn query_collisions() {
         assert!(unranked_collide("/?<a>", "/?<a>"));
@@ -565,6 +703,21 @@ mod tests {
         assert!(!req_route_path_match("/a/b", "/a/b?<a>&b&<rest..>"));
     }
 
+    #[test]
+    fn test_req_route_constrained_match() {
+        assert!(req_route_path_match("/10", "/<id:u64>"));
+        assert!(req_route_path_match("/a/255", "/a/<id:u8>"));
+        assert!(req_route_path_match("/bob", "/<name:[a-z]+>"));
+        assert!(req_route_path_match("/bob/x/y", "/<name:[a-z]+>/<rest..>"));
+        assert!(req_route_path_match("/true?a=b", "/<flag:bool>?<a>"));
+
+        assert!(!req_route_path_match("/bob", "/<id:u64>"));
+        assert!(!req_route_path_match("/a/256", "/a/<id:u8>"));
+        assert!(!req_route_path_match("/Bob", "/<name:[a-z]+>"));
+        assert!(!req_route_path_match("/bob1", "/<name:[a-z]+>"));
+        assert!(!req_route_path_match("/1/x/y", "/<name:[a-z]+>/<rest..>"));
+    }
+
 
     fn catchers_collide<A, B>(a: A, ap: &str, b: B, bp: &str) -> bool
         where A: Into<Option<u16>>, B: Into<Option<u16>>
@@ -2,6 +2,10 @@
 
 mod router;
 mod collider;
+mod tree;
+mod shared;
 
 pub(crate) use router::*;
 pub(crate) use collider::*;
+pub(crate) use tree::Tree;
+pub(crate) use shared::SharedRouter;
@@ -4,14 +4,22 @@ use crate::request::Request;
 use crate::http::{Method, Status};
 
 use crate::{Route, Catcher};
-use crate::router::Collide;
+use crate::config::TrailingSlash;
+use crate::router::{Collide, Tree};
 
 #[derive(Debug, Default)]
 pub(crate) struct Router {
     routes: HashMap<Method, Vec<Route>>,
+    trees: HashMap<Method, Tree>,
     catchers: HashMap<Option<u16>, Vec<Catcher>>,
+    indexed: bool,
 }
 
+/// Identifies a route in a [`Router`]: the route's method and its index among
+/// the routes with that method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteId(Method, usize);
+
 #[derive(Debug)]
 pub struct Collisions {
     pub routes: Vec<(Route, Route)>,
@@ -23,16 +31,50 @@ impl Router {
         Self::default()
     }
 
-    pub fn add_route(&mut self, route: Route) {
-        let routes = self.routes.entry(route.method).or_default();
-        routes.push(route);
-        routes.sort_by_key(|r| r.rank);
+    /// Adds `route` to the router. The route isn't routed to until the router
+    /// is [finalized](Router::finalize()).
+    pub fn add_route(&mut self, mut route: Route) {
+        // Limits are merged with those of the router's configuration on use.
+        route.merged_limits = state::Storage::new();
+        self.routes.entry(route.method).or_default().push(route);
+        self.indexed = false;
     }
 
     pub fn add_catcher(&mut self, catcher: Catcher) {
         let catchers = self.catchers.entry(catcher.code).or_default();
         catchers.push(catcher);
-        catchers.sort_by(|a, b| b.base.path_segments().len().cmp(&a.base.path_segments().len()))
+        catchers.sort_by(|a, b| catcher_precedence(b).cmp(&catcher_precedence(a)))
+    }
+
+    /// Returns a new router with the same catchers as `self`, the routes in
+    /// `self` for which `keep` returns `true`, and `added`.
+    pub fn rebuild<F>(&self, mut keep: F, added: Vec<Route>) -> Router
+        where F: FnMut(&Route) -> bool
+    {
+        let mut router = Router::new();
+        router.catchers = self.catchers.clone();
+        for route in self.routes().filter(|r| keep(r)).cloned().chain(added) {
+            router.add_route(route);
+        }
+
+        router.index();
+        router
+    }
+
+    /// Sorts the routes by rank and builds the tree for each method, unless
+    /// that's already been done since the last route was added.
+    fn index(&mut self) {
+        if self.indexed {
+            return;
+        }
+
+        self.trees.clear();
+        for (method, routes) in self.routes.iter_mut() {
+            routes.sort_by_key(|r| r.rank);
+            self.trees.insert(*method, Tree::new(routes.iter()));
+        }
+
+        self.indexed = true;
     }
 
     #[inline]
@@ -45,16 +87,73 @@ impl Router {
         self.catchers.values().flat_map(|v| v.iter())
     }
 
+    /// Returns the route identified by `id`, if there is one.
+    #[inline]
+    pub fn get(&self, id: RouteId) -> Option<&Route> {
+        self.routes.get(&id.0).and_then(|routes| routes.get(id.1))
+    }
+
     pub fn route<'r, 'a: 'r>(
         &'a self,
         req: &'r Request<'r>
+    ) -> impl Iterator<Item = (RouteId, &'a Route)> + 'r {
+        // Note that routes are presorted by ascending rank by `index()`. The
+        // tree yields indices of path matches in ascending order, so the
+        // matches are visited in rank order. The tree ignores queries and
+        // formats; `matches()` checks those.
+        let method = req.method();
+        self.routes.get(&method)
+            .zip(self.trees.get(&method))
+            .into_iter()
+            .flat_map(move |(routes, tree)| {
+                tree.matches(req.uri().path_segments())
+                    .into_iter()
+                    .map(move |i| (RouteId(method, i), &routes[i]))
+                    .filter(move |(_, r)| r.matches(req))
+            })
+    }
+
+    /// Returns the routes that match `req` in the same order as
+    /// [`Router::route()`] but without consulting the trees: every route with
+    /// `req`'s method is checked in turn.
+    #[cfg(any(test, feature = "__bench"))]
+    pub fn route_linear<'r, 'a: 'r>(
+        &'a self,
+        req: &'r Request<'r>
     ) -> impl Iterator<Item = &'a Route> + 'r {
-        // Note that routes are presorted by ascending rank on each `add`.
         self.routes.get(&req.method())
             .into_iter()
             .flat_map(move |routes| routes.iter().filter(move |r| r.matches(req)))
     }
 
+    /// Returns the methods, in a stable order, of the routes whose path,
+    /// query, and host match `req`, irrespective of `req`'s method. `HEAD` is
+    /// included whenever `GET` is since Rocket handles `HEAD` automatically.
+    pub fn methods(&self, req: &Request<'_>) -> Vec<Method> {
+        let mut methods: Vec<Method> = self.routes.iter()
+            .filter(|(_, routes)| routes.iter().any(|r| r.matches_target(req)))
+            .map(|(method, _)| *method)
+            .collect();
+
+        if methods.contains(&Method::Get) && !methods.contains(&Method::Head) {
+            methods.push(Method::Head);
+        }
+
+        methods.sort_by_key(|m| m.as_str());
+        methods
+    }
+
+    /// Returns the methods `req` could have had to be routed, or nothing if
+    /// a route with `req`'s own method matches, as the request is then simply
+    /// unhandled. See [`Router::methods()`].
+    pub fn allowed_methods(&self, req: &Request<'_>) -> Vec<Method> {
+        let methods = self.methods(req);
+        match methods.contains(&req.method()) {
+            true => vec![],
+            false => methods,
+        }
+    }
+
     // For many catchers, using aho-corasick or similar should be much faster.
     pub fn catch<'r>(&self, status: Status, req: &'r Request<'r>) -> Option<&Catcher> {
         // Note that catchers are presorted by descending base length.
@@ -68,7 +167,7 @@ impl Router {
             (None, None) => None,
             (None, c@Some(_)) | (c@Some(_), None) => c,
             (Some(a), Some(b)) => {
-                if b.base.path_segments().len() > a.base.path_segments().len() {
+                if catcher_precedence(b) > catcher_precedence(a) {
                     Some(b)
                 } else {
                     Some(a)
@@ -77,21 +176,34 @@ impl Router {
         }
     }
 
-    fn collisions<'a, I, T>(&self, items: I) -> impl Iterator<Item = (T, T)> + 'a
-        where I: Iterator<Item = &'a T> + Clone + 'a, T: Collide + Clone + 'a,
+    fn collisions<'a, I, T, F>(&self, items: I, f: F) -> impl Iterator<Item = (T, T)> + 'a
+        where I: Iterator<Item = &'a T> + Clone + 'a,
+              T: Clone + 'a,
+              F: Fn(&T, &T) -> bool + Copy + 'a,
     {
         items.clone().enumerate()
             .flat_map(move |(i, a)| {
                 items.clone()
                     .skip(i + 1)
-                    .filter(move |b| a.collides_with(b))
+                    .filter(move |b| f(a, *b))
                     .map(move |b| (a.clone(), b.clone()))
             })
     }
 
-    pub fn finalize(&self) -> Result<(), Collisions> {
-        let routes: Vec<_> = self.collisions(self.routes()).collect();
-        let catchers: Vec<_> = self.collisions(self.catchers()).collect();
+    /// Prepares the added routes for routing and checks for route and catcher
+    /// collisions, with trailing slashes in route paths matched according to
+    /// `slash`.
+    pub fn finalize(&mut self, slash: TrailingSlash) -> Result<(), Collisions> {
+        self.index();
+        self.check(slash)
+    }
+
+    fn check(&self, slash: TrailingSlash) -> Result<(), Collisions> {
+        let routes: Vec<_> = self.collisions(self.routes(), move |a, b| a.collides_under(b, slash))
+            .collect();
+
+        let catchers: Vec<_> = self.collisions(self.catchers(), |a, b| a.collides_with(b))
+            .collect();
 
         if !routes.is_empty() || !catchers.is_empty() {
             return Err(Collisions { routes, catchers })
@@ -101,6 +213,12 @@ impl Router {
     }
 }
 
+/// Catchers with longer bases take precedence. For equal bases, a catcher with
+/// a host takes precedence over one without.
+fn catcher_precedence(catcher: &Catcher) -> (usize, bool) {
+    (catcher.base.path_segments().len(), catcher.host.is_some())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -111,7 +229,7 @@ mod test {
 
     impl Router {
         fn has_collisions(&self) -> bool {
-            self.finalize().is_err()
+            self.check(TrailingSlash::MatchBoth).is_err()
         }
     }
 
@@ -122,6 +240,7 @@ mod test {
             router.add_route(route);
         }
 
+        router.index();
         router
     }
 
@@ -132,6 +251,7 @@ mod test {
             router.add_route(route);
         }
 
+        router.index();
         router
     }
 
@@ -142,6 +262,7 @@ mod test {
             router.add_route(route);
         }
 
+        router.index();
         router
     }
 
@@ -289,7 +410,7 @@ mod test {
     fn matches<'a>(router: &'a Router, method: Method, uri: &'a str) -> Vec<&'a Route> {
         let client = Client::debug_with(vec![]).expect("client");
         let request = client.req(method, Origin::parse(uri).unwrap());
-        router.route(&request).collect()
+        router.route(&request).map(|(_, route)| route).collect()
     }
 
     fn route<'a>(router: &'a Router, method: Method, uri: &'a str) -> Option<&'a Route> {
@@ -317,6 +438,7 @@ mod test {
         router.add_route(Route::new(Put, "/hello", dummy_handler));
         router.add_route(Route::new(Post, "/hello", dummy_handler));
         router.add_route(Route::new(Delete, "/hello", dummy_handler));
+        router.index();
         assert!(route(&router, Put, "/hello").is_some());
         assert!(route(&router, Post, "/hello").is_some());
         assert!(route(&router, Delete, "/hello").is_some());
@@ -369,6 +491,40 @@ mod test {
         assert!(route(&router, Get, "/prefi/").is_none());
     }
 
+    fn linear_matches<'a>(router: &'a Router, method: Method, uri: &'a str) -> Vec<&'a Route> {
+        let client = Client::debug_with(vec![]).expect("client");
+        let request = client.req(method, Origin::parse(uri).unwrap());
+        router.route_linear(&request).collect()
+    }
+
+    #[test]
+    fn test_tree_agrees_with_linear_scan() {
+        let router = router_with_routes(&[
+            "/", "/<a..>", "/a", "/a/", "/a/b", "/a/<b>", "/<a>/b", "/<a>/<b>",
+            "/a/b/<c..>", "/a/<b>/c", "/a/<b>/<c..>", "/a?b", "/a?<b>", "/<a>?b=c",
+            "/foo/<_..>", "/foo/bar/baz", "/<a>/<b>/<c>", "/a/b/c?<d>&e",
+        ]);
+
+        let requests = &[
+            "/", "//", "/a", "/a/", "/b", "/a/b", "/a/c", "/b/b", "/a/b/c",
+            "/a/b/c/d", "/a/c/c", "/a?b", "/a?c", "/b?b=c", "/foo", "/foo/bar",
+            "/foo/bar/baz", "/foo/bar/baz/qux", "/a/b/c?e", "/a/b/c?d=1&e",
+            "/x/y/z", "/x/y/z/w",
+        ];
+
+        for uri in requests {
+            let linear: Vec<_> = linear_matches(&router, Get, uri).iter()
+                .map(|r| r.uri.to_string())
+                .collect();
+
+            let tree: Vec<_> = matches(&router, Get, uri).iter()
+                .map(|r| r.uri.to_string())
+                .collect();
+
+            assert_eq!(linear, tree, "mismatch routing {}", uri);
+        }
+    }
+
     macro_rules! assert_ranked_match {
         ($routes:expr, $to:expr => $want:expr) => ({
             let router = router_with_routes($routes);
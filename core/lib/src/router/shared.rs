@@ -0,0 +1,53 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::router::Router;
+
+/// A [`Router`] that can be replaced while it is in use.
+///
+/// Users of the router take a snapshot of the current router with
+/// [`SharedRouter::get()`]. Replacing the router never affects existing
+/// snapshots; a replaced router is dropped along with its last snapshot.
+pub(crate) struct SharedRouter {
+    launched: Arc<Router>,
+    current: RwLock<Arc<Router>>,
+    updating: Mutex<()>,
+}
+
+impl SharedRouter {
+    pub fn new(router: Router) -> Self {
+        let launched = Arc::new(router);
+        SharedRouter {
+            current: RwLock::new(launched.clone()),
+            launched,
+            updating: Mutex::new(()),
+        }
+    }
+
+    /// Returns a snapshot of the current router.
+    pub fn get(&self) -> Arc<Router> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Returns the router as it was at launch.
+    pub fn launched(&self) -> &Router {
+        &self.launched
+    }
+
+    /// Calls `f` with the current router and, if `f` returns a new router,
+    /// atomically makes it current. Updates are serialized.
+    pub fn update<F>(&self, f: F)
+        where F: FnOnce(&Router) -> Option<Router>
+    {
+        let _updating = self.updating.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(router) = f(&self.get()) {
+            *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(router);
+        }
+    }
+}
+
+impl fmt::Debug for SharedRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use crate::route::Route;
+use crate::http::uri::Segments;
+
+/// A segment trie over the paths of a set of routes.
+///
+/// Each route is stored by its index into a rank-sorted `Vec<Route>`. Walking
+/// the tree with the segments of a request path yields the indices of exactly
+/// the routes whose _path_ matches the request. Sorting those indices thus
+/// recovers rank order. Queries and formats are not considered by the tree.
+#[derive(Debug, Default)]
+pub(crate) struct Tree {
+    root: Node,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    /// Routes whose last path segment is the one leading to this node.
+    terminal: Vec<usize>,
+    /// Routes with a `<trailing..>` segment at this node's depth. These match
+    /// every path that reaches this node, regardless of what follows.
+    trailing: Vec<usize>,
+    /// Children reached by a static segment, keyed by the segment's value.
+    statics: HashMap<String, Node>,
+    /// The child reached by a dynamic `<param>` segment, if any.
+    dynamic: Option<Box<Node>>,
+}
+
+impl Tree {
+    /// Builds a tree from `routes`, which must be sorted by ascending rank.
+    pub fn new<'a, I>(routes: I) -> Tree
+        where I: IntoIterator<Item = &'a Route>
+    {
+        let mut tree = Tree::default();
+        for (i, route) in routes.into_iter().enumerate() {
+            tree.insert(i, route);
+        }
+
+        tree
+    }
+
+    fn insert(&mut self, index: usize, route: &Route) {
+        let mut node = &mut self.root;
+        for segment in &route.uri.metadata.path_segs {
+            if segment.trailing {
+                node.trailing.push(index);
+                return;
+            }
+
+            node = match segment.dynamic {
+                true => node.dynamic.get_or_insert_with(Default::default),
+                false => node.statics.entry(segment.value.clone()).or_default(),
+            };
+        }
+
+        node.terminal.push(index);
+    }
+
+    /// Returns the indices, in ascending order, of every route whose path
+    /// matches `segments`.
+    pub fn matches(&self, segments: Segments<'_>) -> Vec<usize> {
+        let mut indices = vec![];
+        self.root.collect(segments, &mut indices);
+        indices.sort_unstable();
+        indices
+    }
+}
+
+impl Node {
+    fn collect(&self, segments: Segments<'_>, indices: &mut Vec<usize>) {
+        indices.extend_from_slice(&self.trailing);
+
+        let segment = match segments.get(0) {
+            Some(segment) => segment,
+            None => return indices.extend_from_slice(&self.terminal),
+        };
+
+        if let Some(child) = self.statics.get(segment) {
+            child.collect(segments.skip(1), indices);
+        }
+
+        if let Some(ref child) = self.dynamic {
+            child.collect(segments.skip(1), indices);
+        }
+    }
+}
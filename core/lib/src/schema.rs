@@ -0,0 +1,526 @@
+//! Schema descriptions of route parameters, request bodies, and responses.
+//!
+//! This module provides the hooks from which API documentation, such as an
+//! OpenAPI document, can be generated. A type describes the shape of its
+//! values by implementing [`Describe`]. Implementations are provided for
+//! primitive types, strings, collections, and Rocket's wrapper types, and can
+//! be derived with `#[derive(Describe)]`.
+//!
+//! The route attributes record an [`Operation`] for each route, retrieved
+//! with [`Operation::of()`]: the schemas of the route's path and query
+//! parameters, its data guard, and its return type, as far as those types
+//! implement `Describe`. Types that don't implement `Describe` are simply
+//! omitted.
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::form::Form;
+//! use rocket::schema::{Describe, Operation, Location};
+//!
+//! #[derive(FromForm, Describe)]
+//! struct Task<'r> {
+//!     description: &'r str,
+//!     #[field(name = "done")]
+//!     complete: Option<bool>,
+//! }
+//!
+//! #[post("/tasks/<list>", data = "<task>")]
+//! fn new(list: usize, task: Form<Task<'_>>) -> String {
+//!     # unimplemented!()
+//! }
+//!
+//! let route = routes![new].remove(0);
+//! let operation = Operation::of(&route).unwrap();
+//! assert_eq!(operation.params[0].name, "list");
+//! assert_eq!(operation.params[0].location, Location::Path);
+//! assert_eq!(operation.params[0].schema.as_ref().unwrap(), &usize::schema());
+//!
+//! let body = operation.body.as_ref().unwrap();
+//! assert_eq!(body.schema, Task::schema());
+//! assert_eq!(body.schema.required, &["description"]);
+//! assert!(body.schema.properties.contains_key("done"));
+//! ```
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::Route;
+use crate::http::MediaType;
+
+#[doc(inline)]
+pub use rocket_codegen::Describe;
+
+/// A description of the shape of a value: an OpenAPI 3 Schema Object.
+///
+/// Only the subset of the Schema Object needed to describe Rust types is
+/// supported. A `Schema` serializes as the corresponding JSON object.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::schema::Schema;
+///
+/// let point = Schema::object()
+///     .property("x", Schema::integer("int64"))
+///     .property("y", Schema::integer("int64"))
+///     .property("label", Schema::string().nullable());
+///
+/// assert_eq!(point.required, &["x", "y"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Schema {
+    /// The JSON type: `string`, `integer`, `number`, `boolean`, `array`, or
+    /// `object`. `None` admits values of any type.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<&'static str>,
+    /// The format of the type, such as `int64` or `binary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<&'static str>,
+    /// The schema of array items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Schema>>,
+    /// The schemas of the properties of an object, in declaration order.
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub properties: IndexMap<String, Schema>,
+    /// The names of the properties which must be present.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+    /// The permissible values, if the value is one of a fixed set of strings.
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    /// Alternative schemas, exactly one of which the value matches.
+    #[serde(rename = "oneOf", skip_serializing_if = "Vec::is_empty")]
+    pub one_of: Vec<Schema>,
+    /// Whether the value can be missing or `null`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub nullable: bool,
+}
+
+impl Schema {
+    /// A schema admitting any value.
+    pub fn any() -> Schema {
+        Schema::default()
+    }
+
+    /// A schema admitting strings.
+    pub fn string() -> Schema {
+        Schema { kind: Some("string"), ..Schema::default() }
+    }
+
+    /// A schema admitting integers of the given `format`, i.e, `int32` or
+    /// `int64`.
+    pub fn integer(format: &'static str) -> Schema {
+        Schema { kind: Some("integer"), format: Some(format), ..Schema::default() }
+    }
+
+    /// A schema admitting integers of any size. Used for integers which don't
+    /// fit in an `int64`.
+    pub fn integer_any() -> Schema {
+        Schema { kind: Some("integer"), ..Schema::default() }
+    }
+
+    /// A schema admitting numbers of the given `format`, i.e, `float` or
+    /// `double`.
+    pub fn number(format: &'static str) -> Schema {
+        Schema { kind: Some("number"), format: Some(format), ..Schema::default() }
+    }
+
+    /// A schema admitting booleans.
+    pub fn boolean() -> Schema {
+        Schema { kind: Some("boolean"), ..Schema::default() }
+    }
+
+    /// A schema admitting arrays with items admitted by `items`.
+    pub fn array(items: Schema) -> Schema {
+        Schema { kind: Some("array"), items: Some(Box::new(items)), ..Schema::default() }
+    }
+
+    /// A schema admitting objects. Properties are added with
+    /// [`Schema::property()`].
+    pub fn object() -> Schema {
+        Schema { kind: Some("object"), ..Schema::default() }
+    }
+
+    /// A schema admitting strings in `values`.
+    pub fn one_of_strings<I, S>(values: I) -> Schema
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let values = values.into_iter().map(|v| v.as_ref().to_string()).collect();
+        Schema { values, ..Schema::string() }
+    }
+
+    /// Adds the property `name` admitting values admitted by `schema`. The
+    /// property is required unless `schema` is nullable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::schema::Schema;
+    ///
+    /// let user = Schema::object()
+    ///     .property("name", Schema::string())
+    ///     .property("age", Schema::integer("int32").nullable());
+    ///
+    /// assert_eq!(user.properties.len(), 2);
+    /// assert_eq!(user.required, &["name"]);
+    /// ```
+    pub fn property<N: Into<String>>(mut self, name: N, schema: Schema) -> Schema {
+        let name = name.into();
+        if !schema.nullable {
+            self.required.push(name.clone());
+        }
+
+        self.properties.insert(name, schema);
+        self
+    }
+
+    /// Sets the format to `format`.
+    pub fn format(mut self, format: &'static str) -> Schema {
+        self.format = Some(format);
+        self
+    }
+
+    /// Marks the schema as admitting missing or `null` values.
+    pub fn nullable(mut self) -> Schema {
+        self.nullable = true;
+        self
+    }
+}
+
+/// Trait implemented by types that can describe the shape of their values.
+///
+/// A `Describe` implementation is used to document a type wherever it appears
+/// in a route: as a path or query parameter, as a data guard, or as a return
+/// type. See the [module level docs](self) for details.
+///
+/// # Deriving
+///
+/// `Describe` can be derived for structs and enums:
+///
+///   * A struct with named fields is an object with a property for each
+///     field, named as in `#[field(name = "...")]`, if present, or after the
+///     field otherwise. Properties are required unless they're nullable.
+///   * A tuple struct is described as its first field. So is a struct with a
+///     field marked `#[schema(body)]`, as in a derived [`Responder`] with
+///     header fields.
+///   * A fieldless enum is a string with the values of the variants, named
+///     as in `#[field(value = "...")]`, if present, or after the variant
+///     otherwise.
+///   * An enum with fields is one of the descriptions of each variant's first
+///     field, as in a derived [`Responder`].
+///
+/// Names set via serde's `rename` and `rename_all` attributes take precedence
+/// over those set via `#[field]`, and fields and variants marked
+/// `#[serde(skip)]` are omitted.
+///
+/// The derive is opt-in: types that don't implement `Describe` are simply
+/// left undocumented.
+///
+/// [`Responder`]: crate::response::Responder
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::ContentType;
+/// use rocket::schema::{Describe, Schema};
+///
+/// #[derive(FromFormField, Describe)]
+/// enum Color { Red, Green, #[field(value = "b")] Blue }
+///
+/// #[derive(Responder, Describe)]
+/// struct Swatch {
+///     #[schema(body)]
+///     color: String,
+///     kind: ContentType,
+/// }
+///
+/// assert_eq!(Color::schema(), Schema::one_of_strings(&["Red", "Green", "b"]));
+/// assert_eq!(Swatch::schema(), Schema::string());
+/// ```
+pub trait Describe {
+    /// Returns the schema of values of this type.
+    fn schema() -> Schema;
+
+    /// Returns the media type of a request or response body of this type, if
+    /// it is known. The default implementation returns `None`.
+    fn media_type() -> Option<MediaType> {
+        None
+    }
+
+    /// The schema of a `Vec<Self>`. Overridden by `u8` to describe bytes.
+    #[doc(hidden)]
+    fn vec_schema() -> Schema where Self: Sized {
+        Schema::array(Self::schema())
+    }
+
+    /// The media type of a `Vec<Self>`. Overridden by `u8` to describe bytes.
+    #[doc(hidden)]
+    fn vec_media_type() -> Option<MediaType> where Self: Sized {
+        None
+    }
+}
+
+/// A description of a request or response body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Content {
+    /// The schema of the body.
+    pub schema: Schema,
+    /// The media type of the body, if known.
+    pub media_type: Option<MediaType>,
+}
+
+/// Where a route parameter appears in a request URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// The parameter is a path segment, or trailing segments, `<param..>`.
+    Path,
+    /// The parameter is in the query string.
+    Query,
+}
+
+/// A dynamic path or query parameter of a route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    /// The name of the parameter as it appears in the route URI.
+    pub name: &'static str,
+    /// Where the parameter appears.
+    pub location: Location,
+    /// Whether the parameter is a trailing parameter, `<param..>`.
+    pub trailing: bool,
+    /// The schema of the parameter, if its type implements [`Describe`].
+    pub schema: Option<Schema>,
+}
+
+/// The documented signature of a route.
+///
+/// When Rocket's `operations` feature is enabled, route attributes set an
+/// [`OperationFn`] in every generated route's [`meta`](crate::Route::meta)
+/// which produces the route's `Operation` on demand. Otherwise, no operations
+/// are recorded. An application can set its own `Operation` via `meta(...)`,
+/// which takes precedence. Either way, [`Operation::of()`] retrieves it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    /// The path of the module the route's handler is defined in, as reported
+    /// by [`module_path!()`], if known.
+    pub module: Option<&'static str>,
+    /// The dynamic path parameters, in order, followed by the dynamic query
+    /// parameters, in order.
+    pub params: Vec<Parameter>,
+    /// The request body, if the route has a data guard whose type implements
+    /// [`Describe`].
+    pub body: Option<Content>,
+    /// The response body, if the route's return type implements [`Describe`].
+    pub response: Option<Content>,
+}
+
+/// Produces a route's [`Operation`] when called.
+///
+/// Route attributes set an `OperationFn` instead of an `Operation` so that a
+/// route's schemas are only computed if the route is documented.
+#[derive(Debug, Clone, Copy)]
+pub struct OperationFn(pub fn() -> Operation);
+
+impl Operation {
+    /// Returns the operation of `route`: the `Operation` in the route's
+    /// [`meta`](crate::Route::meta), if there is one, or else the `Operation`
+    /// produced by its [`OperationFn`], if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::http::Method;
+    /// use rocket::schema::{Operation, Schema, Content};
+    /// # use rocket::route::dummy_handler as handler;
+    ///
+    /// let mut route = Route::new(Method::Get, "/", handler);
+    /// assert!(Operation::of(&route).is_none());
+    ///
+    /// let response = Content { schema: Schema::string(), media_type: None };
+    /// let operation = Operation {
+    ///     module: None,
+    ///     params: vec![],
+    ///     body: None,
+    ///     response: Some(response),
+    /// };
+    ///
+    /// route.meta.set(operation.clone());
+    /// assert_eq!(Operation::of(&route), Some(operation));
+    /// ```
+    pub fn of(route: &Route) -> Option<Operation> {
+        route.meta.get::<Operation>()
+            .cloned()
+            .or_else(|| route.meta.get::<OperationFn>().map(|f| (f.0)()))
+    }
+}
+
+macro_rules! impl_describe {
+    ($($T:ty => $schema:expr),* $(,)?) => ($(
+        impl Describe for $T {
+            fn schema() -> Schema { $schema }
+        }
+    )*)
+}
+
+impl_describe! {
+    bool => Schema::boolean(),
+    u16 => Schema::integer("int32"),
+    u32 => Schema::integer("int64"), u64 => Schema::integer("int64"),
+    u128 => Schema::integer_any(), usize => Schema::integer("int64"),
+    i8 => Schema::integer("int32"), i16 => Schema::integer("int32"),
+    i32 => Schema::integer("int32"), i64 => Schema::integer("int64"),
+    i128 => Schema::integer_any(), isize => Schema::integer("int64"),
+    f32 => Schema::number("float"), f64 => Schema::number("double"),
+    char => Schema::string(),
+    PathBuf => Schema::string(),
+    crate::data::TempFile<'_> => Schema::string().format("binary"),
+}
+
+impl Describe for str {
+    fn schema() -> Schema { Schema::string() }
+
+    fn media_type() -> Option<MediaType> { Some(MediaType::Plain) }
+}
+
+impl Describe for String {
+    fn schema() -> Schema { Schema::string() }
+
+    fn media_type() -> Option<MediaType> { Some(MediaType::Plain) }
+}
+
+impl Describe for [u8] {
+    fn schema() -> Schema { Schema::string().format("binary") }
+
+    fn media_type() -> Option<MediaType> { Some(MediaType::Binary) }
+}
+
+impl Describe for u8 {
+    fn schema() -> Schema { Schema::integer("int32") }
+
+    fn vec_schema() -> Schema { <[u8]>::schema() }
+
+    fn vec_media_type() -> Option<MediaType> { <[u8]>::media_type() }
+}
+
+macro_rules! impl_describe_via {
+    ($($T:ty),* $(,)?) => ($(
+        impl<T: Describe + ?Sized> Describe for $T {
+            fn schema() -> Schema { T::schema() }
+
+            fn media_type() -> Option<MediaType> { T::media_type() }
+        }
+    )*)
+}
+
+impl_describe_via!(&T, &mut T, Box<T>, std::sync::Arc<T>);
+
+impl<T: Describe + ToOwned + ?Sized> Describe for Cow<'_, T> {
+    fn schema() -> Schema { T::schema() }
+
+    fn media_type() -> Option<MediaType> { T::media_type() }
+}
+
+impl<T: Describe> Describe for Option<T> {
+    fn schema() -> Schema { T::schema().nullable() }
+
+    fn media_type() -> Option<MediaType> { T::media_type() }
+}
+
+impl<T: Describe, E> Describe for Result<T, E> {
+    fn schema() -> Schema { T::schema() }
+
+    fn media_type() -> Option<MediaType> { T::media_type() }
+}
+
+impl<T: Describe> Describe for Vec<T> {
+    fn schema() -> Schema { T::vec_schema() }
+
+    fn media_type() -> Option<MediaType> { T::vec_media_type() }
+}
+
+impl<T: Describe> Describe for crate::form::Form<T> {
+    fn schema() -> Schema { T::schema() }
+
+    fn media_type() -> Option<MediaType> { Some(MediaType::Form) }
+}
+
+impl<T: Describe> Describe for crate::form::Strict<T> {
+    fn schema() -> Schema { T::schema() }
+}
+
+impl<T: Describe> Describe for crate::form::Lenient<T> {
+    fn schema() -> Schema { T::schema() }
+}
+
+impl<T: Describe> Describe for crate::response::status::Created<T> {
+    fn schema() -> Schema { T::schema() }
+
+    fn media_type() -> Option<MediaType> { T::media_type() }
+}
+
+impl<T: Describe> Describe for crate::response::content::Html<T> {
+    fn schema() -> Schema { T::schema() }
+
+    fn media_type() -> Option<MediaType> { Some(MediaType::HTML) }
+}
+
+#[doc(hidden)]
+pub mod resolution {
+    use super::*;
+
+    /// `Resolve<T>::content()` is `Some` with `T`'s description for
+    /// `T: Describe` and `None` otherwise. See `sentinel::resolution` for the
+    /// mechanics of this static dispatch hack.
+    pub struct Resolve<T: ?Sized>(std::marker::PhantomData<T>);
+
+    /// Fallback for `T: !Describe`.
+    pub trait DefaultDescribe {
+        fn content() -> Option<Content> { None }
+    }
+
+    impl<T: ?Sized> DefaultDescribe for T {}
+
+    impl<T: Describe + ?Sized> Resolve<T> {
+        pub fn content() -> Option<Content> {
+            Some(Content { schema: T::schema(), media_type: T::media_type() })
+        }
+    }
+}
+
+/// Resolves a `T` to `Some` of its [`Content`] if `T: Describe` and `None`
+/// otherwise.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! describe {
+    ($T:ty) => ({
+        #[allow(unused_imports)]
+        use $crate::schema::resolution::{Resolve, DefaultDescribe as _};
+
+        Resolve::<$T>::content()
+    })
+}
+
+pub use describe;
+
+/// Sets the [`OperationFn`] `$op` in the route [`Meta`](crate::route::Meta)
+/// `$meta` if the `operations` feature is enabled and does nothing otherwise.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "operations")]
+macro_rules! __set_operation {
+    ($meta:ident, $op:expr) => ($meta.set($crate::schema::OperationFn($op)))
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "operations"))]
+macro_rules! __set_operation {
+    ($meta:ident, $op:expr) => (())
+}
+
+#[doc(hidden)]
+pub use __set_operation;
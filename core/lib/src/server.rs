@@ -11,10 +11,12 @@ use crate::form::Form;
 use crate::outcome::Outcome;
 use crate::error::{Error, ErrorKind};
 use crate::ext::{AsyncReadExt, CancellableListener, CancellableIo};
+use crate::config::TrailingSlash;
+use crate::fairing::ShortCircuit;
+use crate::tower::{HyperService, RocketService, ConnectionService};
 
-use crate::http::{Method, Status, Header, hyper};
+use crate::http::{Method, Status, Header, hyper, uri::Origin};
 use crate::http::private::{Listener, Connection, Incoming};
-use crate::http::uri::Origin;
 use crate::http::private::bind_tcp;
 
 // A token returned to force the execution of one method before another.
@@ -60,7 +62,7 @@ async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
 // converts Hyper types into Rocket types, then calls the `dispatch` function,
 // which knows nothing about Hyper. Because responding depends on the
 // `HyperResponse` type, this function does the actual response processing.
-async fn hyper_service_fn(
+pub(crate) async fn hyper_service_fn(
     rocket: Arc<Rocket<Orbit>>,
     h_addr: std::net::SocketAddr,
     hyp_req: hyper::Request<hyper::Body>,
@@ -81,14 +83,14 @@ async fn hyper_service_fn(
 
         let mut req = match req_res {
             Ok(req) => req,
-            Err(e) => {
-                error!("Bad incoming request: {}", e);
-                // TODO: We don't have a request to pass in, so we just
-                // fabricate one. This is weird. We should let the user know
-                // that we failed to parse a request (by invoking some special
-                // handler) instead of doing this.
-                let dummy = Request::new(&rocket, Method::Get, Origin::dummy());
-                let r = rocket.handle_error(Status::BadRequest, &dummy).await;
+            Err(bad_req) => {
+                // `bad_req` is a stand-in; the 400 catcher can inspect what we
+                // failed to parse via `Request::malformed()`.
+                if let Some(malformed) = bad_req.malformed() {
+                    error!("Bad incoming request: {}", malformed);
+                }
+
+                let r = rocket.handle_error(Status::BadRequest, &bad_req).await;
                 return rocket.send_response(r, tx).await;
             }
         };
@@ -168,6 +170,7 @@ impl Rocket<Orbit> {
     ///
     ///   * Rewriting the method in the request if _method form field exists.
     ///   * Run the request fairings.
+    ///   * Snapshot the current router, which the request is routed against.
     ///
     /// Keep this in-sync with derive_form when preprocessing form fields.
     pub(crate) async fn preprocess_request(
@@ -195,6 +198,9 @@ impl Rocket<Orbit> {
         // Run request fairings.
         self.fairings.handle_request(req, data).await;
 
+        // Route against the router as it is now, even if it's later replaced.
+        req.state.router = self.router.get();
+
         RequestToken
     }
 
@@ -210,8 +216,9 @@ impl Rocket<Orbit> {
         // Remember if the request is `HEAD` for later body stripping.
         let was_head_request = request.method() == Method::Head;
 
-        // Route the request and run the user's handlers.
-        let mut response = self.route_and_process(request, data).await;
+        // Route the request and run the user's handlers, within any dispatch
+        // fairings.
+        let mut response = self.fairings.handle_dispatch(self, request, data, 0).await;
 
         // Add a default 'Server' header if it isn't already there.
         // TODO: If removing Hyper, write out `Date` header too.
@@ -230,12 +237,18 @@ impl Rocket<Orbit> {
         response
     }
 
-    async fn route_and_process<'s, 'r: 's>(
+    pub(crate) async fn route_and_process<'s, 'r: 's>(
         &'s self,
         request: &'r Request<'s>,
         data: Data
     ) -> Response<'r> {
-        let mut response = match self.route(request, data).await {
+        let outcome = match request.take_short_circuit() {
+            Some(ShortCircuit::Response(response)) => Outcome::Success(response),
+            Some(ShortCircuit::Status(status)) => Outcome::Failure(status),
+            None => self.route(request, data).await,
+        };
+
+        let mut response = match outcome {
             Outcome::Success(response) => response,
             Outcome::Forward(data) if request.method() == Method::Head => {
                 info_!("Autohandling {} request.", Paint::default("HEAD").bold());
@@ -245,10 +258,10 @@ impl Rocket<Orbit> {
                 match self.route(request, data).await {
                     Outcome::Success(response) => response,
                     Outcome::Failure(status) => self.handle_error(status, request).await,
-                    Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+                    Outcome::Forward(_) => self.handle_unrouted(request).await,
                 }
             }
-            Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+            Outcome::Forward(_) => self.handle_unrouted(request).await,
             Outcome::Failure(status) => self.handle_error(status, request).await,
         };
 
@@ -262,6 +275,67 @@ impl Rocket<Orbit> {
         response
     }
 
+    /// Responds to a `request` that no route handled. This is a `404` unless
+    /// `method_not_allowed` is enabled and only routes with other methods
+    /// match the request, in which case it is a `405` with an `Allow` header.
+    /// With the [`TrailingSlash::Redirect`] policy, a request that would be
+    /// routed with its trailing slash added or removed is instead redirected.
+    async fn handle_unrouted<'s, 'r: 's>(&'s self, request: &'r Request<'s>) -> Response<'r> {
+        if self.config.trailing_slash == TrailingSlash::Redirect {
+            if let Some(uri) = self.trailing_slash_redirect(request) {
+                info_!("Redirecting to canonical URI {}.", Paint::default(&uri).bold());
+                return Response::build()
+                    .status(Status::PermanentRedirect)
+                    .header(Header::new("Location", uri.to_string()))
+                    .finalize();
+            }
+        }
+
+        let allowed = match self.config.method_not_allowed {
+            true => request.state.router.allowed_methods(request),
+            false => vec![],
+        };
+
+        if allowed.is_empty() {
+            return self.handle_error(Status::NotFound, request).await;
+        }
+
+        let allow = allowed.iter().map(|m| m.as_str()).collect::<Vec<_>>().join(", ");
+        request.set_allowed_methods(allowed);
+
+        let mut response = self.handle_error(Status::MethodNotAllowed, request).await;
+        if !response.headers().contains("Allow") {
+            response.set_header(Header::new("Allow", allow));
+        }
+
+        response
+    }
+
+    /// Returns `request`'s URI with its trailing slash added or removed if some
+    /// route matches the resulting request.
+    fn trailing_slash_redirect(&self, request: &Request<'_>) -> Option<Origin<'static>> {
+        let path = request.uri().path().as_str();
+        let path = match path.len() > 1 && path.ends_with('/') {
+            true => path.trim_end_matches('/').to_string(),
+            false => format!("{}/", path),
+        };
+
+        if path.len() <= 1 {
+            return None;
+        }
+
+        let uri = match request.uri().query() {
+            Some(query) => format!("{}?{}", path, query),
+            None => path,
+        };
+
+        let uri = Origin::parse_owned(uri).ok()?;
+        let mut alternate = request.clone();
+        alternate.set_uri(uri.clone());
+        let routed = request.state.router.routes().any(|r| r.matches(&alternate));
+        routed.then(|| uri)
+    }
+
     /// Tries to find a `Responder` for a given `request`. It does this by
     /// routing the request and calling the handler for each matching route
     /// until one of the handlers returns success or failure, or there are no
@@ -274,10 +348,10 @@ impl Rocket<Orbit> {
         mut data: Data,
     ) -> route::Outcome<'r> {
         // Go through the list of matching routes until we fail or succeed.
-        for route in self.router.route(request) {
+        for (id, route) in request.state.router.route(request) {
             // Retrieve and set the requests parameters.
             info_!("Matched: {}", route);
-            request.set_route(route);
+            request.set_route(id);
 
             let name = route.name.as_deref();
             let outcome = handle(name, || route.handler.handle(request, data)).await
@@ -317,7 +391,7 @@ impl Rocket<Orbit> {
         // response. We may wish to relax this in the future.
         req.cookies().reset_delta();
 
-        if let Some(catcher) = self.router.catch(status, req) {
+        if let Some(catcher) = req.state.router.catch(status, req) {
             warn_!("Responding with registered {} catcher.", catcher);
             let name = catcher.name.as_deref();
             handle(name, || catcher.handler.handle(status, req)).await
@@ -339,8 +413,14 @@ impl Rocket<Orbit> {
         mut status: Status,
         req: &'r Request<'s>
     ) -> Response<'r> {
-        // Dispatch to the `status` catcher.
-        if let Ok(r) = self.invoke_catcher(status, req).await {
+        // Dispatch to the `status` catcher, challenging unauthorized requests.
+        if let Ok(mut r) = self.invoke_catcher(status, req).await {
+            if status == Status::Unauthorized && !r.headers().contains("WWW-Authenticate") {
+                for challenge in req.auth_challenges() {
+                    r.adjoin_header(Header::new("WWW-Authenticate", challenge));
+                }
+            }
+
             return r;
         }
 
@@ -410,15 +490,13 @@ impl Rocket<Orbit> {
         let grace = self.config.shutdown.grace as u64;
         let mercy = self.config.shutdown.mercy as u64;
 
+        // Apply the layers once; each connection is served by a clone.
         let rocket = Arc::new(self);
+        let service = rocket.layers.apply(HyperService::new(RocketService::shared(rocket.clone())));
         let service_fn = move |conn: &CancellableIo<_, L::Connection>| {
-            let rocket = rocket.clone();
             let remote = conn.remote_addr().unwrap_or_else(|| ([0, 0, 0, 0], 0).into());
-            async move {
-                Ok::<_, std::convert::Infallible>(hyper::service_fn(move |req| {
-                    hyper_service_fn(rocket.clone(), remote, req)
-                }))
-            }
+            let service = ConnectionService::new(remote, service.clone());
+            async move { Ok::<_, std::convert::Infallible>(service) }
         };
 
         // NOTE: `hyper` uses `tokio::spawn()` as the default executor.
@@ -0,0 +1,524 @@
+//! Interoperability with [`tower`](https://docs.rs/tower) middleware and
+//! services.
+//!
+//! Rocket serves each connection with a [`hyper`](https://docs.rs/hyper)
+//! service that converts requests into Rocket's types and dispatches them.
+//! [`Rocket::layer()`](crate::Rocket::layer()) wraps that service in any
+//! tower [`Layer`] whose service handles [`HyperRequest`]s, allowing existing
+//! tower middleware for timeouts, concurrency limits, tracing, and so on to be
+//! used with Rocket.
+//!
+//! Layers are applied once, when Rocket starts serving. The resulting service
+//! is cloned for every connection, so layered services must be `Clone`, and
+//! any state shared by clones, like the permits of a concurrency limit, is
+//! shared by all connections. The remote address of a connection is available
+//! to layered services as a [`SocketAddr`] request extension.
+//!
+//! An error returned by a layered service, such as a timeout elapsing or a
+//! load-shedding layer rejecting a request, is logged and answered with a
+//! `503 Service Unavailable` response; the connection remains open.
+//!
+//! Layered services see raw `hyper` requests and responses: they run before
+//! Rocket parses a request and after Rocket has produced its response. For
+//! middleware that needs Rocket's types, use a
+//! [dispatch fairing](crate::fairing::Fairing::on_dispatch()) instead. Layers
+//! are not applied to requests dispatched via [`local`](crate::local) clients.
+//!
+//! Services can also be embedded in either direction: a [`ServiceHandler`]
+//! serves requests under a mount point with an existing tower service, while
+//! a [`RocketService`] serves requests with Rocket from another server.
+
+use std::{fmt, io};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Buf;
+use futures::future::{poll_fn, BoxFuture};
+use tokio_util::io::StreamReader;
+
+use crate::{Request, Response, Data, Route, Rocket, Orbit};
+use crate::route::{Handler, Outcome};
+use crate::http::{hyper, Method, Status};
+use crate::http::hyper::HttpBody;
+use crate::data::{ByteUnit, Limits};
+use crate::ext::AsyncReadExt;
+
+#[doc(inline)]
+pub use tower_layer::Layer;
+
+#[doc(inline)]
+pub use tower_service::Service;
+
+/// A type-erased error returned by a layered service.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A raw request as served by a [`HyperService`].
+pub type HyperRequest = hyper::Request<hyper::Body>;
+
+/// A raw response as returned by a [`HyperService`].
+pub type HyperResponse = hyper::Response<hyper::Body>;
+
+/// A type-erased service that can be cloned.
+trait CloneService: Service<
+    HyperRequest,
+    Response = HyperResponse,
+    Error = BoxError,
+    Future = BoxFuture<'static, Result<HyperResponse, BoxError>>
+> + Send {
+    fn clone_box(&self) -> Box<dyn CloneService>;
+}
+
+impl<S> CloneService for S
+    where S: Service<
+        HyperRequest,
+        Response = HyperResponse,
+        Error = BoxError,
+        Future = BoxFuture<'static, Result<HyperResponse, BoxError>>
+    > + Clone + Send + 'static
+{
+    fn clone_box(&self) -> Box<dyn CloneService> {
+        Box::new(self.clone())
+    }
+}
+
+/// The `hyper` service Rocket serves connections with, as wrapped by a
+/// [`Layer`] applied with [`Rocket::layer()`](crate::Rocket::layer()).
+///
+/// A `HyperService` is either Rocket's own service or a service resulting from
+/// a previously applied layer. Cloning a `HyperService` clones the service it
+/// wraps.
+pub struct HyperService(Box<dyn CloneService>);
+
+impl HyperService {
+    pub(crate) fn new<S>(service: S) -> HyperService
+        where S: Service<HyperRequest, Response = HyperResponse> + Clone + Send + 'static,
+              S::Error: Into<BoxError>,
+              S::Future: Send + 'static,
+    {
+        HyperService(Box::new(Erased(service)))
+    }
+}
+
+impl Clone for HyperService {
+    fn clone(&self) -> Self {
+        HyperService(self.0.clone_box())
+    }
+}
+
+impl Service<HyperRequest> for HyperService {
+    type Response = HyperResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<HyperResponse, BoxError>>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.0.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, request: HyperRequest) -> Self::Future {
+        self.0.call(request)
+    }
+}
+
+impl fmt::Debug for HyperService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperService").finish()
+    }
+}
+
+/// Erases the error and future types of a service.
+#[derive(Clone)]
+struct Erased<S>(S);
+
+impl<S> Service<HyperRequest> for Erased<S>
+    where S: Service<HyperRequest, Response = HyperResponse>,
+          S::Error: Into<BoxError>,
+          S::Future: Send + 'static,
+{
+    type Response = HyperResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<HyperResponse, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.0.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: HyperRequest) -> Self::Future {
+        let future = self.0.call(request);
+        Box::pin(async move { future.await.map_err(Into::into) })
+    }
+}
+
+type LayerFn = dyn Fn(HyperService) -> HyperService + Send + Sync;
+
+/// The layers applied via [`Rocket::layer()`](crate::Rocket::layer()).
+#[derive(Default, Clone)]
+pub(crate) struct Layers(Vec<Arc<LayerFn>>);
+
+impl Layers {
+    pub fn push<L>(&mut self, layer: L)
+        where L: Layer<HyperService> + Send + Sync + 'static,
+              L::Service: Service<HyperRequest, Response = HyperResponse> + Clone + Send + 'static,
+              <L::Service as Service<HyperRequest>>::Error: Into<BoxError>,
+              <L::Service as Service<HyperRequest>>::Future: Send + 'static,
+    {
+        self.0.push(Arc::new(move |service| HyperService::new(layer.layer(service))));
+    }
+
+    /// Wraps `service` in every layer. The first layer pushed is outermost.
+    pub fn apply(&self, service: HyperService) -> HyperService {
+        self.0.iter().rev().fold(service, |service, layer| layer(service))
+    }
+}
+
+impl fmt::Debug for Layers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Layers").field(&self.0.len()).finish()
+    }
+}
+
+/// Serves a single connection with a clone of the layered service, adding the
+/// connection's remote address to every request as a [`SocketAddr`] extension.
+pub(crate) struct ConnectionService {
+    remote: SocketAddr,
+    service: HyperService,
+    // An error from `service.poll_ready()`, reported in the next response.
+    ready_error: Option<BoxError>,
+}
+
+impl ConnectionService {
+    pub fn new(remote: SocketAddr, service: HyperService) -> ConnectionService {
+        ConnectionService { remote, service, ready_error: None }
+    }
+
+    /// Logs `error`, returned by the layered service, and returns the response
+    /// that is sent in place of one from the service: `503 Service Unavailable`.
+    fn error_response(error: BoxError) -> HyperResponse {
+        error!("Layered service failed to handle request: {}.", error);
+        info_!("Responding with 503 Service Unavailable.");
+        let mut response = hyper::Response::new(hyper::Body::empty());
+        *response.status_mut() = hyper::StatusCode::SERVICE_UNAVAILABLE;
+        response
+    }
+}
+
+impl Service<HyperRequest> for ConnectionService {
+    type Response = HyperResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<HyperResponse, BoxError>>;
+
+    // Errors are turned into responses: `hyper` closes the connection without
+    // responding if the service it serves returns an error.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        match self.service.poll_ready(cx) {
+            Poll::Ready(Err(e)) => {
+                self.ready_error = Some(e);
+                Poll::Ready(Ok(()))
+            }
+            poll => poll,
+        }
+    }
+
+    fn call(&mut self, mut request: HyperRequest) -> Self::Future {
+        if let Some(error) = self.ready_error.take() {
+            return Box::pin(async move { Ok(Self::error_response(error)) });
+        }
+
+        request.extensions_mut().insert(self.remote);
+        let response = self.service.call(request);
+        Box::pin(async move { Ok(response.await.unwrap_or_else(Self::error_response)) })
+    }
+}
+
+/// A [`Handler`] that serves requests with a tower [`Service`].
+///
+/// A `ServiceHandler` converts each request it handles, including its body
+/// data, into a [`HyperRequest`] and calls the service with it, converting the
+/// service's response back into a Rocket [`Response`]. Mounting a
+/// `ServiceHandler` at a base creates a route for every method that matches
+/// every path under the base. The service sees paths relative to the base: a
+/// `ServiceHandler` mounted at `/legacy` sees a request to `/legacy/users?id=1`
+/// as a request to `/users?id=1`. The request's remote address, if known, is
+/// available to the service as a [`SocketAddr`] request extension.
+///
+/// The service is cloned for every request. Request bodies are streamed to the
+/// service and limited by the `service` [limit](crate::data::Limits), which
+/// defaults to 8KiB if not configured: a request whose `Content-Length`
+/// exceeds the limit fails with `413 Payload Too Large` without calling the
+/// service, and a body that exceeds the limit while streaming ends with an
+/// error. Response bodies are streamed to the client. Hop-by-hop headers are
+/// dropped from both the request and the service's response, as is the
+/// request's `Content-Length`. An error from the service is logged and results
+/// in a `500 Internal Server Error`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use std::convert::Infallible;
+///
+/// use rocket::http::hyper;
+/// use rocket::tower::{ServiceHandler, HyperRequest, HyperResponse};
+///
+/// async fn legacy(request: HyperRequest) -> Result<HyperResponse, Infallible> {
+///     let body = format!("legacy: {}", request.uri());
+///     Ok(hyper::Response::new(body.into()))
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let service = ServiceHandler::new(hyper::service_fn(legacy));
+///     rocket::build().mount("/legacy", service)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ServiceHandler<S> {
+    service: S,
+    rank: isize,
+}
+
+impl<S> ServiceHandler<S> {
+    /// The default rank used by `ServiceHandler` routes.
+    const DEFAULT_RANK: isize = 10;
+
+    /// Constructs a `ServiceHandler` serving requests with `service`. The
+    /// generated routes have a rank of `10`. To choose a different rank, use
+    /// [`ServiceHandler::rank()`].
+    pub fn new(service: S) -> Self {
+        ServiceHandler { service, rank: Self::DEFAULT_RANK }
+    }
+
+    /// Sets the rank of the generated routes to `rank`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+}
+
+impl<S, B> Into<Vec<Route>> for ServiceHandler<S>
+    where S: Service<HyperRequest, Response = hyper::Response<B>> + Clone + Send + Sync + 'static,
+          S::Future: Send,
+          S::Error: Into<BoxError>,
+          B: HttpBody + Send + 'static,
+          B::Data: Send,
+          B::Error: Into<BoxError>,
+{
+    fn into(self) -> Vec<Route> {
+        use Method::*;
+
+        [Get, Put, Post, Delete, Options, Head, Trace, Connect, Patch].iter()
+            .map(|method| {
+                let mut route = Route::ranked(self.rank, *method, "/<path..>", self.clone());
+                route.name = Some("ServiceHandler".into());
+                route
+            })
+            .collect()
+    }
+}
+
+#[crate::async_trait]
+impl<S, B> Handler for ServiceHandler<S>
+    where S: Service<HyperRequest, Response = hyper::Response<B>> + Clone + Send + Sync + 'static,
+          S::Future: Send,
+          S::Error: Into<BoxError>,
+          B: HttpBody + Send + 'static,
+          B::Data: Send,
+          B::Error: Into<BoxError>,
+{
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data) -> Outcome<'r> {
+        let limit = req.limits().get("service").unwrap_or(Limits::BYTES);
+        let length = req.headers().get_one("Content-Length").and_then(|n| n.parse().ok());
+        if length.map_or(false, |length: u64| length > limit.as_u64()) {
+            warn_!("Request body exceeds the service limit of {}.", limit);
+            return Outcome::Failure(Status::PayloadTooLarge);
+        }
+
+        let request = match hyper_request(req, data, limit) {
+            Ok(request) => request,
+            Err(e) => {
+                error_!("Failed to convert request for service: {}.", e);
+                return Outcome::Failure(Status::BadRequest);
+            }
+        };
+
+        let mut service = self.service.clone();
+        let response = match poll_fn(|cx| service.poll_ready(cx)).await {
+            Ok(()) => service.call(request).await,
+            Err(e) => Err(e),
+        };
+
+        match response {
+            Ok(response) => Outcome::Success(rocket_response(response)),
+            Err(e) => {
+                let e: BoxError = e.into();
+                error_!("Service failed to handle request: {}.", e);
+                Outcome::Failure(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+/// Headers that apply to a single connection and thus aren't forwarded.
+const HOP_BY_HOP: &[&str] = &[
+    "connection", "keep-alive", "proxy-authenticate", "proxy-authorization",
+    "proxy-connection", "te", "trailer", "transfer-encoding", "upgrade"
+];
+
+/// Converts `req` and `data` into a request relative to `req`'s route base
+/// whose body fails to read past `limit`.
+fn hyper_request(
+    req: &Request<'_>,
+    data: Data,
+    limit: ByteUnit,
+) -> Result<HyperRequest, BoxError> {
+    // Skip as many non-empty segments as the route's base has, keeping the
+    // remaining segments as they were sent.
+    let mut base_segs = req.route().map_or(0, |route| route.uri.metadata.base_segs.len());
+    let segments: Vec<&str> = req.uri().raw_path_segments()
+        .skip_while(|segment| {
+            if base_segs == 0 {
+                return false;
+            }
+
+            base_segs -= !segment.is_empty() as usize;
+            true
+        })
+        .map(|segment| segment.as_str())
+        .collect();
+
+    let uri = match req.uri().query() {
+        Some(query) => format!("/{}?{}", segments.join("/"), query),
+        None => format!("/{}", segments.join("/")),
+    };
+
+    // Besides hop-by-hop headers, including those `Connection` names, drop
+    // `Content-Length`: the body is streamed without a known length.
+    let connection: Vec<&str> = req.headers().get("Connection")
+        .flat_map(|value| value.split(','))
+        .map(|name| name.trim())
+        .collect();
+
+    let mut builder = hyper::Request::builder().method(req.method().as_str()).uri(uri);
+    for header in req.headers().iter() {
+        let name = header.name().as_str();
+        let dropped = std::iter::once(&"content-length")
+            .chain(HOP_BY_HOP)
+            .chain(&connection)
+            .any(|dropped| name.eq_ignore_ascii_case(dropped));
+
+        if !dropped {
+            builder = builder.header(name, header.value());
+        }
+    }
+
+    if let Some(remote) = req.remote() {
+        builder = builder.extension(remote);
+    }
+
+    let body = data.open_strict(limit).into_bytes_stream(4096);
+    Ok(builder.body(hyper::Body::wrap_stream(body))?)
+}
+
+/// Converts a service's `response` into a streaming Rocket response.
+fn rocket_response<'r, B>(response: hyper::Response<B>) -> Response<'r>
+    where B: HttpBody + Send + 'static, B::Data: Send, B::Error: Into<BoxError>,
+{
+    let (parts, body) = response.into_parts();
+    let mut builder = Response::build();
+    builder.status(Status::new(parts.status.as_u16()));
+    for (name, value) in parts.headers.iter() {
+        if !HOP_BY_HOP.contains(&name.as_str()) {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            builder.raw_header_adjoin(name.as_str().to_owned(), value);
+        }
+    }
+
+    let chunks = futures::stream::unfold(Box::pin(body), |mut body| async move {
+        let chunk = body.data().await?
+            .map(|mut data| data.copy_to_bytes(data.remaining()))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+
+        Some((chunk, body))
+    });
+
+    builder.streamed_body(StreamReader::new(chunks)).finalize()
+}
+
+/// A tower [`Service`] that serves [`HyperRequest`]s with a Rocket application.
+///
+/// A `RocketService` allows a Rocket application to be embedded in another
+/// server: requests are handled exactly as if they were received by Rocket's
+/// own server, except that [layers](crate::Rocket::layer()) are not applied.
+/// Instead, the embedding server's middleware applies. The remote address of a
+/// request is read from its [`SocketAddr`] extension, if there is one.
+///
+/// A `RocketService` is created via [`Rocket::into_service()`] and is cheap to
+/// clone. The application it serves is available via
+/// [`RocketService::rocket()`].
+///
+/// [`Rocket::into_service()`]: crate::Rocket::into_service()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::hyper;
+/// use rocket::tower::Service;
+///
+/// #[get("/")]
+/// fn index() -> &'static str { "Hello, world!" }
+///
+/// # rocket::async_test(async {
+/// let rocket = rocket::build().mount("/", routes![index]).ignite().await.unwrap();
+/// let mut service = rocket.into_service().await;
+///
+/// let request = hyper::Request::get("/").body(hyper::Body::empty()).unwrap();
+/// let response = service.call(request).await.unwrap();
+/// assert_eq!(response.status(), 200);
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct RocketService {
+    rocket: Arc<Rocket<Orbit>>,
+}
+
+impl RocketService {
+    pub(crate) fn new(rocket: Rocket<Orbit>) -> RocketService {
+        RocketService::shared(Arc::new(rocket))
+    }
+
+    pub(crate) fn shared(rocket: Arc<Rocket<Orbit>>) -> RocketService {
+        RocketService { rocket }
+    }
+
+    /// Returns the application served by `self`.
+    pub fn rocket(&self) -> &Rocket<Orbit> {
+        &self.rocket
+    }
+}
+
+impl Service<HyperRequest> for RocketService {
+    type Response = HyperResponse;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<HyperResponse>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: HyperRequest) -> Self::Future {
+        let remote = request.extensions().get::<SocketAddr>().copied()
+            .unwrap_or_else(|| ([0, 0, 0, 0], 0).into());
+
+        Box::pin(crate::server::hyper_service_fn(self.rocket.clone(), remote, request))
+    }
+}
+
+impl fmt::Debug for RocketService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocketService").field("rocket", &self.rocket).finish()
+    }
+}
@@ -0,0 +1,116 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::auth::{Basic, Bearer, Verifier, Verified};
+
+struct Tokens;
+
+#[rocket::async_trait]
+impl Verifier for Tokens {
+    type Credentials = Bearer;
+    type Identity = &'static str;
+
+    async fn verify(&self, bearer: &Bearer, _: &Request<'_>) -> Option<&'static str> {
+        match bearer.token() {
+            "s3cr3t" => Some("admin"),
+            _ => None,
+        }
+    }
+
+    fn realm(&self) -> &str {
+        "api"
+    }
+}
+
+#[get("/basic")]
+fn basic(basic: Basic) -> String {
+    format!("{}:{}", basic.username(), basic.password())
+}
+
+#[get("/bearer")]
+fn bearer(bearer: Option<Bearer>) -> String {
+    bearer.map(|b| b.token().to_string()).unwrap_or_else(|| "anonymous".into())
+}
+
+#[get("/verified")]
+fn verified(user: Verified<Tokens>) -> &'static str {
+    user.0
+}
+
+mod auth_guards_tests {
+    use super::*;
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .manage(Tokens)
+            .mount("/", routes![basic, bearer, verified]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn challenges(response: &rocket::local::blocking::LocalResponse<'_>) -> Vec<String> {
+        response.headers().get("WWW-Authenticate").map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn basic_credentials_are_decoded() {
+        let client = client();
+        let auth = Header::new("Authorization", "basic dXNlcjpwYTpzcw==");
+        let response = client.get("/basic").header(auth).dispatch();
+        assert_eq!(response.into_string().unwrap(), "user:pa:ss");
+
+        let response = client.get("/basic").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(challenges(&response), ["Basic realm=\"Rocket\", charset=\"UTF-8\""]);
+
+        let auth = Header::new("Authorization", "Basic !!!");
+        let response = client.get("/basic").header(auth).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(challenges(&response).len(), 1);
+    }
+
+    #[test]
+    fn bearer_tokens_are_validated() {
+        let client = client();
+        let auth = Header::new("Authorization", "Bearer abc.DEF-123~+/==");
+        let response = client.get("/bearer").header(auth).dispatch();
+        assert_eq!(response.into_string().unwrap(), "abc.DEF-123~+/==");
+
+        let response = client.get("/bearer").dispatch();
+        assert_eq!(response.into_string().unwrap(), "anonymous");
+
+        let auth = Header::new("Authorization", "Basic dXNlcjpwYXNz");
+        let response = client.get("/bearer").header(auth).dispatch();
+        assert_eq!(response.into_string().unwrap(), "anonymous");
+    }
+
+    #[test]
+    fn verifiers_challenge_in_their_realm() {
+        let client = client();
+        let auth = Header::new("Authorization", "Bearer s3cr3t");
+        let response = client.get("/verified").header(auth).dispatch();
+        assert_eq!(response.into_string().unwrap(), "admin");
+
+        let response = client.get("/verified").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(challenges(&response), ["Bearer realm=\"api\""]);
+
+        let auth = Header::new("Authorization", "Bearer wrong");
+        let response = client.get("/verified").header(auth).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(challenges(&response), ["Bearer realm=\"api\", error=\"invalid_token\""]);
+
+        let auth = Header::new("Authorization", "Bearer not a token");
+        let response = client.get("/verified").header(auth).dispatch();
+        let expected = "Bearer realm=\"api\", error=\"invalid_request\"";
+        assert_eq!(challenges(&response), [expected]);
+    }
+
+    #[test]
+    fn unmanaged_verifiers_abort_launch() {
+        let rocket = rocket::build().mount("/", routes![verified]);
+        assert!(Client::debug(rocket).is_err());
+    }
+}
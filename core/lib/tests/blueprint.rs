@@ -0,0 +1,99 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Blueprint, State};
+use rocket::error::ErrorKind;
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+use rocket::http::{Header, Status};
+
+struct Ledger(&'static str);
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/charges")]
+fn charges(ledger: &State<Ledger>) -> &'static str { ledger.0 }
+
+#[catch(404)]
+fn not_found() -> &'static str { "no such billing resource" }
+
+fn tag(name: &'static str) -> AdHoc {
+    AdHoc::on_response(name, move |_, res| Box::pin(async move {
+        res.adjoin_header(Header::new("X-Fairing", name));
+    }))
+}
+
+fn billing() -> Blueprint {
+    Blueprint::new()
+        .mount("/", routes![charges])
+        .register("/", catchers![not_found])
+        .manage(Ledger("ledger"))
+        .attach(tag("billing"))
+        .nest("/invoices", Blueprint::new().mount("/", routes![index]).attach(tag("invoices")))
+}
+
+fn fairings(client: &Client, path: &'static str) -> Vec<String> {
+    let response = client.get(path).dispatch();
+    response.headers().get("X-Fairing").map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn blueprints_are_nested_under_base() {
+    let rocket = rocket::build().mount("/", routes![index]).nest("/billing", billing());
+    let client = Client::debug(rocket).unwrap();
+
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "index");
+    assert_eq!(client.get("/billing/charges").dispatch().into_string().unwrap(), "ledger");
+    assert_eq!(client.get("/billing/invoices").dispatch().into_string().unwrap(), "index");
+    assert_eq!(client.get("/charges").dispatch().status(), Status::NotFound);
+
+    let response = client.get("/billing/unknown").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().unwrap(), "no such billing resource");
+    let response = client.get("/unknown").dispatch();
+    assert_ne!(response.into_string().unwrap(), "no such billing resource");
+
+    assert!(fairings(&client, "/").is_empty());
+    assert_eq!(fairings(&client, "/billing/charges"), &["billing"]);
+    assert_eq!(fairings(&client, "/billing/invoices"), &["billing", "invoices"]);
+}
+
+#[test]
+fn duplicate_state_fails_ignite() {
+    let rocket = rocket::build().manage(Ledger("app")).nest("/billing", billing());
+    let error = rocket::async_test(rocket.ignite()).unwrap_err();
+    match error.kind() {
+        ErrorKind::DuplicateState(names) => assert!(names[0].ends_with("Ledger")),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    let rocket = rocket::build()
+        .nest("/billing", billing())
+        .nest("/payments", Blueprint::new().manage(Ledger("payments")));
+
+    let error = rocket::async_test(rocket.ignite()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::DuplicateState(names) if names.len() == 1));
+
+    let rocket = rocket::build().nest("/billing", billing()).manage(Ledger("app"));
+    let error = rocket::async_test(rocket.ignite()).unwrap_err();
+    match error.kind() {
+        ErrorKind::DuplicateState(names) => assert!(names[0].ends_with("Ledger")),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+}
+
+#[test]
+fn blueprint_collisions_fail_ignite() {
+    let rocket = rocket::build()
+        .mount("/billing", routes![charges])
+        .nest("/billing", billing());
+
+    let error = rocket::async_test(rocket.ignite()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Collisions(..)));
+}
+
+#[test]
+#[should_panic]
+fn dynamic_blueprint_base_panics() {
+    let _ = rocket::build().nest("/<id>", billing());
+}
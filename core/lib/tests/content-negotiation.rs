@@ -0,0 +1,57 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::MediaType;
+use rocket::response::Negotiate;
+
+#[get("/")]
+fn index() -> Negotiate {
+    Negotiate::new()
+        .offer(MediaType::JSON, r#"{ "hello": "world" }"#)
+        .offer(MediaType::MsgPack, vec![0x81, 0xa5])
+        .offer(MediaType::HTML, "<p>hello, world</p>")
+}
+
+mod content_negotiation_tests {
+    use super::*;
+    use rocket::http::{Accept, ContentType, Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug(rocket::build().mount("/", routes![index])).unwrap()
+    }
+
+    #[test]
+    fn negotiates_by_quality() {
+        let client = client();
+        let accept = Header::new("Accept", "application/json; q=0.5, text/*");
+        let response = client.get("/").header(accept).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+        assert_eq!(response.into_string().unwrap(), "<p>hello, world</p>");
+
+        let response = client.get("/").header(Accept::MsgPack).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::MsgPack));
+        assert_eq!(response.into_bytes().unwrap(), vec![0x81, 0xa5]);
+
+        let accept = Header::new("Accept", "*/*, application/json; q=0");
+        let response = client.get("/").header(accept).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::MsgPack));
+    }
+
+    #[test]
+    fn first_offer_is_the_default() {
+        let client = client();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+        let response = client.get("/").header(Accept::Any).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+    }
+
+    #[test]
+    fn unacceptable_requests_are_rejected() {
+        let client = client();
+        let response = client.get("/").header(Accept::PNG).dispatch();
+        assert_eq!(response.status(), Status::NotAcceptable);
+    }
+}
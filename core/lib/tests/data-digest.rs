@@ -0,0 +1,84 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+
+use rocket::data::{Data, Digest, ToByteUnit};
+
+#[derive(Default)]
+struct Checksum(u8);
+
+impl Digest for Checksum {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |sum, b| sum.wrapping_add(*b));
+    }
+}
+
+struct Expect(u8, u8);
+
+impl Digest for Expect {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0 = bytes.iter().fold(self.0, |sum, b| sum.wrapping_add(*b));
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.0 == self.1 {
+            true => Ok(()),
+            false => Err(io::Error::new(io::ErrorKind::InvalidData, "bad checksum")),
+        }
+    }
+}
+
+#[post("/checksum?<limit>", data = "<data>")]
+async fn checksum(data: Data, limit: u64) -> io::Result<String> {
+    let (data, digested) = data.digest(Checksum::default());
+    let body = data.open(limit.bytes()).into_string().await?;
+    match digested.into_inner() {
+        Some(checksum) => Ok(format!("{}:{}", body.into_inner(), checksum.0)),
+        None => Ok(format!("{}:incomplete", body.into_inner())),
+    }
+}
+
+#[post("/expect/<sum>", data = "<data>")]
+async fn expect(data: Data, sum: u8) -> Option<String> {
+    let (data, _) = data.digest(Expect(0, sum));
+    data.open(1.kibibytes()).into_string().await.ok().map(|s| s.into_inner())
+}
+
+mod data_digest_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        Client::debug_with(routes![super::checksum, super::expect]).unwrap()
+    }
+
+    #[test]
+    fn digest_sees_entire_body() {
+        let client = client();
+        let body = "a".repeat(1024);
+        let sum = (1024u32 * b'a' as u32 % 256) as u8;
+        let response = client.post("/checksum?limit=2048").body(&body).dispatch();
+        assert_eq!(response.into_string(), Some(format!("{}:{}", body, sum)));
+
+        let response = client.post("/checksum?limit=2048").body("").dispatch();
+        assert_eq!(response.into_string().unwrap(), ":0");
+    }
+
+    #[test]
+    fn digest_of_partial_body_is_incomplete() {
+        let client = client();
+        let response = client.post("/checksum?limit=2").body("abcd").dispatch();
+        assert_eq!(response.into_string().unwrap(), "ab:incomplete");
+    }
+
+    #[test]
+    fn finish_error_fails_read() {
+        let client = client();
+        let sum = b"hi".iter().fold(0u8, |s, b| s.wrapping_add(*b));
+        let response = client.post(format!("/expect/{}", sum)).body("hi").dispatch();
+        assert_eq!(response.into_string().unwrap(), "hi");
+
+        let response = client.post(format!("/expect/{}", sum + 1)).body("hi").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
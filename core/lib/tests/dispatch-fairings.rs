@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Data, Response};
+use rocket::fairing::{AdHoc, Fairing, Info, Kind, Next};
+use rocket::local::blocking::Client;
+use rocket::http::{Header, Status};
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/fail")]
+fn fail() -> Status { Status::ImATeapot }
+
+fn tag(name: &'static str) -> AdHoc {
+    AdHoc::on_dispatch(name, move |_, data, next| Box::pin(async move {
+        let mut response = next.run(data).await;
+        response.adjoin_header(Header::new("X-Around", name));
+        response
+    }))
+}
+
+struct Gate;
+
+#[rocket::async_trait]
+impl Fairing for Gate {
+    fn info(&self) -> Info {
+        Info { name: "Gate", kind: Kind::Dispatch }
+    }
+
+    async fn on_dispatch<'r>(
+        &self,
+        req: &'r Request<'r>,
+        data: Data,
+        next: Next<'r>
+    ) -> Response<'r> {
+        match req.headers().contains("X-Closed") {
+            true => Response::build().status(Status::Forbidden).finalize(),
+            false => next.run(data).await,
+        }
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![index, fail])
+        .attach(tag("outer"))
+        .attach(Gate)
+        .attach(tag("inner"))
+        .attach_at("/fail", tag("scoped"))
+        .attach(AdHoc::on_response("Seen", |_, res| Box::pin(async move {
+            let count = res.headers().get("X-Around").count();
+            res.set_header(Header::new("X-Seen", count.to_string()));
+        })));
+
+    Client::debug(rocket).unwrap()
+}
+
+fn around(response: &rocket::local::blocking::LocalResponse<'_>) -> Vec<String> {
+    response.headers().get("X-Around").map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn dispatch_fairings_nest_in_attach_order() {
+    let client = client();
+    let response = client.get("/").dispatch();
+    assert_eq!(around(&response), &["inner", "outer"]);
+    assert_eq!(response.headers().get_one("X-Seen"), Some("2"));
+    assert_eq!(response.into_string().unwrap(), "index");
+}
+
+#[test]
+fn dispatch_fairings_wrap_catchers_and_scopes() {
+    let client = client();
+    let response = client.get("/fail").dispatch();
+    assert_eq!(response.status(), Status::ImATeapot);
+    assert_eq!(around(&response), &["scoped", "inner", "outer"]);
+
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(around(&response), &["inner", "outer"]);
+}
+
+#[test]
+fn dispatch_fairings_can_respond_without_routing() {
+    let client = client();
+    let response = client.get("/").header(Header::new("X-Closed", "1")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(around(&response), &["outer"]);
+    assert_eq!(response.headers().get_one("X-Seen"), Some("1"));
+}
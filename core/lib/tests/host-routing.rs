@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use rocket::local::blocking::Client;
+use rocket::http::{Header, Status};
+
+#[get("/", host = "admin.example.com")]
+fn admin() -> &'static str { "admin" }
+
+#[get("/")]
+fn api() -> &'static str { "api" }
+
+#[get("/", rank = 2)]
+fn fallback() -> &'static str { "fallback" }
+
+#[catch(404)]
+fn admin_not_found() -> &'static str { "admin: not found" }
+
+#[catch(404)]
+fn not_found() -> &'static str { "not found" }
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![admin, fallback])
+        .mount_host("*.api.example.com", "/", routes![api])
+        .register("/", catchers![not_found])
+        .register_host("admin.example.com", "/", catchers![admin_not_found]);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn get(client: &Client, host: Option<&'static str>, path: &'static str) -> (Status, String) {
+    let mut request = client.get(path);
+    if let Some(host) = host {
+        request.add_header(Header::new("Host", host));
+    }
+
+    let response = request.dispatch();
+    (response.status(), response.into_string().unwrap())
+}
+
+#[test]
+fn routes_match_on_host() {
+    let client = client();
+    let ok = |s: &str| (Status::Ok, s.to_string());
+
+    assert_eq!(get(&client, Some("admin.example.com"), "/"), ok("admin"));
+    assert_eq!(get(&client, Some("ADMIN.example.com:8000"), "/"), ok("admin"));
+    assert_eq!(get(&client, Some("v1.api.example.com"), "/"), ok("api"));
+    assert_eq!(get(&client, Some("a.b.api.example.com"), "/"), ok("api"));
+    assert_eq!(get(&client, Some("api.example.com"), "/"), ok("fallback"));
+    assert_eq!(get(&client, Some("example.com"), "/"), ok("fallback"));
+    assert_eq!(get(&client, None, "/"), ok("fallback"));
+}
+
+#[test]
+fn catchers_match_on_host() {
+    let client = client();
+    let not_found = |s: &str| (Status::NotFound, s.to_string());
+
+    assert_eq!(get(&client, Some("admin.example.com"), "/x"), not_found("admin: not found"));
+    assert_eq!(get(&client, Some("v1.api.example.com"), "/x"), not_found("not found"));
+    assert_eq!(get(&client, None, "/x"), not_found("not found"));
+}
+
+#[test]
+fn overlapping_hosts_collide() {
+    #[get("/")]
+    fn other() -> &'static str { "other" }
+
+    let rocket = rocket::build()
+        .mount_host("*.example.com", "/", routes![api])
+        .mount_host("a.example.com", "/", routes![other]);
+
+    assert!(Client::debug(rocket).is_err());
+
+    let rocket = rocket::build()
+        .mount_host("*.example.com", "/", routes![api])
+        .mount_host("example.com", "/", routes![other]);
+
+    assert!(Client::debug(rocket).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "cannot contain a port")]
+fn host_patterns_are_validated() {
+    let _ = rocket::build().mount_host("example.com:80", "/", routes![api]);
+}
@@ -0,0 +1,69 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{Locale, Locales};
+
+#[get("/")]
+fn locale(locale: Locale) -> String {
+    format!("{}{}", locale, if locale.is_fallback() { " (fallback)" } else { "" })
+}
+
+mod locale_guard_tests {
+    use super::*;
+    use rocket::http::{Cookie, Header};
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let locales = Locales::new(["en-US", "fr", "de-CH"]).cookie("lang").query("lang");
+        let rocket = rocket::build().manage(locales).mount("/", routes![locale]);
+        Client::debug(rocket).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str, accept: Option<&'static str>) -> String {
+        let mut request = client.get(uri);
+        if let Some(value) = accept {
+            request = request.header(Header::new("Accept-Language", value));
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn negotiates_accept_language() {
+        let client = client();
+        assert_eq!(get(&client, "/", None), "en-US (fallback)");
+        assert_eq!(get(&client, "/", Some("fr-CH, en;q=0.9")), "fr");
+        assert_eq!(get(&client, "/", Some("de, fr;q=0.5")), "de-CH");
+        assert_eq!(get(&client, "/", Some("en-GB, fr;q=0.5")), "fr");
+        assert_eq!(get(&client, "/", Some("ja")), "en-US (fallback)");
+        assert_eq!(get(&client, "/", Some("not a language")), "en-US (fallback)");
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let client = client();
+        assert_eq!(get(&client, "/?lang=de-CH", Some("fr")), "de-CH");
+        assert_eq!(get(&client, "/?lang=it", Some("fr")), "fr");
+
+        let response = client.get("/")
+            .cookie(Cookie::new("lang", "fr"))
+            .header(Header::new("Accept-Language", "en"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "fr");
+
+        let response = client.get("/?lang=en").cookie(Cookie::new("lang", "fr")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "en-US");
+    }
+
+    #[test]
+    fn unmanaged_locales_abort_launch() {
+        let rocket = rocket::build().mount("/", routes![locale]);
+        assert!(Client::debug(rocket).is_err());
+
+        let rocket = rocket::build()
+            .manage(Locales::new(Vec::<String>::new()))
+            .mount("/", routes![locale]);
+
+        assert!(Client::debug(rocket).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::local::blocking::Client;
+use rocket::http::Status;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[catch(400)]
+fn bad_request(req: &Request) -> String {
+    match req.malformed() {
+        Some(malformed) => format!("malformed: {} {}", malformed.method(), malformed.uri()),
+        None => "well-formed".into(),
+    }
+}
+
+#[test]
+fn catcher_sees_malformed_request() {
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .register("/", catchers![bad_request]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("this is a bad URI").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(response.into_string().unwrap(), "malformed: GET this is a bad URI");
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "index");
+}
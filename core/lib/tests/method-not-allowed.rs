@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Config};
+use rocket::local::blocking::Client;
+use rocket::http::Status;
+
+#[get("/item/<id>")]
+fn get_item(id: usize) -> String { id.to_string() }
+
+#[put("/item/<_id>")]
+fn put_item(_id: usize) { }
+
+#[post("/items")]
+fn post_item() { }
+
+#[catch(405)]
+fn method_not_allowed(req: &Request) -> String {
+    let allowed = req.allowed_methods().unwrap_or(&[]);
+    allowed.iter().map(|m| m.as_str()).collect::<Vec<_>>().join(" ")
+}
+
+fn client(method_not_allowed: bool) -> Client {
+    let figment = Config::figment().merge((Config::METHOD_NOT_ALLOWED, method_not_allowed));
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![get_item, put_item, post_item])
+        .register("/", catchers![method_not_allowed]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn method_not_allowed_is_opt_in() {
+    let client = client(false);
+    assert_eq!(client.delete("/item/1").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn method_not_allowed_lists_allowed_methods() {
+    let client = client(true);
+
+    let response = client.delete("/item/1").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, PUT"));
+    assert_eq!(response.into_string().unwrap(), "GET HEAD PUT");
+
+    let response = client.get("/items").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(response.headers().get_one("Allow"), Some("POST"));
+}
+
+#[test]
+fn unmatched_or_forwarded_requests_are_not_found() {
+    let client = client(true);
+
+    // No route matches the path under any method.
+    assert_eq!(client.delete("/nothing").dispatch().status(), Status::NotFound);
+
+    // A `GET` route matches but forwards: the method is allowed.
+    assert_eq!(client.get("/item/bob").dispatch().status(), Status::NotFound);
+}
@@ -0,0 +1,106 @@
+#[macro_use] extern crate rocket;
+
+use rocket::form::Multipart;
+use rocket::http::Status;
+use rocket::tokio::io::AsyncReadExt;
+
+#[post("/", data = "<form>")]
+async fn fields(mut form: Multipart) -> Result<String, Status> {
+    let mut fields = vec![];
+    while let Some(mut field) = form.next_field().await.map_err(|e| e.status())? {
+        let mut data = String::new();
+        field.read_to_string(&mut data).await.map_err(|_| Status::BadRequest)?;
+        fields.push(format!("{}|{}|{}|{}|{}|{}",
+            field.name().unwrap_or("-"),
+            field.file_name().map_or("-", |f| f.dangerous_unsafe_unsanitized_raw().as_str()),
+            field.content_type().map_or("-".into(), |ct| ct.to_string()),
+            field.headers().get_one("X-Extra").unwrap_or("-"),
+            field.limit().as_u64(),
+            data));
+    }
+
+    Ok(fields.join("\n"))
+}
+
+#[post("/skip", data = "<form>")]
+async fn skip(mut form: Multipart) -> Result<String, Status> {
+    let mut names = vec![];
+    while let Some(field) = form.next_field().await.map_err(|e| e.status())? {
+        names.push(field.name().unwrap_or("-").to_string());
+    }
+
+    Ok(names.join(","))
+}
+
+mod multipart_streaming_tests {
+    use rocket::local::blocking::Client;
+    use rocket::http::{ContentType, Status};
+    use rocket::data::{Limits, ToByteUnit};
+
+    const BODY: &str = "--X-BOUNDARY\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\
+        \r\n\
+        hello\r\n\
+        --X-BOUNDARY\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+        Content-Type: text/plain\r\n\
+        X-Extra: yes\r\n\
+        \r\n\
+        file contents\r\n\
+        --X-BOUNDARY--\r\n";
+
+    fn client(limits: Limits) -> Client {
+        let mut config = rocket::Config::debug_default();
+        config.limits = limits;
+        let rocket = rocket::custom(config).mount("/", routes![super::fields, super::skip]);
+        Client::debug(rocket).unwrap()
+    }
+
+    fn form_data() -> ContentType {
+        ContentType::with_params("multipart", "form-data", ("boundary", "X-BOUNDARY"))
+    }
+
+    #[test]
+    fn streams_fields_in_order() {
+        let limits = Limits::default().limit("string", 64.bytes()).limit("file/txt", 32.bytes());
+        let client = client(limits);
+        let response = client.post("/").header(form_data()).body(BODY).dispatch();
+        assert_eq!(response.into_string().unwrap(), "title|-|-|-|64|hello\n\
+            file|a.txt|text/plain|yes|32|file contents");
+    }
+
+    #[test]
+    fn field_data_is_limited() {
+        let limits = Limits::default().limit("file", 13.bytes());
+        let client = client(limits);
+        let response = client.post("/").header(form_data()).body(BODY).dispatch();
+        let body = response.into_string().unwrap();
+        assert!(body.ends_with("file|a.txt|text/plain|yes|13|file contents"));
+
+        let limits = Limits::default().limit("file", 4.bytes());
+        let client = client(limits);
+        let response = client.post("/").header(form_data()).body(BODY).dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn unread_fields_are_skipped() {
+        let client = client(Limits::default());
+        let response = client.post("/skip").header(form_data()).body(BODY).dispatch();
+        assert_eq!(response.into_string().unwrap(), "title,file");
+    }
+
+    #[test]
+    fn forwards_non_multipart() {
+        let client = client(Limits::default());
+        let response = client.post("/").header(ContentType::Form).body("a=b").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.post("/")
+            .header(ContentType::new("multipart", "form-data"))
+            .body(BODY)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "decompression")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::form::Form;
+
+#[post("/", data = "<text>")]
+fn text(text: String) -> String {
+    text
+}
+
+#[post("/form", data = "<form>")]
+fn form(form: Form<String>) -> String {
+    form.into_inner()
+}
+
+mod request_decompression_tests {
+    use super::*;
+    use rocket::data::{Decompression, Limits, ToByteUnit};
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::blocking::Client;
+
+    const HELLO: &str = "hello, world!";
+
+    const HELLO_GZIP: &[u8] = &[
+        31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 203, 72, 205, 201, 201, 215, 81, 40, 207, 47, 202,
+        73, 81, 4, 0, 19, 141, 152, 88, 13, 0, 0, 0
+    ];
+
+    const HELLO_ZLIB: &[u8] = &[
+        120, 156, 203, 72, 205, 201, 201, 215, 81, 40, 207, 47, 202, 73, 81, 4, 0, 33, 254,
+        4, 170
+    ];
+
+    const HELLO_DEFLATE: &[u8] = &[
+        203, 72, 205, 201, 201, 215, 81, 40, 207, 47, 202, 73, 81, 4, 0
+    ];
+
+    // An uncompressed meta-block containing `HELLO` followed by an empty one.
+    const HELLO_BROTLI: &[u8] = &[
+        0xc0, 0x00, 0x10, b'h', b'e', b'l', b'l', b'o', b',', b' ', b'w', b'o', b'r', b'l',
+        b'd', b'!', 0x03
+    ];
+
+    // 4KiB of `a`s.
+    const BOMB_GZIP: &[u8] = &[
+        31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 237, 193, 1, 13, 0, 0, 0, 194, 160, 172, 239, 95,
+        194, 30, 14, 40, 0, 0, 0, 224, 221, 0, 115, 220, 153, 156, 0, 16, 0, 0
+    ];
+
+    fn client() -> Client {
+        let mut config = rocket::Config::debug_default();
+        config.limits = Limits::default().limit("string", 1.kibibytes());
+        let rocket = rocket::custom(config)
+            .attach(Decompression::fairing())
+            .mount("/", routes![text, form]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn post(client: &Client, encoding: &'static str, body: &[u8]) -> (Status, Option<String>) {
+        let response = client.post("/")
+            .header(Header::new("Content-Encoding", encoding))
+            .body(body)
+            .dispatch();
+
+        (response.status(), response.into_string())
+    }
+
+    #[test]
+    fn bodies_are_decompressed() {
+        let client = client();
+        let expected = (Status::Ok, Some(HELLO.to_string()));
+        assert_eq!(post(&client, "gzip", HELLO_GZIP), expected);
+        assert_eq!(post(&client, "x-gzip", HELLO_GZIP), expected);
+        assert_eq!(post(&client, "deflate", HELLO_ZLIB), expected);
+        assert_eq!(post(&client, "deflate", HELLO_DEFLATE), expected);
+        assert_eq!(post(&client, "br", HELLO_BROTLI), expected);
+        assert_eq!(post(&client, "identity", HELLO.as_bytes()), expected);
+
+        let response = client.post("/").body(HELLO).dispatch();
+        assert_eq!(response.into_string().unwrap(), HELLO);
+    }
+
+    #[test]
+    fn forms_see_decompressed_data() {
+        // `value=hello`
+        const FORM_GZIP: &[u8] = &[
+            31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 43, 75, 204, 41, 77, 181, 205, 72, 205, 201, 201,
+            7, 0, 252, 141, 138, 140, 11, 0, 0, 0
+        ];
+
+        let client = client();
+        let response = client.post("/form")
+            .header(ContentType::Form)
+            .header(Header::new("Content-Encoding", "gzip"))
+            .body(FORM_GZIP)
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "hello");
+    }
+
+    #[test]
+    fn limits_apply_to_decompressed_data() {
+        let client = client();
+        assert_eq!(BOMB_GZIP.len(), 40);
+        assert_eq!(post(&client, "gzip", BOMB_GZIP).0, Status::BadRequest);
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        let client = client();
+        assert_eq!(post(&client, "compress", HELLO.as_bytes()).0, Status::UnsupportedMediaType);
+        assert_ne!(post(&client, "gzip", HELLO.as_bytes()).0, Status::Ok);
+    }
+}
@@ -0,0 +1,41 @@
+#[macro_use] extern crate rocket;
+
+use rocket::local::blocking::Client;
+use rocket::http::Status;
+
+#[get("/user/<id:u64>")]
+fn user(id: u64) -> String { format!("id: {}", id) }
+
+#[get("/user/<name:[a-z][a-z0-9_]*>")]
+fn user_by_name(name: &str) -> String { format!("name: {}", name) }
+
+#[get("/flag/<flag:bool>")]
+fn flag(flag: bool) -> String { flag.to_string() }
+
+#[test]
+fn constrained_routes_do_not_collide() {
+    let rocket = rocket::build().mount("/", routes![user, user_by_name, flag]);
+    let client = Client::debug(rocket).unwrap();
+
+    let response = client.get("/user/123").dispatch();
+    assert_eq!(response.into_string().unwrap(), "id: 123");
+
+    let response = client.get("/user/bob_1").dispatch();
+    assert_eq!(response.into_string().unwrap(), "name: bob_1");
+
+    let response = client.get("/flag/true").dispatch();
+    assert_eq!(response.into_string().unwrap(), "true");
+
+    for uri in &["/user/Bob", "/user/-1", "/user/1bob", "/flag/1"] {
+        assert_eq!(client.get(*uri).dispatch().status(), Status::NotFound);
+    }
+}
+
+#[test]
+fn overlapping_constraints_collide() {
+    #[get("/user/<id:[0-9a-f]+>")]
+    fn hex(id: &str) -> &str { id }
+
+    let rocket = rocket::build().mount("/", routes![user, hex]);
+    assert!(Client::debug(rocket).is_err());
+}
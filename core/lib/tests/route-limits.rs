@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::{Limits, ToByteUnit};
+use rocket::form::Form;
+
+#[post("/small", data = "<form>")]
+fn small(form: Form<String>) -> String {
+    form.into_inner()
+}
+
+#[post("/large", data = "<form>", limits(form = "64B"))]
+fn large(form: Form<String>) -> String {
+    form.into_inner()
+}
+
+#[post("/string", data = "<string>", limits("string" = "4B"))]
+fn string(string: String) -> String {
+    string
+}
+
+#[get("/file", limits(file = "32B"))]
+fn file_limits(limits: &Limits) -> String {
+    let get = |name| limits.get(name).map_or(0, |limit| limit.as_u64());
+    format!("{}, {}", get("file"), get("file/pdf"))
+}
+
+#[get("/limits", limits(form = "1KiB", "file/pdf" = "2MiB", custom = "3 KB"))]
+fn route_limits(limits: &Limits) -> String {
+    let get = |name| limits.get(name).map_or(0, |limit| limit.as_u64());
+    format!("{}, {}, {}, {}", get("form"), get("file/pdf"), get("file/png"), get("custom"))
+}
+
+mod route_limits_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::{ContentType, Status};
+
+    fn client() -> Client {
+        let mut config = rocket::Config::debug_default();
+        config.limits = Limits::default()
+            .limit("form", 8.bytes())
+            .limit("file", 16.bytes())
+            .limit("file/pdf", 4.bytes());
+
+        let routes = routes![small, large, string, route_limits, file_limits];
+        let rocket = rocket::custom(config).mount("/", routes);
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn route_limits_override_configured_limits() {
+        let client = client();
+        let body = "value=Hello+world";
+        let response = client.post("/small").header(ContentType::Form).body(body).dispatch();
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+
+        let response = client.post("/large").header(ContentType::Form).body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "Hello world");
+
+        let response = client.post("/string").body("abc").dispatch();
+        assert_eq!(response.into_string().unwrap(), "abc");
+
+        let response = client.post("/string").body("abcdef").dispatch();
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+    }
+
+    #[test]
+    fn route_limits_are_merged() {
+        let client = client();
+        let response = client.get("/limits").dispatch();
+        assert_eq!(response.into_string().unwrap(), "1024, 2097152, 16, 3000");
+    }
+
+    #[test]
+    fn route_limits_override_configured_sublimits() {
+        let client = client();
+        let response = client.get("/file").dispatch();
+        assert_eq!(response.into_string().unwrap(), "32, 32");
+    }
+
+    #[test]
+    fn route_attribute_sets_limits() {
+        let routes = routes![small, large];
+        assert!(routes[0].limits.is_none());
+        assert_eq!(routes[1].limits, Some(Limits::new().limit("form", 64.bytes())));
+    }
+}
@@ -0,0 +1,98 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Route};
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+use rocket::http::{Header, Status};
+
+struct Public;
+
+struct Scope(&'static str);
+
+struct Deprecated { sunset: &'static str }
+
+struct Admin;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admin {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let scope = req.route().and_then(|r| r.meta.get::<Scope>());
+        match scope.map(|s| s.0) == req.headers().get_one("X-Scope") {
+            true => request::Outcome::Success(Admin),
+            false => request::Outcome::Failure((Status::Forbidden, ())),
+        }
+    }
+}
+
+#[get("/", meta(Public))]
+fn index() -> &'static str { "index" }
+
+#[get("/admin", meta(Scope("admin")))]
+fn admin(_admin: Admin) -> &'static str { "admin" }
+
+#[route(GET, uri = "/old", meta(Public, Deprecated { sunset: "tomorrow" }))]
+fn old() -> &'static str { "old" }
+
+#[get("/plain")]
+fn plain(route: &Route) -> String { route.meta.is_empty().to_string() }
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![index, admin, old, plain])
+        .attach(AdHoc::on_response("Metadata", |req, res| Box::pin(async move {
+            let meta = match req.route() {
+                Some(route) => &route.meta,
+                None => return,
+            };
+
+            if meta.contains::<Public>() {
+                res.set_header(Header::new("X-Public", "true"));
+            }
+
+            if let Some(deprecated) = meta.get::<Deprecated>() {
+                res.set_header(Header::new("Sunset", deprecated.sunset));
+            }
+        })));
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn route_meta_is_set_from_attribute() {
+    let routes = routes![index, admin, old, plain];
+    assert!(routes[0].meta.contains::<Public>());
+    assert_eq!(routes[1].meta.get::<Scope>().map(|s| s.0), Some("admin"));
+    assert!(routes[2].meta.contains::<Public>() && routes[2].meta.contains::<Deprecated>());
+    assert!(routes[3].meta.is_empty());
+}
+
+#[test]
+fn route_meta_is_visible_to_fairings() {
+    let client = client();
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("X-Public"), Some("true"));
+    assert!(response.headers().get_one("Sunset").is_none());
+
+    let response = client.get("/old").dispatch();
+    assert_eq!(response.headers().get_one("X-Public"), Some("true"));
+    assert_eq!(response.headers().get_one("Sunset"), Some("tomorrow"));
+
+    let response = client.get("/plain").dispatch();
+    assert!(response.headers().get_one("X-Public").is_none());
+    assert_eq!(response.into_string().unwrap(), "true");
+}
+
+#[test]
+fn route_meta_is_visible_to_guards() {
+    let client = client();
+
+    let response = client.get("/admin").header(Header::new("X-Scope", "admin")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "admin");
+
+    let response = client.get("/admin").header(Header::new("X-Scope", "user")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+}
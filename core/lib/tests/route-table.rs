@@ -0,0 +1,79 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Config, Route};
+use rocket::http::{Method, Status, ContentType};
+use rocket::local::blocking::Client;
+use rocket::route::{RouteTable, dummy_handler};
+
+#[get("/items?<page>&sort=asc", rank = 3)]
+fn items(page: usize) -> String { page.to_string() }
+
+#[post("/items", format = "json", data = "<body>")]
+fn new_item(body: &str) -> &str { body }
+
+#[get("/items/<id>", host = "api.rocket.rs")]
+fn item(id: usize) -> String { id.to_string() }
+
+fn rocket() -> rocket::Rocket<rocket::Build> {
+    rocket::build()
+        .mount("/api", routes![items, new_item, item])
+        .mount("/", vec![Route::new(Method::Delete, "/manual", dummy_handler)])
+        .mount("/_routes", RouteTable::routes())
+}
+
+#[test]
+fn route_table_entries() {
+    let client = Client::debug(rocket()).unwrap();
+    let table = client.rocket().route_table();
+    let entries = table.entries();
+
+    let paths: Vec<_> = entries.iter().map(|e| (e.method, e.path.as_str())).collect();
+    assert_eq!(paths, &[
+        ("GET", "/_routes"),
+        ("GET", "/api/items"),
+        ("POST", "/api/items"),
+        ("GET", "/api/items/<id>"),
+        ("DELETE", "/manual"),
+    ]);
+
+    let items = &entries[1];
+    assert_eq!(items.query, &["<page>", "sort=asc"]);
+    assert_eq!(items.rank, 3);
+    assert_eq!(items.name.as_deref(), Some("items"));
+    assert!(items.format.is_none() && items.host.is_none());
+
+    let location = items.location.as_ref().unwrap();
+    assert!(location.file.ends_with("route-table.rs"));
+    assert_eq!(location.line, 9);
+
+    assert_eq!(entries[2].format.as_deref(), Some("application/json"));
+    assert_eq!(entries[3].host.as_deref(), Some("api.rocket.rs"));
+    assert!(entries[4].name.is_none() && entries[4].location.is_none());
+}
+
+#[test]
+fn route_table_text() {
+    let client = Client::debug(rocket()).unwrap();
+    let text = client.rocket().route_table().to_string();
+    let lines: Vec<_> = text.lines().collect();
+
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[0], "method\tpath\tquery\trank\tformat\thost\tname\tlocation");
+    assert!(lines[2].starts_with("GET\t/api/items\t<page>&sort=asc\t3\t-\t-\titems\t"));
+    assert!(lines[2].ends_with("route-table.rs:9:4"));
+    assert_eq!(lines[5], "DELETE\t/manual\t-\t-9\t-\t-\t-\t-");
+    assert!(lines.iter().skip(1).all(|l| l.split('\t').count() == 8));
+}
+
+#[test]
+fn route_table_route_is_debug_only() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/_routes").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_string().unwrap(), client.rocket().route_table().to_string());
+
+    let figment = Config::figment().select("staging");
+    let client = Client::tracked(rocket().configure(figment)).unwrap();
+    assert_eq!(client.get("/_routes").dispatch().status(), Status::NotFound);
+}
@@ -0,0 +1,101 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Route, Request, Data, route};
+use rocket::fairing::AdHoc;
+use rocket::http::{Method, Status};
+use rocket::local::blocking::Client;
+use rocket::route::Routing;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/<name>")]
+fn hello(name: &str) -> String { format!("Hello, {}!", name) }
+
+#[get("/", rank = 5)]
+fn fallback() -> &'static str { "fallback" }
+
+fn remove_self<'r>(req: &'r Request<'_>, _: Data) -> route::BoxFuture<'r> {
+    // Removing the route that's handling this request doesn't affect it.
+    let name = req.route().and_then(|r| r.name.clone());
+    let removed = req.rocket().routing().remove(|r| r.name == name);
+    let body = format!("removed {}, still {:?}", removed.len(), req.route().unwrap().name);
+    route::Outcome::from(req, body).pin()
+}
+
+#[test]
+fn routes_can_be_added_and_removed() {
+    let client = Client::debug(rocket::build().mount("/", routes![index])).unwrap();
+    let routing = client.rocket().routing();
+    assert_eq!(client.get("/hi/Bob").dispatch().status(), Status::NotFound);
+
+    routing.mount("/hi", routes![hello]).unwrap();
+    assert_eq!(client.get("/hi/Bob").dispatch().into_string().unwrap(), "Hello, Bob!");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "index");
+    assert_eq!(routing.routes().len(), 2);
+    assert_eq!(client.rocket().routes().count(), 1);
+
+    let removed = routing.remove(|r| r.uri.base() == "/hi");
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].uri.path(), "/hi/<name>");
+    assert_eq!(client.get("/hi/Bob").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "index");
+
+    assert!(routing.remove(|r| r.uri.base() == "/hi").is_empty());
+    assert_eq!(routing.routes().len(), 1);
+}
+
+#[test]
+fn colliding_additions_are_rejected_atomically() {
+    let client = Client::debug(rocket::build().mount("/", routes![index])).unwrap();
+    let routing = client.rocket().routing();
+
+    let collisions = routing.add(routes![hello, index]).unwrap_err();
+    assert_eq!(collisions.len(), 1);
+    assert!(collisions.iter().all(|(a, b)| a.uri == "/" && b.uri == "/"));
+
+    // Nothing from the rejected batch was added.
+    assert_eq!(routing.routes().len(), 1);
+    assert_eq!(client.get("/Bob").dispatch().status(), Status::NotFound);
+
+    // Ranked routes don't collide and are ordered by rank.
+    routing.add(routes![fallback]).unwrap();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "index");
+    routing.remove(|r| r.name.as_deref() == Some("index"));
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "fallback");
+}
+
+#[test]
+fn in_flight_requests_are_unaffected() {
+    let mut route = Route::new(Method::Get, "/remove", remove_self);
+    route.name = Some("remove_self".into());
+
+    let client = Client::debug(rocket::build().mount("/", vec![route])).unwrap();
+    let response = client.get("/remove").dispatch();
+    assert_eq!(response.into_string().unwrap(), r#"removed 1, still Some("remove_self")"#);
+    assert_eq!(client.get("/remove").dispatch().status(), Status::NotFound);
+}
+
+#[get("/enable")]
+fn enable(routing: Routing) -> Status {
+    match routing.mount("/plugin", routes![index]) {
+        Ok(()) => Status::Created,
+        Err(_) => Status::Conflict,
+    }
+}
+
+#[test]
+fn routing_handles_from_guards_and_fairings() {
+    let rocket = rocket::build()
+        .mount("/", routes![enable])
+        .attach(AdHoc::on_liftoff("Plugins", |rocket| Box::pin(async move {
+            rocket.routing().mount("/hi", routes![hello]).unwrap();
+        })));
+
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/hi/Alice").dispatch().into_string().unwrap(), "Hello, Alice!");
+
+    assert_eq!(client.get("/enable").dispatch().status(), Status::Created);
+    assert_eq!(client.get("/enable").dispatch().status(), Status::Conflict);
+    assert_eq!(client.get("/plugin").dispatch().into_string().unwrap(), "index");
+}
@@ -0,0 +1,57 @@
+#[macro_use] extern crate rocket;
+
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+use rocket::http::{Header, Status, uri::Origin};
+
+#[get("/<_..>")]
+fn index() -> &'static str { "index" }
+
+fn tag(name: &'static str) -> AdHoc {
+    AdHoc::on_response(name, move |_, res| Box::pin(async move {
+        res.adjoin_header(Header::new("X-Fairing", name));
+    }))
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .attach(tag("global"))
+        .attach_at("/api", tag("api"))
+        .attach_at("/api/v2/", tag("v2"))
+        .attach_at("/static", AdHoc::on_request("Rewrite", |req, _| Box::pin(async move {
+            req.set_uri(Origin::parse("/api/v2/rewritten").unwrap());
+        })));
+
+    Client::debug(rocket).unwrap()
+}
+
+fn fairings(client: &Client, path: &'static str) -> Vec<String> {
+    let response = client.get(path).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    response.headers().get("X-Fairing").map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn scoped_fairings_run_under_base() {
+    let client = client();
+    assert_eq!(fairings(&client, "/"), &["global"]);
+    assert_eq!(fairings(&client, "/apis"), &["global"]);
+    assert_eq!(fairings(&client, "/admin/api"), &["global"]);
+    assert_eq!(fairings(&client, "/api"), &["global", "api"]);
+    assert_eq!(fairings(&client, "/api/items?page=2"), &["global", "api"]);
+    assert_eq!(fairings(&client, "/api/v2"), &["global", "api", "v2"]);
+    assert_eq!(fairings(&client, "/api/v2/items/1"), &["global", "api", "v2"]);
+}
+
+#[test]
+fn scoped_fairings_see_rewritten_uris() {
+    let client = client();
+    assert_eq!(fairings(&client, "/static/logo.png"), &["global", "api", "v2"]);
+}
+
+#[test]
+#[should_panic]
+fn dynamic_fairing_base_panics() {
+    let _ = rocket::build().attach_at("/<id>", tag("dynamic"));
+}
@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Response, State};
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+use rocket::http::{Header, Status};
+
+#[derive(Default)]
+struct Hits(AtomicUsize);
+
+#[get("/<_..>")]
+fn index(hits: &State<Hits>) -> &'static str {
+    hits.0.fetch_add(1, Ordering::SeqCst);
+    "index"
+}
+
+#[catch(503)]
+fn unavailable() -> &'static str { "down for maintenance" }
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .register("/", catchers![unavailable])
+        .manage(Hits::default())
+        .attach(AdHoc::on_request("Maintenance", |req, _| Box::pin(async move {
+            if req.headers().contains("X-Maintenance") {
+                req.short_circuit(Status::ServiceUnavailable);
+            }
+        })))
+        .attach_at("/ping", AdHoc::on_request("Pong", |req, _| Box::pin(async move {
+            let body = "pong";
+            let response = Response::build()
+                .status(Status::Accepted)
+                .sized_body(body.len(), std::io::Cursor::new(body))
+                .finalize();
+
+            req.short_circuit(response);
+        })))
+        .attach(AdHoc::on_request("Late", |req, _| Box::pin(async move {
+            req.add_header(Header::new("X-Late", "true"));
+        })))
+        .attach(AdHoc::on_response("Tag", |req, res| Box::pin(async move {
+            let late = req.headers().contains("X-Late").to_string();
+            res.set_header(Header::new("X-Late-Ran", late));
+        })));
+
+    Client::debug(rocket).unwrap()
+}
+
+fn hits(client: &Client) -> usize {
+    client.rocket().state::<Hits>().unwrap().0.load(Ordering::SeqCst)
+}
+
+#[test]
+fn requests_are_routed_when_not_short_circuited() {
+    let client = client();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("X-Late-Ran"), Some("true"));
+    assert_eq!(response.into_string().unwrap(), "index");
+    assert_eq!(hits(&client), 1);
+}
+
+#[test]
+fn status_short_circuit_invokes_catcher() {
+    let client = client();
+    let response = client.get("/").header(Header::new("X-Maintenance", "1")).dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.headers().get_one("X-Late-Ran"), Some("false"));
+    assert_eq!(response.into_string().unwrap(), "down for maintenance");
+    assert_eq!(hits(&client), 0);
+}
+
+#[test]
+fn response_short_circuit_is_used_as_is() {
+    let client = client();
+    let response = client.get("/ping").dispatch();
+    assert_eq!(response.status(), Status::Accepted);
+    assert_eq!(response.headers().get_one("X-Late-Ran"), Some("false"));
+    assert_eq!(response.into_string().unwrap(), "pong");
+
+    let response = client.head("/ping").dispatch();
+    assert_eq!(response.status(), Status::Accepted);
+    assert!(response.into_string().unwrap_or_default().is_empty());
+    assert_eq!(hits(&client), 0);
+
+    assert_eq!(client.get("/pings").dispatch().into_string().unwrap(), "index");
+    assert_eq!(hits(&client), 1);
+}
@@ -0,0 +1,146 @@
+#[macro_use] extern crate rocket;
+
+use std::task::{Context, Poll};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::config::Config;
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::futures::future::BoxFuture;
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+use rocket::tower::{Layer, Service, HyperService, HyperRequest, HyperResponse, BoxError};
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+static LAYERED: AtomicUsize = AtomicUsize::new(0);
+
+struct Tag(&'static str);
+
+#[derive(Clone)]
+struct Tagged(&'static str, HyperService);
+
+impl Layer<HyperService> for Tag {
+    type Service = Tagged;
+
+    fn layer(&self, inner: HyperService) -> Tagged {
+        LAYERED.fetch_add(1, Ordering::SeqCst);
+        Tagged(self.0, inner)
+    }
+}
+
+impl Service<HyperRequest> for Tagged {
+    type Response = HyperResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<HyperResponse, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.1.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HyperRequest) -> Self::Future {
+        let tag = self.0;
+        let response = self.1.call(request);
+        Box::pin(async move {
+            let mut response = response.await?;
+            response.headers_mut().append("X-Layer", tag.parse()?);
+            Ok(response)
+        })
+    }
+}
+
+#[rocket::async_test]
+async fn layers_wrap_the_hyper_service() {
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![index])
+        .layer(Tag("outer"))
+        .layer(Tag("inner"))
+        .attach(AdHoc::on_liftoff("Send Port -> Channel", move |rocket| {
+            Box::pin(async move {
+                tx.send(rocket.config().port).unwrap();
+            })
+        }));
+
+    rocket::tokio::spawn(rocket.launch());
+    let port = rx.await.unwrap();
+
+    for _ in 0..2 {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request = "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let response = response.to_lowercase();
+        assert!(response.starts_with("http/1.1 200 ok"));
+        assert!(response.ends_with("index"));
+
+        // The inner layer sees the response first, so its header is added first.
+        let inner = response.find("x-layer: inner").unwrap();
+        let outer = response.find("x-layer: outer").unwrap();
+        assert!(inner < outer);
+    }
+
+    // Each layer is applied once, not once per connection.
+    assert_eq!(LAYERED.load(Ordering::SeqCst), 2);
+}
+
+struct Reject;
+
+#[derive(Clone)]
+struct Rejecting(HyperService);
+
+impl Layer<HyperService> for Reject {
+    type Service = Rejecting;
+
+    fn layer(&self, inner: HyperService) -> Rejecting {
+        Rejecting(inner)
+    }
+}
+
+impl Service<HyperRequest> for Rejecting {
+    type Response = HyperResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<HyperResponse, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HyperRequest) -> Self::Future {
+        if request.uri().path() == "/reject" {
+            return Box::pin(async { Err("overloaded".into()) });
+        }
+
+        self.0.call(request)
+    }
+}
+
+#[rocket::async_test]
+async fn layer_errors_are_responses() {
+    let (tx, rx) = oneshot::channel();
+    let rocket = rocket::custom(Config { port: 0, ..Config::debug_default() })
+        .mount("/", routes![index])
+        .layer(Reject)
+        .attach(AdHoc::on_liftoff("Send Port -> Channel", move |rocket| {
+            Box::pin(async move {
+                tx.send(rocket.config().port).unwrap();
+            })
+        }));
+
+    rocket::tokio::spawn(rocket.launch());
+    let port = rx.await.unwrap();
+
+    for (path, status) in &[("/reject", "503 service unavailable"), ("/", "200 ok")] {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\
+            Connection: close\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.to_lowercase().starts_with(&format!("http/1.1 {}", status)));
+    }
+}
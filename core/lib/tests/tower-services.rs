@@ -0,0 +1,130 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::http::hyper::{self, HttpBody};
+use rocket::local::blocking::Client;
+use rocket::tower::{Service, ServiceHandler, HyperRequest, HyperResponse};
+
+async fn echo(mut request: HyperRequest) -> Result<HyperResponse, hyper::Error> {
+    let mut body = request.uri().to_string();
+    while let Some(chunk) = request.body_mut().data().await {
+        body.push_str(std::str::from_utf8(&chunk?).unwrap());
+    }
+
+    let mut response = hyper::Response::new(body.into());
+    *response.status_mut() = hyper::StatusCode::ACCEPTED;
+    if let Some(value) = request.headers().get("X-Echo") {
+        response.headers_mut().insert("X-Echo", value.clone());
+    }
+
+    let hop_by_hop = ["Content-Length", "Connection", "Keep-Alive", "X-Hop"];
+    let forwarded = hop_by_hop.iter().filter(|name| request.headers().contains_key(**name));
+    response.headers_mut().insert("X-Hop-Headers", forwarded.count().into());
+
+    Ok(response)
+}
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[test]
+fn services_serve_requests_under_their_base() {
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .mount("/legacy", ServiceHandler::new(hyper::service_fn(echo)));
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let response = client.get("/legacy").dispatch();
+    assert_eq!(response.status(), Status::Accepted);
+    assert_eq!(response.into_string().unwrap(), "/");
+
+    let response = client.post("/legacy/a/b?c=d")
+        .header(Header::new("X-Echo", "hi"))
+        .body(" and a body")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Accepted);
+    assert_eq!(response.headers().get_one("X-Echo"), Some("hi"));
+    assert_eq!(response.into_string().unwrap(), "/a/b?c=d and a body");
+
+    let response = client.get("/legacy/a%2Fb/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "/a%2Fb/");
+}
+
+#[test]
+fn service_bodies_are_limited() {
+    let rocket = rocket::build().mount("/", ServiceHandler::new(hyper::service_fn(echo)));
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/").body(vec![b'a'; 8 * 1024]).dispatch();
+    assert_eq!(response.into_string().unwrap().len(), 1 + 8 * 1024);
+
+    let response = client.post("/")
+        .header(Header::new("Content-Length", (10 * 1024).to_string()))
+        .body(vec![b'a'; 10 * 1024])
+        .dispatch();
+
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let response = client.post("/").body(vec![b'a'; 10 * 1024]).dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn service_requests_drop_hop_by_hop_headers() {
+    let rocket = rocket::build().mount("/", ServiceHandler::new(hyper::service_fn(echo)));
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/")
+        .header(Header::new("Content-Length", "4"))
+        .header(Header::new("Connection", "keep-alive, X-Hop"))
+        .header(Header::new("Keep-Alive", "timeout=5"))
+        .header(Header::new("X-Hop", "1"))
+        .header(Header::new("X-Echo", "kept"))
+        .body("body")
+        .dispatch();
+
+    assert_eq!(response.headers().get_one("X-Echo"), Some("kept"));
+    assert_eq!(response.headers().get_one("X-Hop-Headers"), Some("0"));
+    assert_eq!(response.into_string().unwrap(), "/body");
+}
+
+#[test]
+fn service_routes_are_ranked() {
+    #[get("/legacy/<_..>", rank = 20)]
+    fn fallback() -> &'static str { "fallback" }
+
+    let service = ServiceHandler::new(hyper::service_fn(echo));
+    let rocket = rocket::build()
+        .mount("/", routes![fallback])
+        .mount("/", service.rank(30));
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/legacy/a").dispatch();
+    assert_eq!(response.into_string().unwrap(), "fallback");
+}
+
+#[rocket::async_test]
+async fn rocket_serves_as_a_service() {
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .ignite().await
+        .unwrap();
+
+    let mut service = rocket.into_service().await;
+    let request = hyper::Request::get("/").body(hyper::Body::empty()).unwrap();
+    let mut response = service.call(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.body_mut().data().await {
+        body.extend_from_slice(&chunk.unwrap());
+    }
+
+    assert_eq!(body, b"index");
+
+    let request = hyper::Request::get("/unknown").body(hyper::Body::empty()).unwrap();
+    let response = service.call(request).await.unwrap();
+    assert_eq!(response.status(), 404);
+}
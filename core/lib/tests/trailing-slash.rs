@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Config;
+use rocket::config::TrailingSlash;
+use rocket::local::blocking::Client;
+use rocket::http::Status;
+
+#[get("/dir/")]
+fn dir() -> &'static str { "dir" }
+
+#[get("/file")]
+fn file() -> &'static str { "file" }
+
+#[get("/user/<id>/")]
+fn user(id: usize) -> String { id.to_string() }
+
+#[get("/static/<path..>")]
+fn files(path: std::path::PathBuf) -> String { path.display().to_string() }
+
+fn client(slash: TrailingSlash) -> Client {
+    let figment = Config::figment().merge((Config::TRAILING_SLASH, slash));
+    let rocket = rocket::custom(figment).mount("/", routes![dir, file, user, files]);
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn match_both_is_the_default() {
+    let client = Client::debug(rocket::build().mount("/", routes![dir, file])).unwrap();
+    for uri in &["/dir", "/dir/"] {
+        assert_eq!(client.get(*uri).dispatch().into_string().unwrap(), "dir");
+    }
+
+    for uri in &["/file", "/file/"] {
+        assert_eq!(client.get(*uri).dispatch().into_string().unwrap(), "file");
+    }
+}
+
+#[test]
+fn strict_requires_matching_slash() {
+    let client = client(TrailingSlash::Strict);
+    assert_eq!(client.get("/dir/").dispatch().into_string().unwrap(), "dir");
+    assert_eq!(client.get("/file").dispatch().into_string().unwrap(), "file");
+    assert_eq!(client.get("/user/7/").dispatch().into_string().unwrap(), "7");
+
+    for uri in &["/dir", "/file/", "/user/7"] {
+        assert_eq!(client.get(*uri).dispatch().status(), Status::NotFound);
+    }
+
+    // A trailing parameter matches irrespective of the slash.
+    assert_eq!(client.get("/static/a/b").dispatch().into_string().unwrap(), "a/b");
+    assert_eq!(client.get("/static/a/b/").dispatch().into_string().unwrap(), "a/b");
+}
+
+#[test]
+fn redirect_to_canonical_uri() {
+    let client = client(TrailingSlash::Redirect);
+    assert_eq!(client.get("/dir/").dispatch().into_string().unwrap(), "dir");
+
+    let response = client.get("/dir?a=b").dispatch();
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("Location"), Some("/dir/?a=b"));
+
+    let response = client.get("/file/").dispatch();
+    assert_eq!(response.status(), Status::PermanentRedirect);
+    assert_eq!(response.headers().get_one("Location"), Some("/file"));
+
+    let response = client.get("/user/7").dispatch();
+    assert_eq!(response.headers().get_one("Location"), Some("/user/7/"));
+
+    // No redirect if neither form is routed.
+    assert_eq!(client.get("/nothing/").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/user/7/x").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn strict_routes_differing_in_slash_do_not_collide() {
+    #[get("/dir")]
+    fn dir_no_slash() -> &'static str { "no slash" }
+
+    let rocket = rocket::build().mount("/", routes![dir, dir_no_slash]);
+    assert!(Client::debug(rocket).is_err());
+
+    let figment = Config::figment().merge((Config::TRAILING_SLASH, TrailingSlash::Strict));
+    let rocket = rocket::custom(figment).mount("/", routes![dir, dir_no_slash]);
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/dir").dispatch().into_string().unwrap(), "no slash");
+    assert_eq!(client.get("/dir/").dispatch().into_string().unwrap(), "dir");
+}
+
+#[test]
+fn uri_macro_keeps_trailing_slash() {
+    assert_eq!(uri!(dir), "/dir/");
+    assert_eq!(uri!(file), "/file");
+    assert_eq!(uri!(user(7)), "/user/7/");
+    assert_eq!(uri!("/api", dir), "/api/dir/");
+}
@@ -0,0 +1,93 @@
+#[macro_use] extern crate rocket;
+
+use std::net::IpAddr;
+
+use rocket::Request;
+
+#[get("/")]
+fn client(ip: IpAddr, req: &Request<'_>) -> String {
+    format!("{} {} {}", ip, req.scheme(), req.host().unwrap_or("-"))
+}
+
+mod trusted_proxies_tests {
+    use super::*;
+    use rocket::Config;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let figment = Config::figment()
+            .merge((Config::TRUSTED_PROXIES, ["10.0.0.0/8", "::1"]));
+
+        Client::debug(rocket::custom(figment).mount("/", routes![super::client])).unwrap()
+    }
+
+    fn get(client: &Client, remote: &str, headers: &[(&'static str, &'static str)]) -> String {
+        let mut request = client.get("/").remote(remote.parse().unwrap());
+        for (name, value) in headers {
+            request = request.header(Header::new(*name, *value));
+        }
+
+        request.dispatch().into_string().unwrap()
+    }
+
+    #[test]
+    fn untrusted_peers_are_not_believed() {
+        let client = client();
+        let headers = [
+            ("Host", "rocket.rs"),
+            ("X-Forwarded-For", "1.1.1.1"),
+            ("X-Forwarded-Proto", "https"),
+            ("X-Forwarded-Host", "evil.com"),
+            ("X-Real-IP", "2.2.2.2"),
+        ];
+
+        assert_eq!(get(&client, "8.8.8.8:80", &headers), "8.8.8.8 http rocket.rs");
+
+        let headers = [("Forwarded", "for=1.1.1.1;proto=https;host=evil.com")];
+        assert_eq!(get(&client, "8.8.8.8:80", &headers), "8.8.8.8 http -");
+    }
+
+    #[test]
+    fn trusted_proxies_forward_the_client() {
+        let client = client();
+        let headers = [
+            ("Host", "internal"),
+            ("X-Forwarded-For", "1.1.1.1, 10.0.0.5"),
+            ("X-Forwarded-Proto", "HTTPS"),
+            ("X-Forwarded-Host", "rocket.rs:443"),
+        ];
+
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "1.1.1.1 https rocket.rs");
+        assert_eq!(get(&client, "[::1]:80", &headers), "1.1.1.1 https rocket.rs");
+
+        let headers = [("X-Forwarded-For", "1.1.1.1, 2.2.2.2, 10.0.0.5")];
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "2.2.2.2 http -");
+
+        let headers = [("X-Forwarded-For", "10.0.0.9, 10.0.0.5")];
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "10.0.0.9 http -");
+
+        let headers = [("X-Forwarded-For", "unknown, 10.0.0.5")];
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "10.0.0.5 http -");
+
+        let headers = [("X-Real-IP", "3.3.3.3")];
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "3.3.3.3 http -");
+    }
+
+    #[test]
+    fn forwarded_header_is_preferred() {
+        let client = client();
+        let headers = [
+            ("Forwarded", "for=1.1.1.1;proto=https;host=rocket.rs, for=\"[::1]:4711\""),
+            ("X-Forwarded-For", "4.4.4.4"),
+        ];
+
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "1.1.1.1 https rocket.rs");
+
+        let headers = [("Forwarded", "for=1.1.1.1, for=2.2.2.2;proto=https")];
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "2.2.2.2 https -");
+
+        let headers = [("Forwarded", "for=_hidden;proto=https")];
+        assert_eq!(get(&client, "10.0.0.1:80", &headers), "10.0.0.1 https -");
+    }
+}
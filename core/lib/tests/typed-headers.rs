@@ -0,0 +1,169 @@
+#[macro_use] extern crate rocket;
+
+use std::fmt;
+
+use rocket::http::{Header, Status, TypedHeader, InvalidHeader};
+use rocket::http::{ETag, EntityTag, IfNoneMatch, IfModifiedSince};
+use rocket::http::{CacheControl, CacheDirective, Range};
+use rocket::response::{self, Responder, Response};
+use rocket::request::Typed;
+use rocket::Request;
+
+struct ApiVersion(u8);
+
+impl TypedHeader for ApiVersion {
+    const NAME: &'static str = "X-Api-Version";
+
+    fn parse(value: &str) -> Result<Self, InvalidHeader> {
+        value.parse()
+            .map(ApiVersion)
+            .map_err(|_| InvalidHeader::new(Self::NAME, "expected a version number"))
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+struct Tagged(&'static str);
+
+impl<'r> Responder<'r, 'static> for Tagged {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build_from(self.0.respond_to(req)?)
+            .header(ETag(EntityTag::strong("v1")))
+            .header(CacheControl(vec![CacheDirective::NoCache, CacheDirective::MaxAge(60)]))
+            .ok()
+    }
+}
+
+#[get("/")]
+fn index(if_none_match: Option<IfNoneMatch>) -> Result<Tagged, Status> {
+    match if_none_match {
+        Some(header) if header.matches(&EntityTag::strong("v1")) => Err(Status::NotModified),
+        _ => Ok(Tagged("content")),
+    }
+}
+
+#[get("/range")]
+fn range(range: Result<Range, InvalidHeader>) -> String {
+    match range {
+        Ok(range) => range.0.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(";"),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[get("/range", rank = 2)]
+fn no_range() -> &'static str {
+    "no range"
+}
+
+#[get("/strict")]
+fn strict(_etag: IfNoneMatch) { }
+
+#[get("/version")]
+fn version(version: Typed<ApiVersion>) -> String {
+    version.into_inner().to_string()
+}
+
+#[get("/modified")]
+fn modified(since: IfModifiedSince) -> String {
+    since.0.unix_timestamp().to_string()
+}
+
+#[get("/modified", rank = 2)]
+fn unconditional() -> &'static str {
+    "unconditional"
+}
+
+mod typed_headers_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build().mount("/", routes![
+            index, range, no_range, strict, version, modified, unconditional
+        ]);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn typed_headers_are_set_on_responses() {
+        let client = client();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("ETag"), Some("\"v1\""));
+        assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache, max-age=60"));
+    }
+
+    #[test]
+    fn typed_header_guards() {
+        let client = client();
+        let response = client.get("/")
+            .header(Header::new("If-None-Match", "W/\"v0\""))
+            .header(Header::new("If-None-Match", "W/\"v1\""))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotModified);
+
+        let response = client.get("/").header(Header::new("If-None-Match", "\"v2\"")).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/range").dispatch();
+        assert_eq!(response.into_string().unwrap(), "no range");
+
+        let header = Header::new("Range", "bytes=0-1, -5");
+        let response = client.get("/range").header(header).dispatch();
+        assert_eq!(response.into_string().unwrap(), "0-1;-5");
+
+        let header = Header::new("Range", "lines=1-2");
+        let response = client.get("/range").header(header).dispatch();
+        let error = response.into_string().unwrap();
+        assert_eq!(error, "invalid Range header: expected a `bytes` range");
+    }
+
+    #[test]
+    fn invalid_typed_headers_fail() {
+        let client = client();
+        let response = client.get("/strict").header(Header::new("If-None-Match", "v1")).dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/strict").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn custom_typed_header_guards() {
+        let client = client();
+        let response = client.get("/version").header(Header::new("X-Api-Version", "3")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "3");
+
+        let response = client.get("/version").header(Header::new("X-Api-Version", "x")).dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/version").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn if_modified_since_guard() {
+        let client = client();
+        let dates = [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ];
+
+        for date in dates.iter() {
+            let header = Header::new("If-Modified-Since", *date);
+            let response = client.get("/modified").header(header).dispatch();
+            assert_eq!(response.into_string().unwrap(), "784111777");
+        }
+
+        let header = Header::new("If-Modified-Since", "yesterday");
+        let response = client.get("/modified").header(header).dispatch();
+        assert_eq!(response.into_string().unwrap(), "unconditional");
+    }
+}
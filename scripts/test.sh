@@ -66,6 +66,9 @@ function test_contrib() {
     handlebars_templates
     serve
     helmet
+    cors
+    openapi
+    webhook
     diesel_postgres_pool
     diesel_sqlite_pool
     diesel_mysql_pool
@@ -92,6 +95,8 @@ function test_core() {
   FEATURES=(
     secrets
     tls
+    decompression
+    operations
   )
 
   pushd "${CORE_LIB_ROOT}" > /dev/null 2>&1